@@ -1,5 +1,6 @@
 #![no_std]
 #![feature(doc_cfg)]
+#![cfg_attr(feature = "time", feature(abi_avr_interrupt))]
 
 //! `arduino-hal`
 //! =============
@@ -149,37 +150,114 @@ pub mod i2c {
     pub use crate::hal::i2c::*;
 
     pub type I2c = crate::hal::i2c::I2c<crate::DefaultClock>;
+    pub type IsrI2c<const N: usize> = crate::hal::i2c::IsrI2c<crate::DefaultClock, N>;
+    pub type SoftI2c<SDAPIN, SCLPIN> =
+        crate::hal::i2c::SoftI2c<crate::DefaultClock, SDAPIN, SCLPIN>;
 }
 #[doc(no_inline)]
 #[cfg(feature = "mcu-atmega")]
 pub use i2c::I2c;
 
+/// I2C bus controller and target using the USI.
+#[cfg(feature = "mcu-attiny")]
+pub mod i2c {
+    pub use crate::hal::i2c::*;
+
+    pub type I2c = crate::hal::i2c::I2c<crate::DefaultClock>;
+    pub type SoftI2c<SDAPIN, SCLPIN> =
+        crate::hal::i2c::SoftI2c<crate::DefaultClock, SDAPIN, SCLPIN>;
+}
+#[doc(no_inline)]
+#[cfg(feature = "mcu-attiny")]
+pub use i2c::I2c;
+
 /// SPI controller.
 #[cfg(feature = "mcu-atmega")]
 pub mod spi {
     pub use crate::hal::spi::*;
 
     pub type Spi = crate::hal::spi::Spi;
+    pub type SoftSpi<SCLKPIN, MOSIPIN, MISOPIN> =
+        crate::hal::spi::SoftSpi<crate::DefaultClock, SCLKPIN, MOSIPIN, MISOPIN>;
 }
 #[doc(no_inline)]
 #[cfg(feature = "mcu-atmega")]
 pub use spi::Spi;
 
+/// SPI controller using the USI.
+#[cfg(feature = "mcu-attiny")]
+pub mod spi {
+    pub use crate::hal::spi::*;
+
+    pub type UsiSpi = crate::hal::spi::UsiSpi<crate::DefaultClock>;
+    pub type SoftSpi<SCLKPIN, MOSIPIN, MISOPIN> =
+        crate::hal::spi::SoftSpi<crate::DefaultClock, SCLKPIN, MOSIPIN, MISOPIN>;
+}
+
 #[cfg(feature = "mcu-atmega")]
 pub mod usart {
-    pub use crate::hal::usart::{Baudrate, UsartOps};
+    pub use crate::hal::usart::{
+        Baudrate, DataBits, Error, FrameFormat, NoDriverEnable, Parity, StopBits, UsartBuffers,
+        UsartOps, UsartSettings, UsartSpiOps,
+    };
 
     pub type Usart<USART, RX, TX> = crate::hal::usart::Usart<USART, RX, TX, crate::DefaultClock>;
     pub type UsartWriter<USART, RX, TX> =
         crate::hal::usart::UsartWriter<USART, RX, TX, crate::DefaultClock>;
     pub type UsartReader<USART, RX, TX> =
         crate::hal::usart::UsartReader<USART, RX, TX, crate::DefaultClock>;
+    pub type BufferedUsart<
+        USART,
+        RX,
+        TX,
+        const RX_SIZE: usize,
+        const TX_SIZE: usize,
+        DE = NoDriverEnable,
+    > = crate::hal::usart::BufferedUsart<USART, RX, TX, crate::DefaultClock, RX_SIZE, TX_SIZE, DE>;
+    pub type Rs485Usart<USART, RX, TX, DE> =
+        crate::hal::usart::Rs485Usart<USART, RX, TX, DE, crate::DefaultClock>;
+    pub type UsartSpi<USART, SCLKPIN, MOSIPIN, MISOPIN> =
+        crate::hal::usart::UsartSpi<USART, SCLKPIN, MOSIPIN, MISOPIN>;
+    pub use crate::hal::usart::SoftUartBuffers;
+    pub type SoftUart<RXPIN, TXPIN> =
+        crate::hal::usart::SoftUart<crate::DefaultClock, RXPIN, TXPIN>;
+    pub type BufferedSoftUart<RXPIN, TXPIN, const N: usize> =
+        crate::hal::usart::BufferedSoftUart<crate::DefaultClock, RXPIN, TXPIN, N>;
+}
+
+/// Software UART for boards without a USART.
+#[cfg(feature = "mcu-attiny")]
+pub mod usart {
+    pub use crate::hal::usart::{Error, SoftUartBuffers};
+
+    pub type SoftUart<RXPIN, TXPIN> =
+        crate::hal::usart::SoftUart<crate::DefaultClock, RXPIN, TXPIN>;
+    pub type BufferedSoftUart<RXPIN, TXPIN, const N: usize> =
+        crate::hal::usart::BufferedSoftUart<crate::DefaultClock, RXPIN, TXPIN, N>;
 }
 
 #[doc(no_inline)]
 #[cfg(feature = "mcu-atmega")]
 pub use usart::Usart;
 
+/// External interrupts (`INTn`).
+#[cfg(feature = "mcu-atmega")]
+pub mod exint {
+    pub use crate::hal::exint::*;
+}
+#[doc(no_inline)]
+#[cfg(feature = "mcu-atmega")]
+pub use exint::Exint;
+
+/// Pin change interrupts (`PCINTn`).
+#[cfg(feature = "board-selected")]
+pub mod pcint {
+    pub use crate::hal::pcint::*;
+}
+#[doc(no_inline)]
+#[cfg(feature = "board-selected")]
+pub use pcint::Pcint;
+
 #[cfg(feature = "board-selected")]
 pub mod eeprom {
     pub use crate::hal::eeprom::{Eeprom, EepromOps, OutOfBoundsError};
@@ -193,10 +271,46 @@ pub mod simple_pwm {
     #[cfg(feature = "mcu-atmega")]
     pub use atmega_hal::simple_pwm::*;
 
+    /// Check the [`avr_hal_generic::simple_pwm::Pwm16`] documentation.
+    #[cfg(feature = "mcu-atmega")]
+    pub type Pwm16<TC> = atmega_hal::simple_pwm::Pwm16<TC, crate::DefaultClock>;
+
     #[cfg(feature = "mcu-attiny")]
     pub use attiny_hal::simple_pwm::*;
 }
 
+/// Timer/Counter peripherals.
+#[cfg(feature = "board-selected")]
+pub mod timer {
+    pub use crate::hal::timer::*;
+
+    /// Check the [`avr_hal_generic::timer::Timer`] documentation.
+    pub type Timer<TC> = crate::hal::timer::Timer<TC, crate::DefaultClock>;
+}
+
+/// Hobby servos on 16-bit timer PWM.
+#[cfg(feature = "mcu-atmega")]
+pub mod servo {
+    pub use crate::hal::servo::*;
+
+    /// Check the [`avr_hal_generic::servo::Servo`] documentation.
+    pub type Servo<'a, TC, PIN> = crate::hal::servo::Servo<'a, TC, PIN, crate::DefaultClock>;
+}
+
+/// Input Capture Unit of 16-bit timers.
+#[cfg(feature = "mcu-atmega")]
+pub mod input_capture {
+    pub use crate::hal::input_capture::*;
+
+    /// Check the [`avr_hal_generic::input_capture::InputCapture`] documentation.
+    pub type InputCapture<TC, PIN> =
+        crate::hal::input_capture::InputCapture<TC, PIN, crate::DefaultClock>;
+}
+
+#[cfg(all(feature = "board-selected", any(feature = "time", doc)))]
+#[doc(cfg(feature = "time"))]
+pub mod time;
+
 #[cfg(feature = "mcu-atmega")]
 pub mod prelude {
     pub use crate::hal::prelude::*;
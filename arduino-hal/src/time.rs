@@ -0,0 +1,437 @@
+//! System time keeping.
+//!
+//! This module provides an implementation of the Arduino [`millis()`] and [`micros()`] functions.
+//! It claims the `TC0` timer and uses its `TIMER0_COMPA` interrupt to advance a global time
+//! counter roughly once per millisecond.  The interrupt handler is installed by `arduino-hal`,
+//! thus this module is only available with the `time` feature enabled:
+//!
+//! ```toml
+//! [dependencies.arduino-hal]
+//! features = ["arduino-uno", "time"]
+//! ```
+//!
+//! Timer settings are derived from the board's [`DefaultClock`][crate::DefaultClock].  When the
+//! core clock is not an exact multiple of the tick rate (e.g. 16.5 MHz), the fractional part of
+//! each tick is accumulated so the clock does not drift.
+//!
+//! # Example
+//! ```no_run
+//! let dp = arduino_hal::Peripherals::take().unwrap();
+//!
+//! arduino_hal::time::init(dp.TC0);
+//! // The time only advances while interrupts are enabled.
+//! unsafe { avr_device::interrupt::enable() };
+//!
+//! let start = arduino_hal::time::Instant::now();
+//! arduino_hal::delay_ms(100);
+//! let elapsed = start.elapsed().as_millis();
+//! ```
+//!
+//! *Note*: As `TC0` is used for time keeping, it can no longer be used for PWM on the pins it
+//! is connected to.
+use core::cell::RefCell;
+use core::ops;
+
+use avr_device::interrupt::Mutex;
+use embedded_hal::delay::DelayNs;
+
+use crate::clock::Clock;
+use crate::timer::{Event, Timer};
+
+/// Targeted length of one tick of the system timer, in microseconds.
+const TICK_US: u32 = 1000;
+
+struct SysTick {
+    timer: Timer<crate::pac::TC0>,
+    /// Length of one tick in whole microseconds.
+    tick_us: u32,
+    /// Remainder of the tick length in units of `1 / CLOCK::FREQ` microseconds.
+    tick_fract: u32,
+    /// Timer prescaler division factor.
+    divider: u32,
+
+    fract: u32,
+    micros: u32,
+    millis: u32,
+    sub_millis: u32,
+}
+
+impl SysTick {
+    /// Length of the current tick in whole microseconds, including the carry of the accumulated
+    /// fractional part.
+    fn next_tick_us(&self) -> u32 {
+        self.tick_us + (self.fract + self.tick_fract >= crate::DefaultClock::FREQ) as u32
+    }
+
+    fn advance(&mut self) {
+        let us = self.next_tick_us();
+        self.fract += self.tick_fract;
+        if self.fract >= crate::DefaultClock::FREQ {
+            self.fract -= crate::DefaultClock::FREQ;
+        }
+
+        self.micros = self.micros.wrapping_add(us);
+        self.sub_millis += us;
+        while self.sub_millis >= 1000 {
+            self.sub_millis -= 1000;
+            self.millis = self.millis.wrapping_add(1);
+        }
+    }
+
+    fn micros(&self) -> u32 {
+        let mut counter = self.timer.counter();
+        let mut micros = self.micros;
+
+        // We are in a critical section so a compare match which happened in the meantime has
+        // not been handled yet.  The flag is already set while the counter still equals TOP, so
+        // the counter has only wrapped if it reads lower the second time.
+        if self.timer.is_pending(Event::CompareMatchA) {
+            let wrapped = self.timer.counter();
+            if wrapped < counter {
+                counter = wrapped;
+                micros = micros.wrapping_add(self.next_tick_us());
+            }
+        }
+
+        let counter_us = counter as u32 * self.divider * 1000 / (crate::DefaultClock::FREQ / 1000);
+        micros.wrapping_add(counter_us)
+    }
+}
+
+static SYS_TICK: Mutex<RefCell<Option<SysTick>>> = Mutex::new(RefCell::new(None));
+
+/// Start the system timer.
+///
+/// `TC0` is configured to fire the `TIMER0_COMPA` interrupt once per tick and the time counters
+/// are reset to zero.  Interrupts must be enabled globally for the time to advance.
+pub fn init(tc0: crate::pac::TC0) {
+    let mut timer = Timer::new(tc0);
+    let period = timer
+        .start_ctc_us(TICK_US)
+        .expect("system tick is not reachable with TC0");
+    timer.listen(Event::CompareMatchA);
+
+    let tick = period.cycles() as u64 * 1_000_000;
+    let freq = crate::DefaultClock::FREQ as u64;
+
+    avr_device::interrupt::free(|cs| {
+        SYS_TICK.borrow(cs).replace(Some(SysTick {
+            timer,
+            tick_us: (tick / freq) as u32,
+            tick_fract: (tick % freq) as u32,
+            divider: period.prescaler.divider(),
+            fract: 0,
+            micros: 0,
+            millis: 0,
+            sub_millis: 0,
+        }));
+    });
+}
+
+/// Stop the system timer and release the `TC0` peripheral.
+///
+/// Returns `None` if the system timer was not running.
+pub fn deinit() -> Option<crate::pac::TC0> {
+    avr_device::interrupt::free(|cs| SYS_TICK.borrow(cs).take())
+        .map(|sys_tick| sys_tick.timer.release())
+}
+
+/// Number of milliseconds since [`init()`] was called.
+///
+/// The counter wraps around after approximately 49.7 days.  Returns `0` if the system timer
+/// was not started.
+pub fn millis() -> u32 {
+    avr_device::interrupt::free(|cs| {
+        SYS_TICK
+            .borrow(cs)
+            .borrow()
+            .as_ref()
+            .map_or(0, |sys_tick| sys_tick.millis)
+    })
+}
+
+/// Number of microseconds since [`init()`] was called.
+///
+/// The resolution is one timer tick (4 µs for a 16 MHz clock).  The counter wraps around after
+/// approximately 71.6 minutes.  Returns `0` if the system timer was not started.
+pub fn micros() -> u32 {
+    avr_device::interrupt::free(|cs| {
+        SYS_TICK
+            .borrow(cs)
+            .borrow()
+            .as_ref()
+            .map_or(0, SysTick::micros)
+    })
+}
+
+#[cfg(any(
+    feature = "arduino-uno",
+    feature = "arduino-nano",
+    feature = "sparkfun-promini-3v3",
+    feature = "sparkfun-promini-5v",
+    feature = "trinket-pro",
+))]
+#[avr_device::interrupt(atmega328p)]
+fn TIMER0_COMPA() {
+    on_tick();
+}
+
+#[cfg(any(feature = "arduino-diecimila", feature = "nano168"))]
+#[avr_device::interrupt(atmega168)]
+fn TIMER0_COMPA() {
+    on_tick();
+}
+
+#[cfg(any(feature = "arduino-leonardo", feature = "sparkfun-promicro"))]
+#[avr_device::interrupt(atmega32u4)]
+fn TIMER0_COMPA() {
+    on_tick();
+}
+
+#[cfg(feature = "arduino-mega2560")]
+#[avr_device::interrupt(atmega2560)]
+fn TIMER0_COMPA() {
+    on_tick();
+}
+
+#[cfg(feature = "arduino-mega1280")]
+#[avr_device::interrupt(atmega1280)]
+fn TIMER0_COMPA() {
+    on_tick();
+}
+
+#[cfg(feature = "trinket")]
+#[avr_device::interrupt(attiny85)]
+fn TIMER0_COMPA() {
+    on_tick();
+}
+
+#[inline(always)]
+fn on_tick() {
+    avr_device::interrupt::free(|cs| {
+        if let Some(sys_tick) = SYS_TICK.borrow(cs).borrow_mut().as_mut() {
+            sys_tick.advance();
+        }
+    })
+}
+
+/// A span of time with microsecond resolution.
+///
+/// As it is stored in a `u32`, the longest representable duration is approximately 71.6
+/// minutes.
+#[derive(
+    ufmt::derive::uDebug, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default,
+)]
+pub struct Duration {
+    micros: u32,
+}
+
+impl Duration {
+    /// A duration of zero time.
+    pub const ZERO: Duration = Duration { micros: 0 };
+    /// The longest representable duration.
+    pub const MAX: Duration = Duration { micros: u32::MAX };
+
+    /// Create a new `Duration` from a number of microseconds.
+    pub const fn from_micros(micros: u32) -> Duration {
+        Duration { micros }
+    }
+
+    /// Create a new `Duration` from a number of milliseconds.
+    ///
+    /// Saturates at [`Duration::MAX`].
+    pub const fn from_millis(millis: u32) -> Duration {
+        Duration {
+            micros: millis.saturating_mul(1000),
+        }
+    }
+
+    /// Create a new `Duration` from a number of seconds.
+    ///
+    /// Saturates at [`Duration::MAX`].
+    pub const fn from_secs(secs: u32) -> Duration {
+        Duration {
+            micros: secs.saturating_mul(1_000_000),
+        }
+    }
+
+    /// Total number of whole microseconds in this duration.
+    pub const fn as_micros(&self) -> u32 {
+        self.micros
+    }
+
+    /// Total number of whole milliseconds in this duration.
+    pub const fn as_millis(&self) -> u32 {
+        self.micros / 1000
+    }
+
+    /// Total number of whole seconds in this duration.
+    pub const fn as_secs(&self) -> u32 {
+        self.micros / 1_000_000
+    }
+
+    /// Add two durations, returning `None` on overflow.
+    pub const fn checked_add(self, rhs: Duration) -> Option<Duration> {
+        match self.micros.checked_add(rhs.micros) {
+            Some(micros) => Some(Duration { micros }),
+            None => None,
+        }
+    }
+
+    /// Subtract two durations, returning `None` if `rhs` is larger than `self`.
+    pub const fn checked_sub(self, rhs: Duration) -> Option<Duration> {
+        match self.micros.checked_sub(rhs.micros) {
+            Some(micros) => Some(Duration { micros }),
+            None => None,
+        }
+    }
+
+    /// Subtract two durations, saturating at [`Duration::ZERO`].
+    pub const fn saturating_sub(self, rhs: Duration) -> Duration {
+        Duration {
+            micros: self.micros.saturating_sub(rhs.micros),
+        }
+    }
+}
+
+impl ops::Add for Duration {
+    type Output = Duration;
+
+    fn add(self, rhs: Duration) -> Duration {
+        self.checked_add(rhs)
+            .expect("overflow when adding durations")
+    }
+}
+
+impl ops::AddAssign for Duration {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl ops::Sub for Duration {
+    type Output = Duration;
+
+    fn sub(self, rhs: Duration) -> Duration {
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting durations")
+    }
+}
+
+impl ops::SubAssign for Duration {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+/// A measurement of the system timer.
+///
+/// `Instant`s are based on [`micros()`] and thus wrap around after approximately 71.6 minutes.
+/// All arithmetic is wrapping, so the difference between two instants is correct as long as
+/// they are less than that apart.  For the same reason, `Instant` does not implement ordering.
+#[derive(ufmt::derive::uDebug, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Instant {
+    micros: u32,
+}
+
+impl Instant {
+    /// Current value of the system timer.
+    pub fn now() -> Instant {
+        Instant { micros: micros() }
+    }
+
+    /// Time elapsed from `earlier` to `self`.
+    pub fn duration_since(&self, earlier: Instant) -> Duration {
+        Duration::from_micros(self.micros.wrapping_sub(earlier.micros))
+    }
+
+    /// Time elapsed since this instant.
+    pub fn elapsed(&self) -> Duration {
+        Instant::now().duration_since(*self)
+    }
+
+    /// Raw value of the system timer at this instant, in microseconds.
+    pub fn as_micros(&self) -> u32 {
+        self.micros
+    }
+}
+
+impl ops::Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, rhs: Duration) -> Instant {
+        Instant {
+            micros: self.micros.wrapping_add(rhs.micros),
+        }
+    }
+}
+
+impl ops::AddAssign<Duration> for Instant {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl ops::Sub<Duration> for Instant {
+    type Output = Instant;
+
+    fn sub(self, rhs: Duration) -> Instant {
+        Instant {
+            micros: self.micros.wrapping_sub(rhs.micros),
+        }
+    }
+}
+
+impl ops::SubAssign<Duration> for Instant {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl ops::Sub for Instant {
+    type Output = Duration;
+
+    fn sub(self, rhs: Instant) -> Duration {
+        self.duration_since(rhs)
+    }
+}
+
+/// Delay type based on the system timer.
+///
+/// Unlike [`arduino_hal::Delay`][crate::Delay], this delay is not extended by time spent in
+/// interrupt handlers.  It requires the system timer to be started with [`init()`] and
+/// interrupts to be enabled, otherwise it will never return.  Delays shorter than one
+/// microsecond are busy-looped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Delay {
+    _private: (),
+}
+
+impl Delay {
+    /// Create a new delay handle.
+    pub fn new() -> Delay {
+        Delay { _private: () }
+    }
+}
+
+impl DelayNs for Delay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.delay_us(ns / 1000);
+        crate::delay_ns(ns % 1000);
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        let start = micros();
+        while micros().wrapping_sub(start) < us {}
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        // Wait millisecond by millisecond to not overflow the microsecond counter.  Each step
+        // starts at the deadline of the previous one so no error accumulates.
+        let mut start = micros();
+        for _ in 0..ms {
+            while micros().wrapping_sub(start) < 1000 {}
+            start = start.wrapping_add(1000);
+        }
+    }
+}
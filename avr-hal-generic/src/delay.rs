@@ -62,6 +62,17 @@ fn busy_loop(_c: u16) {
     unimplemented!("Implementation is only available for avr targets!")
 }
 
+/// Busy-wait for roughly `cycles` CPU cycles.
+///
+/// The delay has a resolution of 4 cycles (one loop iteration) and does not include the overhead
+/// of the call itself.
+pub(crate) fn delay_cycles(cycles: u16) {
+    let iterations = cycles / 4;
+    if iterations > 0 {
+        busy_loop(iterations);
+    }
+}
+
 // Clock-Specific Delay Implementations ----------------------------------- {{{
 impl delay_v0::DelayUs<u16> for Delay<crate::clock::MHz24> {
     fn delay_us(&mut self, mut us: u16) {
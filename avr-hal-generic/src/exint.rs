@@ -0,0 +1,257 @@
+//! External interrupts (`INTn`)
+//!
+//! Some pins can trigger one of the dedicated external interrupts `INT0`, `INT1`, ... on a
+//! level or an edge.  Check the documentation of [`Exint`] for details.
+use crate::port;
+use core::marker::PhantomData;
+
+/// Condition which triggers an external interrupt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sense {
+    /// The interrupt is triggered continuously as long as the pin is low.
+    LowLevel,
+    /// Any logical change on the pin triggers the interrupt.
+    AnyChange,
+    /// A falling edge on the pin triggers the interrupt.
+    FallingEdge,
+    /// A rising edge on the pin triggers the interrupt.
+    RisingEdge,
+}
+
+/// Internal trait for low-level external interrupt peripherals.
+///
+/// This trait defines the common interface for the external interrupt controller.  It is used as
+/// an intermediate abstraction ontop of which the [`Exint`] API is built.  **Prefer using the
+/// [`Exint`] API instead of this trait.**
+pub trait ExintOps<H> {
+    /// Select the sense control for external interrupt `int`.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_sense(&mut self, int: u8, sense: Sense);
+    /// Enable/Disable external interrupt `int`.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_interrupt(&mut self, int: u8, state: bool);
+    /// Check whether external interrupt `int` is enabled.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_is_enabled(&self, int: u8) -> bool;
+    /// Check whether the flag of external interrupt `int` is set.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_is_pending(&self, int: u8) -> bool;
+    /// Clear the flag of external interrupt `int`.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_pending(&mut self, int: u8);
+}
+
+/// Pins which are connected to an external interrupt.
+///
+/// Implemented for the pin types of all `INTn` capable pins.  `INT` is the number of the
+/// external interrupt.
+pub trait ExintPin<H, EXINT>: port::PinOps {
+    const INT: u8;
+}
+
+/// External interrupt controller
+///
+/// The controller takes ownership of `INTn` capable pins using [`Exint::configure`].  The
+/// returned [`IntPin`] is then used to enable, disable or reconfigure its interrupt.  Only pins
+/// which are connected to an external interrupt are accepted, this is checked at compile time.
+///
+/// The interrupt handler for `INTn` must be defined by the user.  The interrupt flag is cleared
+/// automatically when the handler runs.
+///
+/// # Example
+/// ```
+/// #[avr_device::interrupt(atmega328p)]
+/// fn INT0() {
+///     // ...
+/// }
+///
+/// let dp = atmega_hal::Peripherals::take().unwrap();
+/// let pins = atmega_hal::pins!(dp);
+///
+/// let mut exint = atmega_hal::exint::Exint::new(dp.EXINT);
+/// let button = exint.configure(pins.pd2.into_pull_up_input(), Sense::FallingEdge);
+/// exint.enable(&button);
+///
+/// unsafe { avr_device::interrupt::enable() };
+/// ```
+pub struct Exint<H, EXINT> {
+    p: EXINT,
+    _h: PhantomData<H>,
+}
+
+/// An `INTn` capable pin which was configured using [`Exint::configure`].
+pub struct IntPin<PIN> {
+    pin: port::Pin<port::mode::Input, PIN>,
+}
+
+impl<PIN: port::PinOps> IntPin<PIN> {
+    /// Check whether the pin is driven high.
+    #[inline]
+    pub fn is_high(&self) -> bool {
+        self.pin.is_high()
+    }
+
+    /// Check whether the pin is driven low.
+    #[inline]
+    pub fn is_low(&self) -> bool {
+        self.pin.is_low()
+    }
+}
+
+impl<H, EXINT: ExintOps<H>> Exint<H, EXINT> {
+    /// Take ownership of the external interrupt controller.
+    pub fn new(p: EXINT) -> Self {
+        Self { p, _h: PhantomData }
+    }
+
+    /// Release the external interrupt controller.
+    ///
+    /// Interrupts which are still enabled stay enabled.
+    pub fn release(self) -> EXINT {
+        self.p
+    }
+
+    /// Configure an `INTn` capable pin to trigger on the given `sense`.
+    ///
+    /// The interrupt is left disabled, use [`Exint::enable`] to enable it.
+    pub fn configure<PIN, IMODE>(
+        &mut self,
+        pin: port::Pin<port::mode::Input<IMODE>, PIN>,
+        sense: Sense,
+    ) -> IntPin<PIN>
+    where
+        PIN: ExintPin<H, EXINT>,
+    {
+        let pin = IntPin {
+            pin: pin.forget_imode(),
+        };
+        self.p.raw_interrupt(PIN::INT, false);
+        self.p.raw_set_sense(PIN::INT, sense);
+        self.p.raw_clear_pending(PIN::INT);
+        pin
+    }
+
+    /// Change the sense control of a configured pin.
+    ///
+    /// Changing the sense control can set the interrupt flag, so the interrupt is disabled and the
+    /// flag is cleared while changing it.  An enabled interrupt is enabled again afterwards.
+    pub fn set_sense<PIN: ExintPin<H, EXINT>>(&mut self, _pin: &IntPin<PIN>, sense: Sense) {
+        let enabled = self.p.raw_is_enabled(PIN::INT);
+        self.p.raw_interrupt(PIN::INT, false);
+        self.p.raw_set_sense(PIN::INT, sense);
+        self.p.raw_clear_pending(PIN::INT);
+        if enabled {
+            self.p.raw_interrupt(PIN::INT, true);
+        }
+    }
+
+    /// Enable the interrupt of a configured pin.
+    pub fn enable<PIN: ExintPin<H, EXINT>>(&mut self, _pin: &IntPin<PIN>) {
+        self.p.raw_interrupt(PIN::INT, true);
+    }
+
+    /// Disable the interrupt of a configured pin.
+    pub fn disable<PIN: ExintPin<H, EXINT>>(&mut self, _pin: &IntPin<PIN>) {
+        self.p.raw_interrupt(PIN::INT, false);
+    }
+
+    /// Check whether the interrupt of a configured pin is enabled.
+    pub fn is_enabled<PIN: ExintPin<H, EXINT>>(&self, _pin: &IntPin<PIN>) -> bool {
+        self.p.raw_is_enabled(PIN::INT)
+    }
+
+    /// Check whether the interrupt of a configured pin is pending.
+    ///
+    /// This also works while the interrupt is disabled, which allows polling for edges.
+    pub fn is_pending<PIN: ExintPin<H, EXINT>>(&self, _pin: &IntPin<PIN>) -> bool {
+        self.p.raw_is_pending(PIN::INT)
+    }
+
+    /// Clear the pending interrupt of a configured pin.
+    pub fn clear_pending<PIN: ExintPin<H, EXINT>>(&mut self, _pin: &IntPin<PIN>) {
+        self.p.raw_clear_pending(PIN::INT);
+    }
+
+    /// Disable the interrupt of a configured pin and release the pin.
+    pub fn release_pin<PIN: ExintPin<H, EXINT>>(
+        &mut self,
+        pin: IntPin<PIN>,
+    ) -> port::Pin<port::mode::Input, PIN> {
+        self.p.raw_interrupt(PIN::INT, false);
+        pin.pin
+    }
+}
+
+/// Implement traits for the external interrupt controller and the `INTn` pins
+///
+/// `eicrb` has to be given for devices with more than four external interrupts.
+#[macro_export]
+macro_rules! impl_exint {
+    (
+        hal: $HAL:ty,
+        peripheral: $EXINT:ty,
+        pins: {
+            $($pin:ty: $int:expr,)+
+        },
+        $(eicrb: $eicrb:ident,)?
+    ) => {
+        impl $crate::exint::ExintOps<$HAL> for $EXINT {
+            fn raw_set_sense(&mut self, int: u8, sense: $crate::exint::Sense) {
+                let bits = match sense {
+                    $crate::exint::Sense::LowLevel => 0b00,
+                    $crate::exint::Sense::AnyChange => 0b01,
+                    $crate::exint::Sense::FallingEdge => 0b10,
+                    $crate::exint::Sense::RisingEdge => 0b11,
+                };
+                // Each interrupt has two ISCn bits, four interrupts per register.
+                let shift = (int % 4) * 2;
+                if int < 4 {
+                    self.eicra.modify(|r, w| unsafe {
+                        w.bits(r.bits() & !(0b11 << shift) | bits << shift)
+                    });
+                }
+                $(
+                    if int >= 4 {
+                        self.$eicrb.modify(|r, w| unsafe {
+                            w.bits(r.bits() & !(0b11 << shift) | bits << shift)
+                        });
+                    }
+                )?
+            }
+
+            fn raw_interrupt(&mut self, int: u8, state: bool) {
+                self.eimsk.modify(|r, w| unsafe {
+                    w.bits(if state {
+                        r.bits() | 1 << int
+                    } else {
+                        r.bits() & !(1 << int)
+                    })
+                });
+            }
+
+            fn raw_is_enabled(&self, int: u8) -> bool {
+                self.eimsk.read().bits() & 1 << int != 0
+            }
+
+            fn raw_is_pending(&self, int: u8) -> bool {
+                self.eifr.read().bits() & 1 << int != 0
+            }
+
+            fn raw_clear_pending(&mut self, int: u8) {
+                // Flags are cleared by writing a one, all others must be written as zero.
+                self.eifr.write(|w| unsafe { w.bits(1 << int) });
+            }
+        }
+
+        $(
+            impl $crate::exint::ExintPin<$HAL, $EXINT> for $pin {
+                const INT: u8 = $int;
+            }
+        )+
+    };
+}
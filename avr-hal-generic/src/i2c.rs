@@ -118,6 +118,11 @@ pub enum Error {
     BusError,
     /// An unknown error occured.  The bus might be in an unknown state.
     Unknown,
+    /// The peripheral did not finish an operation within the configured timeout.
+    ///
+    /// The bus is most likely blocked by another device.  Use [`I2c::recover_bus`] to try
+    /// freeing it again.
+    Timeout,
 }
 
 impl embedded_hal::i2c::Error for Error {
@@ -131,7 +136,7 @@ impl embedded_hal::i2c::Error for Error {
                 embedded_hal::i2c::NoAcknowledgeSource::Data,
             ),
             Error::BusError => embedded_hal::i2c::ErrorKind::Bus,
-            Error::Unknown => embedded_hal::i2c::ErrorKind::Other,
+            Error::Unknown | Error::Timeout => embedded_hal::i2c::ErrorKind::Other,
         }
     }
 }
@@ -152,6 +157,30 @@ pub enum Direction {
     Read,
 }
 
+/// Approximate number of CPU cycles spent in one iteration of [`wait_until`].
+pub(crate) const WAIT_LOOP_CYCLES: u32 = 10;
+
+/// Busy-wait until `ready` returns true.
+///
+/// `timeout` is the maximum number of iterations to wait for.  If it is `None`, this function
+/// waits forever.
+#[doc(hidden)]
+#[inline(always)]
+pub fn wait_until<F: FnMut() -> bool>(timeout: Option<u32>, mut ready: F) -> Result<(), Error> {
+    match timeout {
+        None => while !ready() {},
+        Some(mut remaining) => {
+            while !ready() {
+                if remaining == 0 {
+                    return Err(Error::Timeout);
+                }
+                remaining -= 1;
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Internal trait for low-level I2C peripherals.
 ///
 /// This trait defines the common interface for all I2C peripheral variants.  It is used as an
@@ -163,27 +192,41 @@ pub trait I2cOps<H, SDA, SCL> {
     /// **Warning**: This is a low-level method and should not be called directly from user code.
     fn raw_setup<CLOCK: crate::clock::Clock>(&mut self, speed: u32);
 
+    /// Disable the peripheral such that the pins can be used as GPIOs.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_release(&mut self);
+
     /// Start a bus transaction to a certain `address` in either read or write mode.
     ///
     /// If a previous transaction was not stopped via `raw_stop()`, this should generate a repeated
     /// start condition.
     ///
+    /// If the peripheral does not respond within `timeout` wait-loop iterations,
+    /// [`Error::Timeout`] must be returned.  `None` means waiting forever.  The same applies to
+    /// `raw_write()` and `raw_read()`.
+    ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
-    fn raw_start(&mut self, address: u8, direction: Direction) -> Result<(), Error>;
+    fn raw_start(
+        &mut self,
+        address: u8,
+        direction: Direction,
+        timeout: Option<u32>,
+    ) -> Result<(), Error>;
 
     /// Write some bytes to the bus.
     ///
     /// This method must only be called after a transaction in write mode was successfully started.
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
-    fn raw_write(&mut self, bytes: &[u8]) -> Result<(), Error>;
+    fn raw_write(&mut self, bytes: &[u8], timeout: Option<u32>) -> Result<(), Error>;
 
     /// Read some bytes from the bus.
     ///
     /// This method must only be called after a transaction in read mode was successfully started.
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
-    fn raw_read(&mut self, buffer: &mut [u8]) -> Result<(), Error>;
+    fn raw_read(&mut self, buffer: &mut [u8], timeout: Option<u32>) -> Result<(), Error>;
 
     /// Send a stop-condition and release the bus.
     ///
@@ -211,12 +254,18 @@ pub trait I2cOps<H, SDA, SCL> {
 ///
 /// // i2c implements the embedded-hal traits so it can be used with generic drivers.
 /// ```
+///
+/// By default, all operations wait forever for the bus.  A device holding SDA or SCL low will
+/// then block the program.  To prevent this, configure a timeout using [`I2c::set_timeout`] and
+/// call [`I2c::recover_bus`] when an operation returns [`Error::Timeout`].
 pub struct I2c<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> {
     p: I2C,
     #[allow(dead_code)]
     sda: SDA,
     #[allow(dead_code)]
     scl: SCL,
+    speed: u32,
+    timeout: Option<u32>,
     _clock: PhantomData<CLOCK>,
     _h: PhantomData<H>,
 }
@@ -247,6 +296,8 @@ where
             p,
             sda: sda.forget_imode(),
             scl: scl.forget_imode(),
+            speed,
+            timeout: None,
             _clock: PhantomData,
             _h: PhantomData,
         };
@@ -270,6 +321,8 @@ where
             p,
             sda: sda.forget_imode(),
             scl: scl.forget_imode(),
+            speed,
+            timeout: None,
             _clock: PhantomData,
             _h: PhantomData,
         };
@@ -278,14 +331,99 @@ where
     }
 }
 
+impl<H, I2C, SDAPIN, SCLPIN, CLOCK>
+    I2c<H, I2C, port::Pin<port::mode::Input, SDAPIN>, port::Pin<port::mode::Input, SCLPIN>, CLOCK>
+where
+    I2C: I2cOps<H, port::Pin<port::mode::Input, SDAPIN>, port::Pin<port::mode::Input, SCLPIN>>,
+    SDAPIN: port::PinOps,
+    SCLPIN: port::PinOps,
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: embedded_hal::delay::DelayNs,
+{
+    /// Try to free a bus which is blocked by a device holding SDA low.
+    ///
+    /// This can happen when a transaction was interrupted (for example by a reset of the
+    /// controller) while a device was sending data.  The peripheral is released and SCL is clocked
+    /// manually up to nine times until the device lets go of SDA.  Then, a stop condition is
+    /// generated and the peripheral is set up again.
+    ///
+    /// Returns [`Error::BusError`] if SDA is still held low afterwards.
+    pub fn recover_bus(&mut self) -> Result<(), Error> {
+        use embedded_hal::delay::DelayNs;
+        let mut delay = crate::delay::Delay::<CLOCK>::new();
+        let half_period = (500_000 / self.speed).max(1);
+
+        self.p.raw_release();
+
+        // The port bits of the inputs tell whether the internal pull-ups are enabled.  They are
+        // re-enabled whenever a line is released.
+        let sda_pull_up = unsafe { self.sda.pin.out_get() };
+        let scl_pull_up = unsafe { self.scl.pin.out_get() };
+
+        for _ in 0..9 {
+            if unsafe { self.sda.pin.in_get() } {
+                break;
+            }
+            pull_line_low(&mut self.scl.pin);
+            delay.delay_us(half_period);
+            release_line(&mut self.scl.pin, scl_pull_up);
+            delay.delay_us(half_period);
+        }
+
+        // Stop condition: SDA rises while SCL is high.
+        pull_line_low(&mut self.scl.pin);
+        delay.delay_us(half_period);
+        pull_line_low(&mut self.sda.pin);
+        delay.delay_us(half_period);
+        release_line(&mut self.scl.pin, scl_pull_up);
+        delay.delay_us(half_period);
+        release_line(&mut self.sda.pin, sda_pull_up);
+        delay.delay_us(half_period);
+
+        let released = unsafe { self.sda.pin.in_get() };
+        self.p.raw_setup::<CLOCK>(self.speed);
+        if released {
+            Ok(())
+        } else {
+            Err(Error::BusError)
+        }
+    }
+}
+
+fn pull_line_low<PIN: port::PinOps>(pin: &mut PIN) {
+    unsafe {
+        pin.out_clear();
+        pin.make_output();
+    }
+}
+
+fn release_line<PIN: port::PinOps>(pin: &mut PIN, pull_up: bool) {
+    unsafe { pin.make_input(pull_up) };
+}
+
+impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> I2c<H, I2C, SDA, SCL, CLOCK>
+where
+    CLOCK: crate::clock::Clock,
+{
+    /// Set the timeout for all bus operations in microseconds.
+    ///
+    /// If the peripheral does not make progress within this time, the operation is aborted with
+    /// [`Error::Timeout`].  `None` (the default) disables the timeout.  The timeout is only
+    /// approximate as it is implemented by counting loop iterations.
+    pub fn set_timeout(&mut self, timeout_us: Option<u32>) {
+        self.timeout =
+            timeout_us.map(|us| us.saturating_mul(CLOCK::FREQ / 1_000_000) / WAIT_LOOP_CYCLES);
+    }
+}
+
 impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> I2c<H, I2C, SDA, SCL, CLOCK>
 where
     CLOCK: crate::clock::Clock,
-    crate::delay::Delay<CLOCK>: embedded_hal_v0::blocking::delay::DelayMs<u16>,
+    crate::delay::Delay<CLOCK>: embedded_hal::delay::DelayNs,
 {
     /// Test whether a device answers on a certain address.
     pub fn ping_device(&mut self, address: u8, direction: Direction) -> Result<bool, Error> {
-        match self.p.raw_start(address, direction) {
+        match self.p.raw_start(address, direction, self.timeout) {
             Ok(_) => {
                 self.p.raw_stop()?;
                 Ok(true)
@@ -316,54 +454,67 @@ where
         w: &mut W,
         direction: Direction,
     ) -> Result<(), W::Error> {
-        use embedded_hal_v0::blocking::delay::DelayMs;
-        let mut delay = crate::delay::Delay::<CLOCK>::new();
+        i2cdetect::<CLOCK, W, _>(w, |address| self.ping_device(address, direction))
+    }
+}
 
-        w.write_str(
-            "\
+/// Print an [`i2cdetect(8)`][i2cdetect-linux] style summary using `ping` to probe each address.
+///
+/// [i2cdetect-linux]: https://man.archlinux.org/man/community/i2c-tools/i2cdetect.8.en
+pub(crate) fn i2cdetect<CLOCK, W, F>(w: &mut W, mut ping: F) -> Result<(), W::Error>
+where
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: embedded_hal::delay::DelayNs,
+    W: ufmt::uWrite,
+    F: FnMut(u8) -> Result<bool, Error>,
+{
+    use embedded_hal::delay::DelayNs;
+    let mut delay = crate::delay::Delay::<CLOCK>::new();
+
+    w.write_str(
+        "\
 -    0  1  2  3  4  5  6  7  8  9  a  b  c  d  e  f\r\n\
 00:      ",
-        )?;
+    )?;
 
-        fn u4_to_hex(b: u8) -> char {
-            match b {
-                x if x < 0xa => (0x30 + x).into(),
-                x if x < 0x10 => (0x57 + x).into(),
-                _ => '?',
-            }
+    fn u4_to_hex(b: u8) -> char {
+        match b {
+            x if x < 0xa => (0x30 + x).into(),
+            x if x < 0x10 => (0x57 + x).into(),
+            _ => '?',
         }
+    }
 
-        for address in 0x02..=0x77 {
-            let (ah, al) = (u4_to_hex(address >> 4), u4_to_hex(address & 0xf));
+    for address in 0x02..=0x77 {
+        let (ah, al) = (u4_to_hex(address >> 4), u4_to_hex(address & 0xf));
 
-            if address % 0x10 == 0 {
-                w.write_str("\r\n")?;
+        if address % 0x10 == 0 {
+            w.write_str("\r\n")?;
+            w.write_char(ah)?;
+            w.write_str("0:")?;
+        }
+
+        match ping(address) {
+            Ok(true) => {
+                w.write_char(' ')?;
                 w.write_char(ah)?;
-                w.write_str("0:")?;
+                w.write_char(al)?;
             }
-
-            match self.ping_device(address, direction) {
-                Ok(true) => {
-                    w.write_char(' ')?;
-                    w.write_char(ah)?;
-                    w.write_char(al)?;
-                }
-                Ok(false) => {
-                    w.write_str(" --")?;
-                }
-                Err(e) => {
-                    w.write_str(" E")?;
-                    w.write_char(u4_to_hex(e as u8))?;
-                }
+            Ok(false) => {
+                w.write_str(" --")?;
+            }
+            Err(e) => {
+                w.write_str(" E")?;
+                w.write_char(u4_to_hex(e as u8))?;
             }
-
-            delay.delay_ms(10u16);
         }
 
-        w.write_str("\r\n")?;
-
-        Ok(())
+        delay.delay_ms(10);
     }
+
+    w.write_str("\r\n")?;
+
+    Ok(())
 }
 
 impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> embedded_hal_v0::blocking::i2c::Write
@@ -372,8 +523,8 @@ impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> embedded_hal_v0::blocking::i2
     type Error = Error;
 
     fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
-        self.p.raw_start(address, Direction::Write)?;
-        self.p.raw_write(bytes)?;
+        self.p.raw_start(address, Direction::Write, self.timeout)?;
+        self.p.raw_write(bytes, self.timeout)?;
         self.p.raw_stop()?;
         Ok(())
     }
@@ -385,8 +536,8 @@ impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> embedded_hal_v0::blocking::i2
     type Error = Error;
 
     fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
-        self.p.raw_start(address, Direction::Read)?;
-        self.p.raw_read(buffer)?;
+        self.p.raw_start(address, Direction::Read, self.timeout)?;
+        self.p.raw_read(buffer, self.timeout)?;
         self.p.raw_stop()?;
         Ok(())
     }
@@ -403,10 +554,10 @@ impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> embedded_hal_v0::blocking::i2
         bytes: &[u8],
         buffer: &mut [u8],
     ) -> Result<(), Self::Error> {
-        self.p.raw_start(address, Direction::Write)?;
-        self.p.raw_write(bytes)?;
-        self.p.raw_start(address, Direction::Read)?;
-        self.p.raw_read(buffer)?;
+        self.p.raw_start(address, Direction::Write, self.timeout)?;
+        self.p.raw_write(bytes, self.timeout)?;
+        self.p.raw_start(address, Direction::Read, self.timeout)?;
+        self.p.raw_read(buffer, self.timeout)?;
         self.p.raw_stop()?;
         Ok(())
     }
@@ -425,16 +576,16 @@ impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> embedded_hal::i2c::I2c<SevenB
             match operation {
                 embedded_hal::i2c::Operation::Read(buffer) => {
                     if idx == 0 || previous_direction != Direction::Read {
-                        self.p.raw_start(address, Direction::Read)?;
+                        self.p.raw_start(address, Direction::Read, self.timeout)?;
                     }
-                    self.p.raw_read(buffer)?;
+                    self.p.raw_read(buffer, self.timeout)?;
                     previous_direction = Direction::Read;
                 }
                 embedded_hal::i2c::Operation::Write(bytes) => {
                     if idx == 0 || previous_direction != Direction::Write {
-                        self.p.raw_start(address, Direction::Write)?;
+                        self.p.raw_start(address, Direction::Write, self.timeout)?;
                     }
-                    self.p.raw_write(bytes)?;
+                    self.p.raw_write(bytes, self.timeout)?;
                     previous_direction = Direction::Write;
                 }
             }
@@ -447,6 +598,697 @@ impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> embedded_hal::i2c::I2c<SevenB
     }
 }
 
+/// Internal trait for low-level I2C peripherals driven by interrupts.
+///
+/// Each method performs a single step of a transaction and returns immediately.  Completion of
+/// the step is signaled by the `TWI` interrupt.  This trait is used as an intermediate abstraction
+/// ontop of which the [`IsrI2c`] API is built.  **Prefer using the [`IsrI2c`] API instead of this
+/// trait.**
+pub trait I2cIsrOps<H, SDA, SCL> {
+    /// Generate a (repeated) start condition and enable the interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_isr_start(&mut self);
+
+    /// Generate a stop condition and disable the interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_isr_stop(&mut self);
+
+    /// Release the bus without a stop condition and disable the interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_isr_abort(&mut self);
+
+    /// Return the status code (see [`twi_status`]) of the last step.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_isr_status(&mut self) -> u8;
+
+    /// Send a byte (address or data).
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_isr_send(&mut self, byte: u8);
+
+    /// Receive the next byte and reply with ACK or NACK.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_isr_receive(&mut self, ack: bool);
+
+    /// Read the last received byte.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_isr_data(&mut self) -> u8;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum IsrPhase {
+    Idle,
+    Write,
+    Read,
+    Done(Result<(), Error>),
+}
+
+struct IsrI2cInner<I2C, const N: usize> {
+    p: Option<I2C>,
+    buffer: [u8; N],
+    address: u8,
+    write_len: usize,
+    read_len: usize,
+    pos: usize,
+    phase: IsrPhase,
+    callback: Option<fn(Result<(), Error>)>,
+}
+
+impl<I2C, const N: usize> IsrI2cInner<I2C, N> {
+    fn finish<H, SDA, SCL>(&mut self, result: Result<(), Error>)
+    where
+        I2C: I2cIsrOps<H, SDA, SCL>,
+    {
+        if let Some(p) = self.p.as_mut() {
+            match result {
+                Err(Error::ArbitrationLost) => p.raw_isr_abort(),
+                _ => p.raw_isr_stop(),
+            }
+        }
+        self.phase = IsrPhase::Done(result);
+        if let Some(callback) = self.callback {
+            callback(result);
+        }
+    }
+}
+
+/// Shared state of an [`IsrI2c`] and its interrupt handler.
+///
+/// The state holds the peripheral and a transfer buffer of `N` bytes.  It must be placed in a
+/// `static` so it can be shared with the `TWI` interrupt handler which must be defined by the
+/// user and forward to [`IsrI2cState::on_interrupt`]:
+///
+/// ```
+/// static I2C_STATE: IsrI2cState<pac::TWI, 16> = IsrI2cState::new();
+///
+/// #[avr_device::interrupt(atmega328p)]
+/// fn TWI() {
+///     I2C_STATE.on_interrupt();
+/// }
+/// ```
+pub struct IsrI2cState<I2C, const N: usize> {
+    inner: avr_device::interrupt::Mutex<core::cell::RefCell<IsrI2cInner<I2C, N>>>,
+}
+
+impl<I2C, const N: usize> IsrI2cState<I2C, N> {
+    /// Create an empty state.
+    pub const fn new() -> Self {
+        Self {
+            inner: avr_device::interrupt::Mutex::new(core::cell::RefCell::new(IsrI2cInner {
+                p: None,
+                buffer: [0; N],
+                address: 0,
+                write_len: 0,
+                read_len: 0,
+                pos: 0,
+                phase: IsrPhase::Idle,
+                callback: None,
+            })),
+        }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut IsrI2cInner<I2C, N>) -> R) -> R {
+        avr_device::interrupt::free(|cs| f(&mut self.inner.borrow(cs).borrow_mut()))
+    }
+
+    /// Handler for the `TWI` interrupt.
+    ///
+    /// Advances the ongoing transaction by one step.  When the transaction is finished, the
+    /// callback set with [`IsrI2c::set_callback`] is invoked from here.
+    pub fn on_interrupt<H, SDA, SCL>(&self)
+    where
+        I2C: I2cIsrOps<H, SDA, SCL>,
+    {
+        self.with(|inner| {
+            let p = match inner.p.as_mut() {
+                Some(p) => p,
+                None => return,
+            };
+            let result = match p.raw_isr_status() {
+                twi_status::TW_START | twi_status::TW_REP_START => {
+                    let dirbit = (inner.phase == IsrPhase::Read) as u8;
+                    p.raw_isr_send((inner.address << 1) | dirbit);
+                    return;
+                }
+                twi_status::TW_MT_SLA_ACK | twi_status::TW_MT_DATA_ACK => {
+                    if inner.pos < inner.write_len {
+                        p.raw_isr_send(inner.buffer[inner.pos]);
+                        inner.pos += 1;
+                        return;
+                    } else if inner.read_len > 0 {
+                        inner.phase = IsrPhase::Read;
+                        inner.pos = 0;
+                        p.raw_isr_start();
+                        return;
+                    }
+                    Ok(())
+                }
+                twi_status::TW_MR_SLA_ACK => {
+                    p.raw_isr_receive(inner.read_len > 1);
+                    return;
+                }
+                twi_status::TW_MR_DATA_ACK => {
+                    inner.buffer[inner.pos] = p.raw_isr_data();
+                    inner.pos += 1;
+                    p.raw_isr_receive(inner.pos + 1 < inner.read_len);
+                    return;
+                }
+                twi_status::TW_MR_DATA_NACK => {
+                    inner.buffer[inner.pos] = p.raw_isr_data();
+                    inner.pos += 1;
+                    Ok(())
+                }
+                twi_status::TW_MT_SLA_NACK | twi_status::TW_MR_SLA_NACK => Err(Error::AddressNack),
+                twi_status::TW_MT_DATA_NACK => Err(Error::DataNack),
+                twi_status::TW_MT_ARB_LOST => Err(Error::ArbitrationLost),
+                twi_status::TW_BUS_ERROR => Err(Error::BusError),
+                _ => Err(Error::Unknown),
+            };
+            inner.finish(result);
+        })
+    }
+}
+
+impl<I2C, const N: usize> Default for IsrI2cState<I2C, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Interrupt-driven, non-blocking I2C controller
+///
+/// Created by calling [`I2c::into_isr_driven`].  A transaction is started using
+/// [`IsrI2c::write`], [`IsrI2c::read`] or [`IsrI2c::write_read`] and then carried out by the
+/// `TWI` interrupt while the application continues running.  The data is copied into the transfer
+/// buffer of the [`IsrI2cState`], so at most `N` bytes can be written or read per transaction.
+///
+/// Completion can be checked with [`IsrI2c::is_done`] and the outcome (and any data which was
+/// read) is collected with [`IsrI2c::result`].  Alternatively, a callback can be registered
+/// using [`IsrI2c::set_callback`].
+///
+/// **Note**: Interrupts must be enabled globally for transactions to make progress.
+///
+/// # Example
+/// ```
+/// static I2C_STATE: IsrI2cState<pac::TWI, 16> = IsrI2cState::new();
+///
+/// let mut i2c = arduino_hal::I2c::new(
+///     dp.TWI,
+///     pins.a4.into_pull_up_input(),
+///     pins.a5.into_pull_up_input(),
+///     100_000,
+/// )
+/// .into_isr_driven(&I2C_STATE);
+/// unsafe { avr_device::interrupt::enable() };
+///
+/// // Read two bytes from register 0x00 of the device at 0x48
+/// i2c.write_read(0x48, &[0x00], 2).unwrap_infallible();
+/// loop {
+///     let mut data = [0u8; 2];
+///     match i2c.result(&mut data) {
+///         Ok(()) => break,
+///         Err(nb::Error::WouldBlock) => do_other_work(),
+///         Err(nb::Error::Other(e)) => panic!("I2C error: {:?}", e),
+///     }
+/// }
+/// ```
+pub struct IsrI2c<H, I2C: I2cIsrOps<H, SDA, SCL> + 'static, SDA, SCL, CLOCK, const N: usize> {
+    state: &'static IsrI2cState<I2C, N>,
+    sda: SDA,
+    scl: SCL,
+    speed: u32,
+    _clock: PhantomData<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, I2C, SDA, SCL, CLOCK> I2c<H, I2C, SDA, SCL, CLOCK>
+where
+    I2C: I2cOps<H, SDA, SCL> + I2cIsrOps<H, SDA, SCL>,
+{
+    /// Convert this I2C controller into an interrupt-driven [`IsrI2c`].
+    ///
+    /// The peripheral is moved into `state`.  The timeout set by [`I2c::set_timeout`] does not
+    /// apply to interrupt-driven transactions and is discarded.
+    pub fn into_isr_driven<const N: usize>(
+        self,
+        state: &'static IsrI2cState<I2C, N>,
+    ) -> IsrI2c<H, I2C, SDA, SCL, CLOCK, N> {
+        state.with(|inner| {
+            inner.p = Some(self.p);
+            inner.phase = IsrPhase::Idle;
+        });
+
+        IsrI2c {
+            state,
+            sda: self.sda,
+            scl: self.scl,
+            speed: self.speed,
+            _clock: PhantomData,
+            _h: PhantomData,
+        }
+    }
+}
+
+impl<H, I2C, SDA, SCL, CLOCK, const N: usize> IsrI2c<H, I2C, SDA, SCL, CLOCK, N>
+where
+    I2C: I2cOps<H, SDA, SCL> + I2cIsrOps<H, SDA, SCL>,
+{
+    /// Wait for the ongoing transaction to finish and convert back into a blocking [`I2c`].
+    ///
+    /// The returned controller has no timeout configured.
+    pub fn into_blocking(self) -> I2c<H, I2C, SDA, SCL, CLOCK> {
+        while !self.is_done() {}
+        let p = self
+            .state
+            .with(|inner| {
+                inner.phase = IsrPhase::Idle;
+                inner.p.take()
+            })
+            .expect("state of an IsrI2c without peripheral");
+
+        I2c {
+            p,
+            sda: self.sda,
+            scl: self.scl,
+            speed: self.speed,
+            timeout: None,
+            _clock: PhantomData,
+            _h: PhantomData,
+        }
+    }
+
+    /// Set a function to be called from the interrupt handler when a transaction finished.
+    ///
+    /// The callback runs inside the `TWI` interrupt and receives the outcome of the transaction.
+    /// Use [`IsrI2c::result`] to fetch data which was read.
+    pub fn set_callback(&mut self, callback: Option<fn(Result<(), Error>)>) {
+        self.state.with(|inner| inner.callback = callback);
+    }
+
+    /// Start writing `bytes` to the device at `address`.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] while another transaction is still ongoing.
+    ///
+    /// # Panics
+    /// Panics if more than `N` bytes should be written.
+    pub fn write(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+    ) -> nb::Result<(), core::convert::Infallible> {
+        self.submit(address, bytes, 0)
+    }
+
+    /// Start reading `len` bytes from the device at `address`.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] while another transaction is still ongoing.
+    ///
+    /// # Panics
+    /// Panics if `len` is zero or larger than `N`.
+    pub fn read(&mut self, address: u8, len: usize) -> nb::Result<(), core::convert::Infallible> {
+        assert!(len > 0, "I2C read of zero bytes");
+        self.submit(address, &[], len)
+    }
+
+    /// Start writing `bytes` to the device at `address` and then reading `len` bytes back after a
+    /// repeated start.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] while another transaction is still ongoing.
+    ///
+    /// # Panics
+    /// Panics if more than `N` bytes should be written or read.
+    pub fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        len: usize,
+    ) -> nb::Result<(), core::convert::Infallible> {
+        self.submit(address, bytes, len)
+    }
+
+    /// Check whether the last transaction finished.
+    pub fn is_done(&self) -> bool {
+        self.state
+            .with(|inner| !matches!(inner.phase, IsrPhase::Write | IsrPhase::Read))
+    }
+
+    /// Get the outcome of the last transaction.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] while the transaction is still ongoing.  On success, the
+    /// data which was read is copied into `buffer` (as far as it fits).
+    pub fn result(&mut self, buffer: &mut [u8]) -> nb::Result<(), Error> {
+        self.state.with(|inner| match inner.phase {
+            IsrPhase::Write | IsrPhase::Read => Err(nb::Error::WouldBlock),
+            IsrPhase::Done(Err(e)) => Err(nb::Error::Other(e)),
+            IsrPhase::Idle | IsrPhase::Done(Ok(())) => {
+                let len = buffer.len().min(inner.read_len);
+                buffer[..len].copy_from_slice(&inner.buffer[..len]);
+                Ok(())
+            }
+        })
+    }
+
+    fn submit(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        read_len: usize,
+    ) -> nb::Result<(), core::convert::Infallible> {
+        assert!(
+            bytes.len() <= N && read_len <= N,
+            "I2C transaction too long"
+        );
+        self.state.with(|inner| {
+            if matches!(inner.phase, IsrPhase::Write | IsrPhase::Read) {
+                return Err(nb::Error::WouldBlock);
+            }
+            inner.buffer[..bytes.len()].copy_from_slice(bytes);
+            inner.address = address;
+            inner.write_len = bytes.len();
+            inner.read_len = read_len;
+            inner.pos = 0;
+            inner.phase = if bytes.is_empty() && read_len > 0 {
+                IsrPhase::Read
+            } else {
+                IsrPhase::Write
+            };
+            if let Some(p) = inner.p.as_mut() {
+                p.raw_isr_start();
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Events reported by an [`I2cTarget`]
+#[derive(ufmt::derive::uDebug, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TargetEvent {
+    /// A controller addressed this target for writing.
+    ///
+    /// `general_call` is set if the transaction was addressed to the general call address (0x00)
+    /// instead of the own address.
+    WriteRequest { general_call: bool },
+    /// A byte written by the controller was received and acknowledged.
+    Received(u8),
+    /// A controller requests a byte from this target.
+    ///
+    /// The bus is held (by stretching SCL) until the byte is supplied using
+    /// [`I2cTarget::respond`].
+    ReadRequest,
+    /// The transaction ended.
+    ///
+    /// This is either a stop or repeated start condition after a write or the controller replying
+    /// NACK to the last byte of a read.
+    Stop,
+}
+
+/// Internal trait for low-level I2C peripherals in target (slave) mode.
+///
+/// This trait defines the common interface for all I2C peripheral variants supporting target
+/// mode.  It is used as an intermediate abstraction ontop of which the [`I2cTarget`] API is
+/// built.  **Prefer using the [`I2cTarget`] API instead of this trait.**
+pub trait I2cTargetOps<H, SDA, SCL> {
+    /// Enable the peripheral as a target listening on `address`.
+    ///
+    /// Bits set in `mask` are ignored when matching the address.  If `general_call` is set, the
+    /// target also answers to the general call address.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_target_setup(&mut self, address: u8, mask: u8, general_call: bool);
+
+    /// Disable the peripheral.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_target_release(&mut self);
+
+    /// Enable/Disable the TWI interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_target_interrupt(&mut self, state: bool);
+
+    /// Return the current status code (see [`twi_status`]) if the peripheral is waiting for the
+    /// application.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_target_status(&mut self) -> Option<u8>;
+
+    /// Read the byte in the data register.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_target_read(&mut self) -> u8;
+
+    /// Write a byte into the data register.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_target_write(&mut self, byte: u8);
+
+    /// Release the bus and continue with the next step of the transaction.
+    ///
+    /// If `ack` is set, the next byte received (or the own address) is acknowledged.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_target_continue(&mut self, ack: bool);
+
+    /// Recover from a bus error by releasing SDA and SCL.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_target_recover(&mut self);
+}
+
+/// I2C target (slave) driver
+///
+/// The target answers to its own 7-bit address and optionally to the general call address.  It
+/// can be used in two ways:
+///
+/// - **Polling**: Call [`I2cTarget::poll`] regularly and handle the returned [`TargetEvent`]s.
+///   On [`TargetEvent::ReadRequest`], the next byte must be passed to [`I2cTarget::respond`].
+/// - **Interrupt-driven**: Enable the TWI interrupt using [`I2cTarget::listen`] and call
+///   [`I2cTarget::handle_interrupt`] from the `TWI` interrupt handler.  The callback is invoked
+///   for each event and supplies the response to read requests.
+///
+/// While the peripheral waits for the application, the bus is held by stretching SCL.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let mut target = arduino_hal::i2c::I2cTarget::new(
+///     dp.TWI,
+///     pins.a4.into_pull_up_input(),
+///     pins.a5.into_pull_up_input(),
+///     0x42,
+/// );
+///
+/// let mut register = 0u8;
+/// loop {
+///     match nb::block!(target.poll()) {
+///         Ok(TargetEvent::Received(b)) => register = b,
+///         Ok(TargetEvent::ReadRequest) => target.respond(register),
+///         _ => (),
+///     }
+/// }
+/// ```
+pub struct I2cTarget<H, I2C: I2cTargetOps<H, SDA, SCL>, SDA, SCL> {
+    p: I2C,
+    sda: SDA,
+    scl: SCL,
+    address: u8,
+    mask: u8,
+    general_call: bool,
+    _h: PhantomData<H>,
+}
+
+impl<H, I2C, SDAPIN, SCLPIN>
+    I2cTarget<H, I2C, port::Pin<port::mode::Input, SDAPIN>, port::Pin<port::mode::Input, SCLPIN>>
+where
+    I2C:
+        I2cTargetOps<H, port::Pin<port::mode::Input, SDAPIN>, port::Pin<port::mode::Input, SCLPIN>>,
+    SDAPIN: port::PinOps,
+    SCLPIN: port::PinOps,
+{
+    /// Initialize an I2C peripheral as a target with the 7-bit address `address`.
+    ///
+    /// Note that the SDA and SCL pins are hardwired for each I2C peripheral and you *must* pass
+    /// the correct ones.  This is enforced at compile time.
+    ///
+    /// This method expects the internal pull-ups to be configured for both pins to comply with the
+    /// I2C specification.  If you have external pull-ups connected, use
+    /// [`I2cTarget::with_external_pullup`] instead.
+    pub fn new(
+        p: I2C,
+        sda: port::Pin<port::mode::Input<port::mode::PullUp>, SDAPIN>,
+        scl: port::Pin<port::mode::Input<port::mode::PullUp>, SCLPIN>,
+        address: u8,
+    ) -> Self {
+        Self::init(p, sda.forget_imode(), scl.forget_imode(), address)
+    }
+
+    /// Initialize an I2C peripheral as a target with the 7-bit address `address`.
+    ///
+    /// Note that the SDA and SCL pins are hardwired for each I2C peripheral and you *must* pass
+    /// the correct ones.  This is enforced at compile time.
+    ///
+    /// This method expects that external resistors pull up SDA and SCL.
+    pub fn with_external_pullup(
+        p: I2C,
+        sda: port::Pin<port::mode::Input<port::mode::Floating>, SDAPIN>,
+        scl: port::Pin<port::mode::Input<port::mode::Floating>, SCLPIN>,
+        address: u8,
+    ) -> Self {
+        Self::init(p, sda.forget_imode(), scl.forget_imode(), address)
+    }
+
+    fn init(
+        p: I2C,
+        sda: port::Pin<port::mode::Input, SDAPIN>,
+        scl: port::Pin<port::mode::Input, SCLPIN>,
+        address: u8,
+    ) -> Self {
+        let mut target = Self {
+            p,
+            sda,
+            scl,
+            address,
+            mask: 0,
+            general_call: false,
+            _h: PhantomData,
+        };
+        target.reconfigure();
+        target
+    }
+}
+
+impl<H, I2C: I2cTargetOps<H, SDA, SCL>, SDA, SCL> I2cTarget<H, I2C, SDA, SCL> {
+    /// Disable the peripheral and release ownership of the peripheral and pins.
+    pub fn release(mut self) -> (I2C, SDA, SCL) {
+        self.p.raw_target_release();
+        (self.p, self.sda, self.scl)
+    }
+
+    /// Enable/Disable answering to the general call address (0x00).
+    pub fn set_general_call(&mut self, enable: bool) {
+        self.general_call = enable;
+        self.reconfigure();
+    }
+
+    /// Set a mask of address bits to ignore.
+    ///
+    /// This allows answering to a range of addresses.  Which address was used by the controller
+    /// can't be determined.
+    ///
+    /// **Note**: Some devices (ATmega8, ATmega32A, ATmega128A) don't support address masks.  The
+    /// mask is ignored there.
+    pub fn set_address_mask(&mut self, mask: u8) {
+        self.mask = mask;
+        self.reconfigure();
+    }
+
+    /// Enable the `TWI` interrupt.
+    ///
+    /// The interrupt handler must call [`I2cTarget::handle_interrupt`], otherwise it will fire
+    /// continuously.
+    pub fn listen(&mut self) {
+        self.p.raw_target_interrupt(true);
+    }
+
+    /// Disable the `TWI` interrupt.
+    pub fn unlisten(&mut self) {
+        self.p.raw_target_interrupt(false);
+    }
+
+    /// Check for the next event.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] if nothing happened on the bus.  After a
+    /// [`TargetEvent::ReadRequest`], the bus stays blocked until [`I2cTarget::respond`] is
+    /// called.  All other events are acknowledged automatically.
+    pub fn poll(&mut self) -> nb::Result<TargetEvent, Error> {
+        let status = self.p.raw_target_status().ok_or(nb::Error::WouldBlock)?;
+        let event = match status {
+            twi_status::TW_SR_SLA_ACK | twi_status::TW_SR_ARB_LOST_SLA_ACK => {
+                TargetEvent::WriteRequest {
+                    general_call: false,
+                }
+            }
+            twi_status::TW_SR_GCALL_ACK | twi_status::TW_SR_ARB_LOST_GCALL_ACK => {
+                TargetEvent::WriteRequest { general_call: true }
+            }
+            twi_status::TW_SR_DATA_ACK
+            | twi_status::TW_SR_DATA_NACK
+            | twi_status::TW_SR_GCALL_DATA_ACK
+            | twi_status::TW_SR_GCALL_DATA_NACK => TargetEvent::Received(self.p.raw_target_read()),
+            twi_status::TW_SR_STOP | twi_status::TW_ST_DATA_NACK | twi_status::TW_ST_LAST_DATA => {
+                TargetEvent::Stop
+            }
+            twi_status::TW_ST_SLA_ACK
+            | twi_status::TW_ST_ARB_LOST_SLA_ACK
+            | twi_status::TW_ST_DATA_ACK => {
+                // Leave the bus blocked until respond() was called.
+                return Ok(TargetEvent::ReadRequest);
+            }
+            twi_status::TW_BUS_ERROR => {
+                self.p.raw_target_recover();
+                return Err(nb::Error::Other(Error::BusError));
+            }
+            _ => {
+                self.p.raw_target_continue(true);
+                return Err(nb::Error::Other(Error::Unknown));
+            }
+        };
+        self.p.raw_target_continue(true);
+        Ok(event)
+    }
+
+    /// Send a byte in response to a [`TargetEvent::ReadRequest`].
+    pub fn respond(&mut self, byte: u8) {
+        self.p.raw_target_write(byte);
+        self.p.raw_target_continue(true);
+    }
+
+    /// Handle the event which triggered the `TWI` interrupt.
+    ///
+    /// `f` is called with the event and must return the byte to send for a
+    /// [`TargetEvent::ReadRequest`].  The return value is ignored for all other events.  Errors
+    /// are handled internally and not reported.
+    ///
+    /// # Example
+    /// ```
+    /// static TARGET: Mutex<RefCell<Option<I2cTarget>>> = Mutex::new(RefCell::new(None));
+    ///
+    /// #[avr_device::interrupt(atmega328p)]
+    /// fn TWI() {
+    ///     avr_device::interrupt::free(|cs| {
+    ///         if let Some(target) = TARGET.borrow(cs).borrow_mut().as_mut() {
+    ///             target.handle_interrupt(|event| match event {
+    ///                 TargetEvent::ReadRequest => 0x42,
+    ///                 _ => 0,
+    ///             });
+    ///         }
+    ///     })
+    /// }
+    /// ```
+    pub fn handle_interrupt<F: FnOnce(TargetEvent) -> u8>(&mut self, f: F) {
+        if let Ok(event) = self.poll() {
+            let response = f(event);
+            if event == TargetEvent::ReadRequest {
+                self.respond(response);
+            }
+        }
+    }
+
+    fn reconfigure(&mut self) {
+        self.p
+            .raw_target_setup(self.address, self.mask, self.general_call);
+    }
+}
+
 #[macro_export]
 macro_rules! impl_i2c_twi {
     (
@@ -454,6 +1296,28 @@ macro_rules! impl_i2c_twi {
         peripheral: $I2C:ty,
         sda: $sdapin:ty,
         scl: $sclpin:ty,
+    ) => {
+        $crate::impl_i2c_twi! {
+            hal: $HAL,
+            peripheral: $I2C,
+            sda: $sdapin,
+            scl: $sclpin,
+            address_mask: true,
+        }
+    };
+    (@address_mask true, $twi:expr, $mask:expr) => {
+        $twi.twamr.write(|w| unsafe { w.bits($mask << 1) });
+    };
+    (@address_mask false, $twi:expr, $mask:expr) => {
+        // This device has no TWAMR register.
+        let _ = $mask;
+    };
+    (
+        hal: $HAL:ty,
+        peripheral: $I2C:ty,
+        sda: $sdapin:ty,
+        scl: $sclpin:ty,
+        address_mask: $address_mask:tt,
     ) => {
         impl
             $crate::i2c::I2cOps<
@@ -473,12 +1337,22 @@ macro_rules! impl_i2c_twi {
             }
 
             #[inline]
-            fn raw_start(&mut self, address: u8, direction: Direction) -> Result<(), Error> {
+            fn raw_release(&mut self) {
+                self.twcr.reset();
+            }
+
+            #[inline]
+            fn raw_start(
+                &mut self,
+                address: u8,
+                direction: Direction,
+                timeout: Option<u32>,
+            ) -> Result<(), Error> {
                 // Write start condition
                 self.twcr
                     .write(|w| w.twen().set_bit().twint().set_bit().twsta().set_bit());
                 // wait()
-                while self.twcr.read().twint().bit_is_clear() {}
+                $crate::i2c::wait_until(timeout, || self.twcr.read().twint().bit_is_set())?;
 
                 // Validate status
                 match self.twsr.read().tws().bits() {
@@ -505,7 +1379,7 @@ macro_rules! impl_i2c_twi {
                 self.twdr.write(|w| unsafe { w.bits(rawaddr) });
                 // transact()
                 self.twcr.write(|w| w.twen().set_bit().twint().set_bit());
-                while self.twcr.read().twint().bit_is_clear() {}
+                $crate::i2c::wait_until(timeout, || self.twcr.read().twint().bit_is_set())?;
 
                 // Check if the slave responded
                 match self.twsr.read().tws().bits() {
@@ -533,12 +1407,12 @@ macro_rules! impl_i2c_twi {
             }
 
             #[inline]
-            fn raw_write(&mut self, bytes: &[u8]) -> Result<(), Error> {
+            fn raw_write(&mut self, bytes: &[u8], timeout: Option<u32>) -> Result<(), Error> {
                 for byte in bytes {
                     self.twdr.write(|w| unsafe { w.bits(*byte) });
                     // transact()
                     self.twcr.write(|w| w.twen().set_bit().twint().set_bit());
-                    while self.twcr.read().twint().bit_is_clear() {}
+                    $crate::i2c::wait_until(timeout, || self.twcr.read().twint().bit_is_set())?;
 
                     match self.twsr.read().tws().bits() {
                         $crate::i2c::twi_status::TW_MT_DATA_ACK => (),
@@ -561,19 +1435,17 @@ macro_rules! impl_i2c_twi {
             }
 
             #[inline]
-            fn raw_read(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
+            fn raw_read(&mut self, buffer: &mut [u8], timeout: Option<u32>) -> Result<(), Error> {
                 let last = buffer.len() - 1;
                 for (i, byte) in buffer.iter_mut().enumerate() {
                     if i != last {
                         self.twcr
                             .write(|w| w.twint().set_bit().twen().set_bit().twea().set_bit());
-                        // wait()
-                        while self.twcr.read().twint().bit_is_clear() {}
                     } else {
                         self.twcr.write(|w| w.twint().set_bit().twen().set_bit());
-                        // wait()
-                        while self.twcr.read().twint().bit_is_clear() {}
                     }
+                    // wait()
+                    $crate::i2c::wait_until(timeout, || self.twcr.read().twint().bit_is_set())?;
 
                     match self.twsr.read().tws().bits() {
                         $crate::i2c::twi_status::TW_MR_DATA_ACK
@@ -601,5 +1473,136 @@ macro_rules! impl_i2c_twi {
                 Ok(())
             }
         }
+
+        impl
+            $crate::i2c::I2cIsrOps<
+                $HAL,
+                $crate::port::Pin<$crate::port::mode::Input, $sdapin>,
+                $crate::port::Pin<$crate::port::mode::Input, $sclpin>,
+            > for $I2C
+        {
+            fn raw_isr_start(&mut self) {
+                self.twcr.write(|w| {
+                    w.twen()
+                        .set_bit()
+                        .twint()
+                        .set_bit()
+                        .twsta()
+                        .set_bit()
+                        .twie()
+                        .set_bit()
+                });
+            }
+
+            fn raw_isr_stop(&mut self) {
+                self.twcr
+                    .write(|w| w.twen().set_bit().twint().set_bit().twsto().set_bit());
+            }
+
+            fn raw_isr_abort(&mut self) {
+                self.twcr.write(|w| w.twen().set_bit().twint().set_bit());
+            }
+
+            fn raw_isr_status(&mut self) -> u8 {
+                self.twsr.read().tws().bits()
+            }
+
+            fn raw_isr_send(&mut self, byte: u8) {
+                self.twdr.write(|w| unsafe { w.bits(byte) });
+                self.twcr
+                    .write(|w| w.twen().set_bit().twint().set_bit().twie().set_bit());
+            }
+
+            fn raw_isr_receive(&mut self, ack: bool) {
+                self.twcr.write(|w| {
+                    w.twen()
+                        .set_bit()
+                        .twint()
+                        .set_bit()
+                        .twea()
+                        .bit(ack)
+                        .twie()
+                        .set_bit()
+                });
+            }
+
+            fn raw_isr_data(&mut self) -> u8 {
+                self.twdr.read().bits()
+            }
+        }
+
+        impl
+            $crate::i2c::I2cTargetOps<
+                $HAL,
+                $crate::port::Pin<$crate::port::mode::Input, $sdapin>,
+                $crate::port::Pin<$crate::port::mode::Input, $sclpin>,
+            > for $I2C
+        {
+            fn raw_target_setup(&mut self, address: u8, mask: u8, general_call: bool) {
+                // TWGCE is the LSB of the address register.
+                self.twar
+                    .write(|w| unsafe { w.bits((address << 1) | general_call as u8) });
+                $crate::impl_i2c_twi!(@address_mask $address_mask, self, mask);
+                self.twcr.write(|w| w.twen().set_bit().twea().set_bit());
+            }
+
+            fn raw_target_release(&mut self) {
+                self.twcr.reset();
+                self.twar.reset();
+            }
+
+            fn raw_target_interrupt(&mut self, state: bool) {
+                // Writing zero to TWINT has no effect so an ongoing transaction is not disturbed.
+                self.twcr
+                    .modify(|_, w| w.twie().bit(state).twint().clear_bit());
+            }
+
+            fn raw_target_status(&mut self) -> Option<u8> {
+                if self.twcr.read().twint().bit_is_clear() {
+                    return None;
+                }
+                Some(self.twsr.read().tws().bits())
+            }
+
+            fn raw_target_read(&mut self) -> u8 {
+                self.twdr.read().bits()
+            }
+
+            fn raw_target_write(&mut self, byte: u8) {
+                self.twdr.write(|w| unsafe { w.bits(byte) });
+            }
+
+            fn raw_target_continue(&mut self, ack: bool) {
+                let interrupt = self.twcr.read().twie().bit();
+                self.twcr.write(|w| {
+                    w.twen()
+                        .set_bit()
+                        .twint()
+                        .set_bit()
+                        .twea()
+                        .bit(ack)
+                        .twie()
+                        .bit(interrupt)
+                });
+            }
+
+            fn raw_target_recover(&mut self) {
+                // A stop condition in target mode does not appear on the bus but releases SDA and
+                // SCL and returns to the not addressed state.
+                let interrupt = self.twcr.read().twie().bit();
+                self.twcr.write(|w| {
+                    w.twen()
+                        .set_bit()
+                        .twint()
+                        .set_bit()
+                        .twsto()
+                        .set_bit()
+                        .twea()
+                        .set_bit()
+                        .twie()
+                        .bit(interrupt)
+                });
+            }
+        }
     };
 }
@@ -0,0 +1,410 @@
+//! Input Capture Unit of 16-bit timers
+//!
+//! Check the documentation of [`InputCapture`] for details.
+
+use crate::port;
+use core::marker::PhantomData;
+
+pub use crate::simple_pwm::Prescaler;
+
+/// Edge of the `ICPn` pin which triggers a capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    /// Capture on a falling (high to low) edge.
+    Falling,
+    /// Capture on a rising (low to high) edge.
+    Rising,
+}
+
+impl Edge {
+    /// The opposite edge.
+    pub fn opposite(self) -> Self {
+        match self {
+            Edge::Falling => Edge::Rising,
+            Edge::Rising => Edge::Falling,
+        }
+    }
+}
+
+/// Input Capture Error
+#[derive(ufmt::derive::uDebug, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Error {
+    /// No edge was captured within one full period of the 16-bit counter.
+    Timeout,
+}
+
+/// Duty cycle of a signal, as measured by [`InputCapture::duty_cycle_us`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DutyCycle {
+    /// Time the signal was high, in microseconds.
+    pub high_us: u32,
+    /// Period of the signal, in microseconds.
+    pub period_us: u32,
+}
+
+impl DutyCycle {
+    /// Duty cycle in percent.
+    pub fn percent(&self) -> u8 {
+        if self.period_us == 0 {
+            return 0;
+        }
+        (self.high_us as u64 * 100 / self.period_us as u64) as u8
+    }
+}
+
+/// Internal trait for low-level input capture units.
+///
+/// This trait defines the common interface for the input capture units of all 16-bit timers.  It
+/// is used as an intermediate abstraction ontop of which the [`InputCapture`] API is built.
+/// **Prefer using the [`InputCapture`] API instead of this trait.**
+pub trait InputCaptureOps<H> {
+    /// Start the counter in normal mode with the given prescaler and configure the capture edge
+    /// and the noise canceler.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_setup(&mut self, prescaler: Prescaler, edge: Edge, noise_canceler: bool);
+
+    /// Stop the timer by disconnecting its clock source.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_stop(&mut self);
+
+    /// Select the edge which triggers a capture.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_edge(&mut self, edge: Edge);
+
+    /// Enable/Disable the noise canceler.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_noise_canceler(&mut self, state: bool);
+
+    /// Read the current value of the counter register (`TCNTn`).
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_counter(&self) -> u16;
+
+    /// Read the value of the input capture register (`ICRn`).
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_capture(&self) -> u16;
+
+    /// Enable/Disable the input capture interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_interrupt(&mut self, state: bool);
+
+    /// Check whether the input capture flag is set.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_is_pending(&self) -> bool;
+
+    /// Clear the input capture flag.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_pending(&mut self);
+}
+
+/// Pins which are connected to the input capture unit of a timer.
+pub trait IcpPin<H, TC>: port::PinOps {}
+
+/// Input capture driver
+///
+/// The timer counts freely and on each selected edge of its `ICPn` pin, the counter value is
+/// copied into the input capture register.  These timestamps can be fetched by polling with
+/// [`InputCapture::capture`] or from the `TIMERn_CAPT` interrupt handler with
+/// [`InputCapture::read_capture`].
+///
+/// For simple measurements, the blocking helpers [`InputCapture::pulse_width_us`],
+/// [`InputCapture::period_us`] and [`InputCapture::duty_cycle_us`] are available.  They can only
+/// measure durations shorter than one full period of the 16-bit counter, so the prescaler should
+/// be chosen accordingly.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let mut icp = arduino_hal::input_capture::InputCapture::new(
+///     dp.TC1,
+///     pins.d8.into_floating_input(),
+///     arduino_hal::input_capture::Prescaler::Prescale64,
+/// );
+///
+/// match icp.pulse_width_us(arduino_hal::input_capture::Edge::Rising) {
+///     Ok(us) => ufmt::uwriteln!(&mut serial, "Pulse: {} us", us).unwrap_infallible(),
+///     Err(_) => ufmt::uwriteln!(&mut serial, "No pulse").unwrap_infallible(),
+/// }
+/// ```
+pub struct InputCapture<H, TC, PIN, CLOCK> {
+    p: TC,
+    pin: port::Pin<port::mode::Input, PIN>,
+    prescaler: Prescaler,
+    edge: Edge,
+    _clock: PhantomData<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, TC, PIN, CLOCK> InputCapture<H, TC, PIN, CLOCK>
+where
+    TC: InputCaptureOps<H>,
+    PIN: IcpPin<H, TC>,
+    CLOCK: crate::clock::Clock,
+{
+    /// Start the timer with the given prescaler and capture rising edges on `pin`.
+    ///
+    /// The noise canceler is disabled and the input capture interrupt is not enabled.
+    pub fn new<IMODE>(
+        p: TC,
+        pin: port::Pin<port::mode::Input<IMODE>, PIN>,
+        prescaler: Prescaler,
+    ) -> Self {
+        let mut icp = Self {
+            p,
+            pin: pin.forget_imode(),
+            prescaler,
+            edge: Edge::Rising,
+            _clock: PhantomData,
+            _h: PhantomData,
+        };
+        icp.p.raw_interrupt(false);
+        icp.p.raw_setup(prescaler, Edge::Rising, false);
+        icp.p.raw_clear_pending();
+        icp
+    }
+
+    /// Stop the timer and release the peripheral and the pin.
+    pub fn release(mut self) -> (TC, port::Pin<port::mode::Input, PIN>) {
+        self.p.raw_interrupt(false);
+        self.p.raw_stop();
+        (self.p, self.pin)
+    }
+
+    /// Select the edge which triggers a capture.
+    ///
+    /// Changing the edge can set the input capture flag, so it is cleared afterwards.
+    pub fn set_edge(&mut self, edge: Edge) {
+        self.edge = edge;
+        self.p.raw_set_edge(edge);
+        self.p.raw_clear_pending();
+    }
+
+    /// The edge which currently triggers a capture.
+    pub fn edge(&self) -> Edge {
+        self.edge
+    }
+
+    /// Enable/Disable the noise canceler.
+    ///
+    /// With the noise canceler enabled, the input must be stable for four clock cycles before a
+    /// change is accepted.  This delays each capture by the same four cycles.
+    pub fn set_noise_canceler(&mut self, state: bool) {
+        self.p.raw_set_noise_canceler(state);
+    }
+
+    /// Read the current counter value.
+    pub fn counter(&self) -> u16 {
+        self.p.raw_read_counter()
+    }
+
+    /// Read the last captured timestamp.
+    ///
+    /// This is meant to be used from the `TIMERn_CAPT` interrupt handler, where the input capture
+    /// flag is cleared automatically.
+    pub fn read_capture(&self) -> u16 {
+        self.p.raw_read_capture()
+    }
+
+    /// Fetch a captured timestamp.
+    ///
+    /// Returns `WouldBlock` until the selected edge occurs.
+    pub fn capture(&mut self) -> nb::Result<u16, core::convert::Infallible> {
+        if self.p.raw_is_pending() {
+            let timestamp = self.p.raw_read_capture();
+            self.p.raw_clear_pending();
+            Ok(timestamp)
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    /// Enable the `TIMERn_CAPT` interrupt.
+    pub fn listen(&mut self) {
+        self.p.raw_interrupt(true);
+    }
+
+    /// Disable the `TIMERn_CAPT` interrupt.
+    pub fn unlisten(&mut self) {
+        self.p.raw_interrupt(false);
+    }
+
+    /// Check whether a capture is pending.
+    pub fn is_pending(&self) -> bool {
+        self.p.raw_is_pending()
+    }
+
+    /// Clear the input capture flag.
+    pub fn clear(&mut self) {
+        self.p.raw_clear_pending();
+    }
+
+    /// Convert a number of timer ticks into microseconds (rounded down).
+    pub fn ticks_to_us(&self, ticks: u32) -> u32 {
+        (ticks as u64 * self.prescaler.divider() as u64 * 1_000_000 / CLOCK::FREQ as u64) as u32
+    }
+
+    /// Measure the width of the next pulse in microseconds.
+    ///
+    /// The pulse starts with the given `edge` and ends with the opposite one, so
+    /// [`Edge::Rising`] measures a high pulse.  The pulse must start and end within one period
+    /// of the counter each, otherwise [`Error::Timeout`] is returned.
+    pub fn pulse_width_us(&mut self, edge: Edge) -> Result<u32, Error> {
+        self.set_edge(edge);
+        let start = self.wait_capture()?;
+        self.set_edge(edge.opposite());
+        let end = self.wait_capture()?;
+        Ok(self.ticks_to_us(end.wrapping_sub(start) as u32))
+    }
+
+    /// Measure the period of the signal in microseconds.
+    ///
+    /// The period is measured between two consecutive edges of the given kind.
+    pub fn period_us(&mut self, edge: Edge) -> Result<u32, Error> {
+        self.set_edge(edge);
+        let start = self.wait_capture()?;
+        let end = self.wait_capture()?;
+        Ok(self.ticks_to_us(end.wrapping_sub(start) as u32))
+    }
+
+    /// Measure the high time and the period of the signal in microseconds.
+    pub fn duty_cycle_us(&mut self) -> Result<DutyCycle, Error> {
+        self.set_edge(Edge::Rising);
+        let rise = self.wait_capture()?;
+        self.set_edge(Edge::Falling);
+        let fall = self.wait_capture()?;
+        self.set_edge(Edge::Rising);
+        let next_rise = self.wait_capture()?;
+        Ok(DutyCycle {
+            high_us: self.ticks_to_us(fall.wrapping_sub(rise) as u32),
+            period_us: self.ticks_to_us(next_rise.wrapping_sub(rise) as u32),
+        })
+    }
+
+    /// Wait for the next capture, at most one full period of the counter.
+    fn wait_capture(&mut self) -> Result<u16, Error> {
+        let mut last = self.p.raw_read_counter();
+        let mut elapsed: u32 = 0;
+        loop {
+            if let Ok(timestamp) = self.capture() {
+                return Ok(timestamp);
+            }
+            let now = self.p.raw_read_counter();
+            elapsed += now.wrapping_sub(last) as u32;
+            last = now;
+            if elapsed > u16::MAX as u32 {
+                return Err(Error::Timeout);
+            }
+        }
+    }
+}
+
+/// Implement [`InputCaptureOps`] for the input capture unit of a 16-bit timer
+#[macro_export]
+macro_rules! impl_input_capture {
+    (
+        hal: $HAL:ty,
+        peripheral: $TC:ty,
+        pin: $pin:ty,
+        tccra: $tccra:ident,
+        tccrb: $tccrb:ident,
+        tcnt: $tcnt:ident,
+        icr: $icr:ident,
+        timsk: $timsk:ident,
+        tifr: $tifr:ident,
+    ) => {
+        impl $crate::input_capture::InputCaptureOps<$HAL> for $TC {
+            fn raw_setup(
+                &mut self,
+                prescaler: $crate::input_capture::Prescaler,
+                edge: $crate::input_capture::Edge,
+                noise_canceler: bool,
+            ) {
+                // ICNCn is bit 7 and ICESn is bit 6 of TCCRnB, WGMn3:0 = 0 selects normal mode.
+                let cs = match prescaler {
+                    $crate::input_capture::Prescaler::Direct => 0b001,
+                    $crate::input_capture::Prescaler::Prescale8 => 0b010,
+                    $crate::input_capture::Prescaler::Prescale64 => 0b011,
+                    $crate::input_capture::Prescaler::Prescale256 => 0b100,
+                    $crate::input_capture::Prescaler::Prescale1024 => 0b101,
+                };
+                let ices = match edge {
+                    $crate::input_capture::Edge::Falling => 0,
+                    $crate::input_capture::Edge::Rising => 1 << 6,
+                };
+                let icnc = if noise_canceler { 1 << 7 } else { 0 };
+                self.$tccra.write(|w| unsafe { w.bits(0) });
+                self.$tccrb.write(|w| unsafe { w.bits(icnc | ices | cs) });
+            }
+
+            fn raw_stop(&mut self) {
+                self.$tccrb
+                    .modify(|r, w| unsafe { w.bits(r.bits() & !0b111) });
+            }
+
+            fn raw_set_edge(&mut self, edge: $crate::input_capture::Edge) {
+                self.$tccrb.modify(|r, w| unsafe {
+                    w.bits(match edge {
+                        $crate::input_capture::Edge::Falling => r.bits() & !(1 << 6),
+                        $crate::input_capture::Edge::Rising => r.bits() | 1 << 6,
+                    })
+                });
+            }
+
+            fn raw_set_noise_canceler(&mut self, state: bool) {
+                self.$tccrb.modify(|r, w| unsafe {
+                    w.bits(if state {
+                        r.bits() | 1 << 7
+                    } else {
+                        r.bits() & !(1 << 7)
+                    })
+                });
+            }
+
+            fn raw_read_counter(&self) -> u16 {
+                // 16-bit access needs a critical section, see `raw_read_counter()` in
+                // `impl_timer!`.
+                $crate::avr_device::interrupt::free(|_| self.$tcnt.read().bits())
+            }
+
+            fn raw_read_capture(&self) -> u16 {
+                $crate::avr_device::interrupt::free(|_| self.$icr.read().bits())
+            }
+
+            fn raw_interrupt(&mut self, state: bool) {
+                // ICIEn is bit 5 of TIMSKn.
+                $crate::avr_device::interrupt::free(|_| {
+                    self.$timsk.modify(|r, w| unsafe {
+                        w.bits(if state {
+                            r.bits() | 1 << 5
+                        } else {
+                            r.bits() & !(1 << 5)
+                        })
+                    })
+                });
+            }
+
+            fn raw_is_pending(&self) -> bool {
+                // ICFn is bit 5 of TIFRn.
+                self.$tifr.read().bits() & 1 << 5 != 0
+            }
+
+            fn raw_clear_pending(&mut self) {
+                // Flags are cleared by writing a logical one, all others must be written as zero.
+                self.$tifr.write(|w| unsafe { w.bits(1 << 5) });
+            }
+        }
+
+        impl $crate::input_capture::IcpPin<$HAL, $TC> for $pin {}
+    };
+}
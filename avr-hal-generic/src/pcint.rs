@@ -0,0 +1,311 @@
+//! Pin change interrupts (`PCINTn`)
+//!
+//! Pins are organized in groups of up to eight, each group sharing one pin change interrupt.
+//! Check the documentation of [`Pcint`] for details.
+use crate::port;
+use core::marker::PhantomData;
+
+/// Maximum number of pin change interrupt groups on any supported device.
+const MAX_GROUPS: usize = 4;
+
+/// Internal trait for low-level pin change interrupt peripherals.
+///
+/// This trait defines the common interface for the pin change interrupt controller.  It is used
+/// as an intermediate abstraction ontop of which the [`Pcint`] API is built.  **Prefer using the
+/// [`Pcint`] API instead of this trait.**
+pub trait PcintOps<H> {
+    /// Enable/Disable the pin change interrupt of `group`.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_interrupt(&mut self, group: u8, state: bool);
+    /// Read the pin change mask of `group`.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_mask(&self, group: u8) -> u8;
+    /// Write the pin change mask of `group`.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_write_mask(&mut self, group: u8, mask: u8);
+    /// Check whether the flag of `group` is set.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_is_pending(&self, group: u8) -> bool;
+    /// Clear the flag of `group`.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_pending(&mut self, group: u8);
+    /// Read the input levels of all pins in `group`, arranged by their bit in the group.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_inputs(&self, group: u8) -> u8;
+}
+
+/// Pins which can trigger a pin change interrupt.
+///
+/// `GROUP` is the number of the pin change interrupt (`PCINTn` vector) and `BIT` is the position
+/// of the pin in the group's mask register.
+pub trait PcintPin<H, PCINT>: port::PinOps {
+    const GROUP: u8;
+    const BIT: u8;
+}
+
+/// Pin change interrupt controller
+///
+/// Pins are enabled and disabled individually using [`Pcint::enable`] and [`Pcint::disable`].
+/// The interrupt of a group is enabled as long as at least one of its pins is enabled.
+///
+/// A pin change interrupt does not tell which pin changed.  To find out, the controller keeps a
+/// snapshot of the input levels of each group.  Calling [`Pcint::changes`] from the `PCINTn`
+/// handler compares the current levels against this snapshot and reports the enabled pins which
+/// changed since the last call.  For this, the controller is usually moved into a global
+/// `Mutex<RefCell<Option<...>>>` which is shared with the interrupt handler.
+///
+/// # Example
+/// ```
+/// static PCINT: Mutex<RefCell<Option<atmega_hal::pcint::Pcint>>> =
+///     Mutex::new(RefCell::new(None));
+///
+/// #[avr_device::interrupt(atmega328p)]
+/// fn PCINT2() {
+///     avr_device::interrupt::free(|cs| {
+///         if let Some(pcint) = PCINT.borrow(cs).borrow_mut().as_mut() {
+///             let changes = pcint.changes(2);
+///             if changes.contains::<atmega_hal::port::PD2>() {
+///                 // ...
+///             }
+///         }
+///     })
+/// }
+///
+/// let dp = atmega_hal::Peripherals::take().unwrap();
+/// let pins = atmega_hal::pins!(dp);
+///
+/// let button = pins.pd2.into_pull_up_input();
+/// let mut pcint = atmega_hal::pcint::Pcint::new(dp.EXINT);
+/// pcint.enable(&button);
+/// avr_device::interrupt::free(|cs| PCINT.borrow(cs).replace(Some(pcint)));
+///
+/// unsafe { avr_device::interrupt::enable() };
+/// ```
+pub struct Pcint<H, PCINT> {
+    p: PCINT,
+    snapshot: [u8; MAX_GROUPS],
+    _h: PhantomData<H>,
+}
+
+impl<H, PCINT: PcintOps<H>> Pcint<H, PCINT> {
+    /// Take ownership of the pin change interrupt controller.
+    ///
+    /// All pin change interrupts are disabled initially.
+    pub fn new(mut p: PCINT) -> Self {
+        let mut snapshot = [0; MAX_GROUPS];
+        for (group, levels) in snapshot.iter_mut().enumerate() {
+            let group = group as u8;
+            if p.raw_read_mask(group) != 0 {
+                p.raw_interrupt(group, false);
+                p.raw_write_mask(group, 0);
+            }
+            *levels = p.raw_read_inputs(group);
+        }
+        Self {
+            p,
+            snapshot,
+            _h: PhantomData,
+        }
+    }
+
+    /// Disable all pin change interrupts and release the controller.
+    pub fn release(mut self) -> PCINT {
+        for group in 0..MAX_GROUPS as u8 {
+            if self.p.raw_read_mask(group) != 0 {
+                self.p.raw_interrupt(group, false);
+                self.p.raw_write_mask(group, 0);
+            }
+        }
+        self.p
+    }
+
+    /// Enable the pin change interrupt for a pin.
+    ///
+    /// The current level of the pin is taken as the reference for [`Pcint::changes`].
+    pub fn enable<MODE, PIN: PcintPin<H, PCINT>>(&mut self, _pin: &port::Pin<MODE, PIN>) {
+        let bit = 1 << PIN::BIT;
+        let mask = self.p.raw_read_mask(PIN::GROUP);
+        let levels = self.p.raw_read_inputs(PIN::GROUP);
+        let snapshot = &mut self.snapshot[PIN::GROUP as usize];
+        *snapshot = *snapshot & !bit | levels & bit;
+        self.p.raw_write_mask(PIN::GROUP, mask | bit);
+        if mask == 0 {
+            self.p.raw_clear_pending(PIN::GROUP);
+            self.p.raw_interrupt(PIN::GROUP, true);
+        }
+    }
+
+    /// Disable the pin change interrupt for a pin.
+    ///
+    /// The interrupt of the group is disabled when no other pin of the group is enabled.
+    pub fn disable<MODE, PIN: PcintPin<H, PCINT>>(&mut self, _pin: &port::Pin<MODE, PIN>) {
+        let mask = self.p.raw_read_mask(PIN::GROUP) & !(1 << PIN::BIT);
+        if mask == 0 {
+            self.p.raw_interrupt(PIN::GROUP, false);
+        }
+        self.p.raw_write_mask(PIN::GROUP, mask);
+    }
+
+    /// Check whether the pin change interrupt for a pin is enabled.
+    pub fn is_enabled<MODE, PIN: PcintPin<H, PCINT>>(&self, _pin: &port::Pin<MODE, PIN>) -> bool {
+        self.p.raw_read_mask(PIN::GROUP) & 1 << PIN::BIT != 0
+    }
+
+    /// Check whether the interrupt of a group is pending.
+    pub fn is_pending(&self, group: u8) -> bool {
+        self.p.raw_is_pending(group)
+    }
+
+    /// Clear the pending interrupt of a group.
+    pub fn clear_pending(&mut self, group: u8) {
+        self.p.raw_clear_pending(group);
+    }
+
+    /// Report which enabled pins of a group changed since the last call.
+    ///
+    /// This is meant to be called from the `PCINTn` handler of the group.  Changes which happen
+    /// in quick succession, before the handler runs, can cancel each other out and are then not
+    /// reported.
+    ///
+    /// Groups which do not exist on the device never report any changes.
+    pub fn changes(&mut self, group: u8) -> Changes<H, PCINT> {
+        let levels = self.p.raw_read_inputs(group);
+        let changed = match self.snapshot.get_mut(group as usize) {
+            Some(snapshot) => {
+                let changed = (levels ^ *snapshot) & self.p.raw_read_mask(group);
+                *snapshot = levels;
+                changed
+            }
+            None => 0,
+        };
+        Changes {
+            group,
+            changed,
+            levels,
+            _p: PhantomData,
+        }
+    }
+}
+
+/// Pins of a group which changed, as reported by [`Pcint::changes`].
+pub struct Changes<H, PCINT> {
+    group: u8,
+    changed: u8,
+    levels: u8,
+    _p: PhantomData<(H, PCINT)>,
+}
+
+impl<H, PCINT> Changes<H, PCINT> {
+    /// The group these changes belong to.
+    pub fn group(&self) -> u8 {
+        self.group
+    }
+
+    /// Mask of the changed pins, arranged by their bit in the group.
+    pub fn bits(&self) -> u8 {
+        self.changed
+    }
+
+    /// Check whether no pin changed.
+    pub fn is_empty(&self) -> bool {
+        self.changed == 0
+    }
+
+    /// Check whether the given pin changed.
+    pub fn contains<PIN: PcintPin<H, PCINT>>(&self) -> bool {
+        PIN::GROUP == self.group && self.changed & 1 << PIN::BIT != 0
+    }
+
+    /// Check whether the given pin was high when the changes were read.
+    ///
+    /// Together with [`Changes::contains`] this tells rising from falling edges.
+    pub fn is_high<PIN: PcintPin<H, PCINT>>(&self) -> bool {
+        PIN::GROUP == self.group && self.levels & 1 << PIN::BIT != 0
+    }
+}
+
+/// Implement traits for the pin change interrupt controller and the `PCINTn` pins
+///
+/// Each group lists the bit of its `PCIEn`/`PCIFn` flags, its mask register, an expression which
+/// reads the input levels of the group and its pins.  `pcicr` and `pcifr` are the registers
+/// holding the interrupt enable and flag bits (`GIMSK`/`GIFR` on some ATtiny).
+#[macro_export]
+macro_rules! impl_pcint {
+    (
+        hal: $HAL:ty,
+        peripheral: $PCINT:ty,
+        pcicr: $pcicr:ident,
+        pcifr: $pcifr:ident,
+        groups: {
+            $($group:literal: {
+                pcie: $pcie:literal,
+                pcmsk: $pcmsk:ident,
+                inputs: $inputs:expr,
+                pins: {
+                    $($pin:ty: $bit:literal,)+
+                },
+            },)+
+        },
+    ) => {
+        impl $crate::pcint::PcintOps<$HAL> for $PCINT {
+            fn raw_interrupt(&mut self, group: u8, state: bool) {
+                let bit = match group {
+                    $($group => 1 << $pcie,)+
+                    _ => return,
+                };
+                self.$pcicr.modify(|r, w| unsafe {
+                    w.bits(if state { r.bits() | bit } else { r.bits() & !bit })
+                });
+            }
+
+            fn raw_read_mask(&self, group: u8) -> u8 {
+                match group {
+                    $($group => self.$pcmsk.read().bits(),)+
+                    _ => 0,
+                }
+            }
+
+            fn raw_write_mask(&mut self, group: u8, mask: u8) {
+                match group {
+                    $($group => self.$pcmsk.write(|w| unsafe { w.bits(mask) }),)+
+                    _ => (),
+                }
+            }
+
+            fn raw_is_pending(&self, group: u8) -> bool {
+                match group {
+                    $($group => self.$pcifr.read().bits() & 1 << $pcie != 0,)+
+                    _ => false,
+                }
+            }
+
+            fn raw_clear_pending(&mut self, group: u8) {
+                // Flags are cleared by writing a one, all others must be written as zero.
+                match group {
+                    $($group => self.$pcifr.write(|w| unsafe { w.bits(1 << $pcie) }),)+
+                    _ => (),
+                }
+            }
+
+            fn raw_read_inputs(&self, group: u8) -> u8 {
+                match group {
+                    $($group => unsafe { $inputs },)+
+                    _ => 0,
+                }
+            }
+        }
+
+        $($(
+            impl $crate::pcint::PcintPin<$HAL, $PCINT> for $pin {
+                const GROUP: u8 = $group;
+                const BIT: u8 = $bit;
+            }
+        )+)+
+    };
+}
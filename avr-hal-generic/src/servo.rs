@@ -0,0 +1,114 @@
+//! Hobby servo driver on top of 16-bit timer PWM
+//!
+//! Check the documentation of [`Servo`] for details.
+use crate::port::{mode, Pin};
+use crate::simple_pwm::{IntoPwmPin, Pwm16, Pwm16Mode, Pwm16Ops, PwmPinOps};
+
+/// Frame rate of the servo signal in Hertz.
+pub const FREQUENCY: u32 = 50;
+
+/// Hobby servo on an output compare pin of a 16-bit timer
+///
+/// The servo position is set through the width of a pulse which is repeated every 20 ms.  The
+/// pulse width is limited to a configurable range, which is 1000 µs to 2000 µs initially.
+/// [`Servo::set_angle`] maps 0° to 180° onto this range.
+///
+/// The timer must run at 50 Hz, see [`Pwm16::for_servos`].  The pulse width resolution then is
+/// 0.5 µs with a 16 MHz clock and 1 µs with an 8 MHz clock.  The servo borrows the timer, so its
+/// frequency cannot be changed while servos are attached.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let timer1 = arduino_hal::simple_pwm::Pwm16::for_servos(dp.TC1).unwrap();
+///
+/// let mut servo = arduino_hal::servo::Servo::new(pins.d9.into_output(), &timer1);
+/// servo.set_limits(500, 2500);
+/// servo.set_angle(90);
+/// ```
+pub struct Servo<'a, H, TC, PIN, CLOCK> {
+    timer: &'a Pwm16<H, TC, CLOCK>,
+    pin: Pin<mode::PwmOutput<Pwm16<H, TC, CLOCK>>, PIN>,
+    min_us: u16,
+    max_us: u16,
+    pulse_us: u16,
+}
+
+impl<'a, H, TC, PIN, CLOCK> Servo<'a, H, TC, PIN, CLOCK>
+where
+    TC: Pwm16Ops<H>,
+    PIN: PwmPinOps<Pwm16<H, TC, CLOCK>, Duty = u16>,
+    CLOCK: crate::clock::Clock,
+{
+    /// Attach a servo to `pin`, moving it to the center position.
+    pub fn new(pin: Pin<mode::Output, PIN>, timer: &'a Pwm16<H, TC, CLOCK>) -> Self {
+        let mut servo = Self {
+            timer,
+            pin: pin.into_pwm(timer),
+            min_us: 1000,
+            max_us: 2000,
+            pulse_us: 1500,
+        };
+        servo.set_pulse_us(1500);
+        servo.pin.enable();
+        servo
+    }
+
+    /// Limit the pulse width to the range from `min_us` to `max_us` microseconds.
+    ///
+    /// The current pulse width is clamped into the new range.  If `min_us` is larger than
+    /// `max_us`, the two are swapped.
+    pub fn set_limits(&mut self, min_us: u16, max_us: u16) {
+        self.min_us = min_us.min(max_us);
+        self.max_us = max_us.max(min_us);
+        self.set_pulse_us(self.pulse_us);
+    }
+
+    /// The configured limits of the pulse width in microseconds.
+    pub fn limits(&self) -> (u16, u16) {
+        (self.min_us, self.max_us)
+    }
+
+    /// Set the pulse width in microseconds, clamped to the configured limits.
+    pub fn set_pulse_us(&mut self, us: u16) {
+        let us = us.clamp(self.min_us, self.max_us);
+        // Divide the clock down to kHz first to stay within `u32` for pulses of up to 20 ms.
+        let divider = self.timer.period().prescaler.divider();
+        let mut ticks = us as u32 * (CLOCK::FREQ / 1000) / (divider * 1000);
+        // In the dual-slope modes, the pulse is high for `2 * OCR` ticks.
+        if self.timer.mode() != Pwm16Mode::Fast {
+            ticks /= 2;
+        }
+        self.pin.set_duty(ticks.min(u16::MAX as u32) as u16);
+        self.pulse_us = us;
+    }
+
+    /// The current pulse width in microseconds.
+    pub fn pulse_us(&self) -> u16 {
+        self.pulse_us
+    }
+
+    /// Move the servo to `degrees` between 0 and 180, mapped linearly onto the pulse width
+    /// limits.  Larger values are treated as 180°.
+    pub fn set_angle(&mut self, degrees: u8) {
+        let degrees = degrees.min(180) as u32;
+        let range = (self.max_us - self.min_us) as u32;
+        self.set_pulse_us(self.min_us + (range * degrees / 180) as u16);
+    }
+
+    /// Stop sending pulses, which lets most servos go limp.
+    pub fn detach(&mut self) {
+        self.pin.disable();
+    }
+
+    /// Resume sending pulses after [`Servo::detach`].
+    pub fn attach(&mut self) {
+        self.pin.enable();
+    }
+
+    /// Stop sending pulses and release the PWM pin.
+    pub fn release(mut self) -> Pin<mode::PwmOutput<Pwm16<H, TC, CLOCK>>, PIN> {
+        self.pin.disable();
+        self.pin
+    }
+}
@@ -16,6 +16,9 @@ use crate::port::Pin;
 /// F_pwm = CLK_io / (Prescaler * 256);
 /// ```
 ///
+/// In [`PwmMode::PhaseCorrect`] the counter counts up and down, which takes 510 ticks, so the
+/// frequencies are roughly halved.
+///
 /// | Prescaler | 16 MHz Clock | 8 MHz Clock |
 /// | --- | --- | ---|
 /// | `Direct` | 62.5 kHz | 31.3 kHz |
@@ -37,16 +40,61 @@ pub enum Prescaler {
     Prescale1024,
 }
 
+impl Prescaler {
+    /// Division factor between the IO clock and the timer clock.
+    pub const fn divider(self) -> u32 {
+        match self {
+            Prescaler::Direct => 1,
+            Prescaler::Prescale8 => 8,
+            Prescaler::Prescale64 => 64,
+            Prescaler::Prescale256 => 256,
+            Prescaler::Prescale1024 => 1024,
+        }
+    }
+}
+
+/// Waveform generation mode of the simple PWM timers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PwmMode {
+    /// Fast PWM: The counter counts from 0 to 255 and restarts.
+    ///
+    /// The output is set at `BOTTOM` and cleared on compare match, so a duty cycle of 0 still
+    /// produces a narrow spike.
+    Fast,
+    /// Phase correct PWM: The counter counts up to 255 and back down again.
+    ///
+    /// The pulses are centered within each period and the frequency is halved compared to fast
+    /// PWM.  A duty cycle of 0 keeps the output low, 255 keeps it high.
+    PhaseCorrect,
+}
+
+/// Waveform generation mode of [`Pwm16`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pwm16Mode {
+    /// Fast PWM with `ICRn` as `TOP` (mode 14).
+    Fast,
+    /// Phase correct PWM with `ICRn` as `TOP` (mode 10).
+    ///
+    /// The counter counts up to `TOP` and back down again, producing center-aligned pulses.
+    PhaseCorrect,
+    /// Phase and frequency correct PWM with `ICRn` as `TOP` (mode 8).
+    ///
+    /// Like [`Pwm16Mode::PhaseCorrect`], but the compare registers are updated at `BOTTOM`, so
+    /// the waveform stays symmetric when the frequency is changed while running.
+    PhaseFrequencyCorrect,
+}
+
 /// Implement traits and types for PWM timers
 pub trait PwmPinOps<TC> {
     type Duty;
 
     fn enable(&mut self);
+    fn enable_inverted(&mut self);
     fn disable(&mut self);
     fn get_duty(&self) -> Self::Duty;
     fn get_max_duty(&self) -> Self::Duty;
 
-    fn set_duty(&mut self, value: u8);
+    fn set_duty(&mut self, value: Self::Duty);
 }
 
 pub trait IntoPwmPin<TC, PIN> {
@@ -67,6 +115,13 @@ impl<TC, PIN: PwmPinOps<TC>> Pin<mode::PwmOutput<TC>, PIN> {
         self.pin.enable();
     }
 
+    /// Enable the PWM output with inverted polarity (`COMnx = 0b11`).
+    ///
+    /// The pin is low for the duty cycle and high for the rest of the period.
+    pub fn enable_inverted(&mut self) {
+        self.pin.enable_inverted();
+    }
+
     pub fn disable(&mut self) {
         self.pin.disable();
     }
@@ -79,15 +134,17 @@ impl<TC, PIN: PwmPinOps<TC>> Pin<mode::PwmOutput<TC>, PIN> {
         self.pin.get_max_duty()
     }
 
-    pub fn set_duty(&mut self, duty: u8) {
+    pub fn set_duty(&mut self, duty: <PIN as PwmPinOps<TC>>::Duty) {
         self.pin.set_duty(duty);
     }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum PwmError {
-    /// `embedded-hal` supports duty cycles up to `u16`, however `avr` devices only support up to `u8`.
-    /// Passing a duty cycle larger than [`u8::MAX`] will result in this error.
+    /// The duty cycle is larger than the maximum duty cycle of the timer.
+    ///
+    /// Simple 8-bit timers only support duty cycles up to [`u8::MAX`], for [`Pwm16`] the maximum
+    /// is the `TOP` value which was derived from the frequency.
     DutyCycleTooLarge,
 }
 
@@ -101,16 +158,21 @@ impl<TC, PIN: PwmPinOps<TC>> ErrorType for Pin<mode::PwmOutput<TC>, PIN> {
     type Error = PwmError;
 }
 
-impl<TC, PIN: PwmPinOps<TC, Duty = u8>> SetDutyCycle for Pin<mode::PwmOutput<TC>, PIN> {
+impl<TC, PIN> SetDutyCycle for Pin<mode::PwmOutput<TC>, PIN>
+where
+    PIN: PwmPinOps<TC>,
+    PIN::Duty: Into<u16> + TryFrom<u16>,
+{
     fn max_duty_cycle(&self) -> u16 {
-        self.get_max_duty() as u16
+        self.get_max_duty().into()
     }
 
     fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
-        if duty > u8::MAX as u16 {
+        if duty > self.max_duty_cycle() {
             return Err(PwmError::DutyCycleTooLarge);
         }
-        self.set_duty(duty as u8);
+        let duty = PIN::Duty::try_from(duty).map_err(|_| PwmError::DutyCycleTooLarge)?;
+        self.set_duty(duty);
         Ok(())
     }
 }
@@ -122,11 +184,13 @@ macro_rules! impl_simple_pwm {
         pub struct $TimerPwm:ident {
             timer: $TIMER:ty,
             init: |$init_timer:ident, $prescaler:ident| $init_block:block,
+            $(mode: |$mode_timer:ident, $mode:ident| $mode_block:block,)?
             pins: {$(
                 $PXi:ident: {
                     ocr: $ocr:ident,
                     $into_pwm:ident: |$pin_timer:ident| if enable
                         $pin_enable_block:block else $pin_disable_block:block,
+                    inverted: |$inv_timer:ident| $pin_inverted_block:block,
                 },
             )+},
         }
@@ -148,6 +212,27 @@ macro_rules! impl_simple_pwm {
 
                 t
             }
+
+            $(
+                /// Start the timer in the given waveform mode.
+                ///
+                /// [`new`][Self::new] keeps the default mode of the timer.
+                pub fn with_mode(
+                    timer: $TIMER,
+                    prescaler: $crate::simple_pwm::Prescaler,
+                    mode: $crate::simple_pwm::PwmMode,
+                ) -> $TimerPwm {
+                    let mut t = Self::new(timer, prescaler);
+
+                    {
+                        let $mode_timer = &mut t.timer;
+                        let $mode = mode;
+                        $mode_block
+                    }
+
+                    t
+                }
+            )?
         }
 
         $(
@@ -164,6 +249,14 @@ macro_rules! impl_simple_pwm {
                     });
                 }
 
+                fn enable_inverted(&mut self) {
+                    // SAFETY: See `enable()`.
+                    $crate::avr_device::interrupt::free(|_| {
+                        let $inv_timer = unsafe { &*<$TIMER>::ptr() };
+                        $pin_inverted_block
+                    });
+                }
+
                 fn disable(&mut self) {
                     // SAFETY: This block will usually result in a read-modify-write sequence which
                     // is not concurrency safe.  Thus, it is wrapped in a critical section which
@@ -191,3 +284,297 @@ macro_rules! impl_simple_pwm {
         )+
     }
 }
+
+/// Internal trait for 16-bit timers in PWM mode with `ICRn` as `TOP`.
+///
+/// This trait is used as an intermediate abstraction ontop of which the [`Pwm16`] API is built.
+/// **Prefer using the [`Pwm16`] API instead of this trait.**
+pub trait Pwm16Ops<H> {
+    /// Configure the PWM `mode` with `ICRn` as `TOP`, set `TOP` and start the timer with the given
+    /// prescaler.
+    ///
+    /// The compare output modes of the PWM pins must be preserved.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_setup(&mut self, prescaler: Prescaler, top: u16, mode: Pwm16Mode);
+}
+
+/// PWM on a 16-bit timer with configurable frequency
+///
+/// In contrast to the simple PWM timers which always count up to 255, the `TOP` value of the
+/// counter is set through the `ICRn` register.  This allows setting the PWM frequency precisely
+/// while keeping the highest possible duty cycle resolution.  The resulting frequency is
+///
+/// ```text
+/// F_pwm = CLK_io / (Prescaler * (TOP + 1))     (fast PWM)
+/// F_pwm = CLK_io / (2 * Prescaler * TOP)       (phase (and frequency) correct PWM)
+/// ```
+///
+/// The maximum duty cycle of all pins is `TOP` in every [`Pwm16Mode`], see [`Pwm16::max_duty`].
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let mut timer1 = arduino_hal::simple_pwm::Pwm16::new(dp.TC1, 50).unwrap();
+///
+/// let mut d9 = pins.d9.into_output().into_pwm(&timer1);
+/// // 1.5 ms pulse out of the 20 ms period
+/// d9.set_duty(timer1.max_duty() / 40 * 3);
+/// d9.enable();
+/// ```
+pub struct Pwm16<H, TC, CLOCK> {
+    timer: TC,
+    period: crate::timer::Period<CLOCK>,
+    mode: Pwm16Mode,
+    _h: PhantomData<H>,
+}
+
+impl<H, TC: Pwm16Ops<H>, CLOCK: crate::clock::Clock> Pwm16<H, TC, CLOCK> {
+    /// Start PWM with a frequency of `hz` Hertz.
+    ///
+    /// The prescaler is selected to get the best possible resolution.
+    pub fn new(timer: TC, hz: u32) -> Result<Self, crate::timer::Error> {
+        Self::with_mode(timer, hz, Pwm16Mode::Fast)
+    }
+
+    /// Start PWM with the 50 Hz frame rate expected by hobby servos.
+    ///
+    /// All output compare pins of the timer can then be used for a
+    /// [`Servo`][crate::servo::Servo] each.
+    pub fn for_servos(timer: TC) -> Result<Self, crate::timer::Error> {
+        Self::new(timer, crate::servo::FREQUENCY)
+    }
+
+    /// Start PWM in the given waveform mode with a frequency of `hz` Hertz.
+    pub fn with_mode(timer: TC, hz: u32, mode: Pwm16Mode) -> Result<Self, crate::timer::Error> {
+        Self::with_period(timer, Self::period_for(hz, mode)?, mode)
+    }
+
+    /// Start PWM in the given waveform mode with a precalculated
+    /// [`Period`][crate::timer::Period].
+    ///
+    /// `period.top` is used as `TOP` directly, so in the dual-slope modes the actual period is
+    /// twice as long as described by `period`.
+    pub fn with_period(
+        mut timer: TC,
+        period: crate::timer::Period<CLOCK>,
+        mode: Pwm16Mode,
+    ) -> Result<Self, crate::timer::Error> {
+        Self::setup(&mut timer, period, mode)?;
+        Ok(Self {
+            timer,
+            period,
+            mode,
+            _h: PhantomData,
+        })
+    }
+
+    /// Change the PWM frequency to `hz` Hertz.
+    ///
+    /// The duty cycles of the pins are not scaled along, so they should be set again afterwards.
+    /// The actually configured prescaler and `TOP` are returned.
+    pub fn set_frequency(
+        &mut self,
+        hz: u32,
+    ) -> Result<crate::timer::Period<CLOCK>, crate::timer::Error> {
+        let period = Self::period_for(hz, self.mode)?;
+        self.set_period(period)?;
+        Ok(period)
+    }
+
+    /// Change the PWM period to a precalculated [`Period`][crate::timer::Period].
+    ///
+    /// As for [`Pwm16::with_period`], `period.top` is used as `TOP` directly.
+    pub fn set_period(
+        &mut self,
+        period: crate::timer::Period<CLOCK>,
+    ) -> Result<(), crate::timer::Error> {
+        Self::setup(&mut self.timer, period, self.mode)?;
+        self.period = period;
+        Ok(())
+    }
+
+    /// Change the waveform mode, keeping the current frequency as close as possible.
+    pub fn set_mode(&mut self, mode: Pwm16Mode) -> Result<(), crate::timer::Error> {
+        let period = Self::period_for(self.frequency(), mode)?;
+        Self::setup(&mut self.timer, period, mode)?;
+        self.mode = mode;
+        self.period = period;
+        Ok(())
+    }
+
+    /// The currently configured waveform mode.
+    pub fn mode(&self) -> Pwm16Mode {
+        self.mode
+    }
+
+    /// The currently configured prescaler and `TOP` value.
+    pub fn period(&self) -> crate::timer::Period<CLOCK> {
+        self.period
+    }
+
+    /// The actual PWM frequency in Hertz, rounded down.
+    pub fn frequency(&self) -> u32 {
+        let ticks = match self.mode {
+            Pwm16Mode::Fast => self.period.top as u32 + 1,
+            _ => 2 * self.period.top as u32,
+        };
+        CLOCK::FREQ / (self.period.prescaler.divider() * ticks)
+    }
+
+    fn setup(
+        timer: &mut TC,
+        period: crate::timer::Period<CLOCK>,
+        mode: Pwm16Mode,
+    ) -> Result<(), crate::timer::Error> {
+        // The smallest `TOP` value allowed in these modes is 3 (2-bit resolution).
+        if period.top < 3 {
+            return Err(crate::timer::Error::PeriodOutOfRange);
+        }
+        timer.raw_setup(period.prescaler, period.top, mode);
+        Ok(())
+    }
+
+    fn period_for(
+        hz: u32,
+        mode: Pwm16Mode,
+    ) -> Result<crate::timer::Period<CLOCK>, crate::timer::Error> {
+        match mode {
+            Pwm16Mode::Fast => crate::timer::Period::from_hz(hz, u16::MAX),
+            _ => {
+                if hz == 0 {
+                    return Err(crate::timer::Error::PeriodOutOfRange);
+                }
+                // In the dual-slope modes one period takes `2 * TOP` ticks.  Calculating a
+                // period for half the cycles yields `TOP - 1` in place of `top`.
+                let half =
+                    crate::timer::Period::<CLOCK>::from_cycles(CLOCK::FREQ / hz / 2, u16::MAX - 1)?;
+                Ok(crate::timer::Period::with_exact(
+                    half.prescaler,
+                    half.top + 1,
+                ))
+            }
+        }
+    }
+
+    /// The maximum duty cycle, which is equal to `TOP`.
+    pub fn max_duty(&self) -> u16 {
+        self.period.top
+    }
+
+    /// Release the timer peripheral.
+    pub fn release(self) -> TC {
+        self.timer
+    }
+}
+
+/// Implement [`Pwm16Ops`] for a 16-bit timer and [`PwmPinOps`] for its output compare pins
+///
+/// `com` is the position of the `COMnx1:0` bits of each pin in `TCCRnA`.
+#[macro_export]
+macro_rules! impl_pwm16 {
+    (
+        hal: $HAL:ty,
+        peripheral: $TC:ty,
+        tccra: $tccra:ident,
+        tccrb: $tccrb:ident,
+        tcnt: $tcnt:ident,
+        icr: $icr:ident,
+        pins: {
+            $($pin:ty: {
+                ocr: $ocr:ident,
+                com: $com:literal,
+            },)+
+        },
+    ) => {
+        impl $crate::simple_pwm::Pwm16Ops<$HAL> for $TC {
+            fn raw_setup(
+                &mut self,
+                prescaler: $crate::simple_pwm::Prescaler,
+                top: u16,
+                mode: $crate::simple_pwm::Pwm16Mode,
+            ) {
+                let cs = match prescaler {
+                    $crate::simple_pwm::Prescaler::Direct => 0b001,
+                    $crate::simple_pwm::Prescaler::Prescale8 => 0b010,
+                    $crate::simple_pwm::Prescaler::Prescale64 => 0b011,
+                    $crate::simple_pwm::Prescaler::Prescale256 => 0b100,
+                    $crate::simple_pwm::Prescaler::Prescale1024 => 0b101,
+                };
+                // WGMn1:0 are bits 1:0 of TCCRnA, WGMn3:2 are bits 4:3 of TCCRnB.
+                let (wgm_a, wgm_b) = match mode {
+                    // Mode 14: Fast PWM with ICRn as TOP (WGMn3:0 = 0b1110).
+                    $crate::simple_pwm::Pwm16Mode::Fast => (0b10, 0b11),
+                    // Mode 10: Phase correct PWM with ICRn as TOP (WGMn3:0 = 0b1010).
+                    $crate::simple_pwm::Pwm16Mode::PhaseCorrect => (0b10, 0b10),
+                    // Mode 8: Phase and frequency correct PWM with ICRn as TOP (WGMn3:0 = 0b1000).
+                    $crate::simple_pwm::Pwm16Mode::PhaseFrequencyCorrect => (0b00, 0b10),
+                };
+                $crate::avr_device::interrupt::free(|_| {
+                    // ICRn is not double buffered, so the timer is stopped and restarted from
+                    // zero to never let the counter run past the new TOP value.
+                    self.$tccrb.write(|w| unsafe { w.bits(0) });
+                    self.$tccra.modify(|r, w| unsafe { w.bits(r.bits() & !0b11 | wgm_a) });
+                    self.$icr.write(|w| unsafe { w.bits(top) });
+                    self.$tcnt.write(|w| unsafe { w.bits(0) });
+                    self.$tccrb.write(|w| unsafe { w.bits(wgm_b << 3 | cs) });
+                });
+            }
+        }
+
+        $(
+            impl<CLOCK> $crate::simple_pwm::PwmPinOps<$crate::simple_pwm::Pwm16<$HAL, $TC, CLOCK>>
+                for $pin
+            {
+                type Duty = u16;
+
+                fn enable(&mut self) {
+                    // SAFETY: TCCRnA is shared by all pins of the timer, so the
+                    // read-modify-write sequence is wrapped in a critical section.
+                    $crate::avr_device::interrupt::free(|_| {
+                        let tc = unsafe { &*<$TC>::ptr() };
+                        // Non-inverting mode: Clear on compare match, set at BOTTOM.
+                        tc.$tccra.modify(|r, w| unsafe {
+                            w.bits(r.bits() & !(0b11 << $com) | 0b10 << $com)
+                        });
+                    });
+                }
+
+                fn enable_inverted(&mut self) {
+                    $crate::avr_device::interrupt::free(|_| {
+                        let tc = unsafe { &*<$TC>::ptr() };
+                        // Inverting mode: Set on compare match, clear at BOTTOM.
+                        tc.$tccra.modify(|r, w| unsafe { w.bits(r.bits() | 0b11 << $com) });
+                    });
+                }
+
+                fn disable(&mut self) {
+                    $crate::avr_device::interrupt::free(|_| {
+                        let tc = unsafe { &*<$TC>::ptr() };
+                        tc.$tccra.modify(|r, w| unsafe { w.bits(r.bits() & !(0b11 << $com)) });
+                    });
+                }
+
+                fn get_duty(&self) -> Self::Duty {
+                    // 16-bit access needs a critical section, see `raw_read_counter()` in
+                    // `impl_timer!`.
+                    $crate::avr_device::interrupt::free(|_| {
+                        unsafe { &*<$TC>::ptr() }.$ocr.read().bits()
+                    })
+                }
+
+                fn get_max_duty(&self) -> Self::Duty {
+                    $crate::avr_device::interrupt::free(|_| {
+                        unsafe { &*<$TC>::ptr() }.$icr.read().bits()
+                    })
+                }
+
+                fn set_duty(&mut self, duty: Self::Duty) {
+                    $crate::avr_device::interrupt::free(|_| {
+                        unsafe { (*<$TC>::ptr()).$ocr.write(|w| w.bits(duty)) };
+                    });
+                }
+            }
+        )+
+    };
+}
@@ -0,0 +1,315 @@
+//! Software (bit-banged) I2C controller
+//!
+//! For MCUs without a TWI peripheral or when a second bus is needed, [`SoftI2c`] can drive an I2C
+//! bus from any two GPIO pins.  Check the documentation of [`SoftI2c`] for details.
+use crate::i2c::{Direction, Error};
+use crate::port;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::SevenBitAddress;
+
+/// Bit-banged I2C controller on arbitrary pins
+///
+/// Both lines are driven in [open-drain][port::mode::OpenDrain] mode, so external pull-up
+/// resistors are required.  Devices which hold SCL low to slow down the transfer (clock
+/// stretching) are supported.  Use [`SoftI2c::set_timeout`] to limit how long the controller
+/// waits for them.
+///
+/// The `speed` passed to the constructor determines the delays between the edges.  The actual
+/// clock rate is somewhat lower because of the overhead of toggling the pins in software.
+///
+/// # Example
+/// ```
+/// let dp = attiny_hal::Peripherals::take().unwrap();
+/// let pins = attiny_hal::pins!(dp);
+///
+/// let mut i2c = attiny_hal::i2c::SoftI2c::<CoreClock, _, _>::new(
+///     pins.pb0.into_opendrain_high(),
+///     pins.pb2.into_opendrain_high(),
+///     100_000,
+/// );
+///
+/// let mut buffer = [0u8; 2];
+/// i2c.write_read(0x48, &[0x00], &mut buffer).unwrap();
+/// ```
+pub struct SoftI2c<CLOCK, SDAPIN, SCLPIN> {
+    sda: port::Pin<port::mode::OpenDrain, SDAPIN>,
+    scl: port::Pin<port::mode::OpenDrain, SCLPIN>,
+    half_period: u16,
+    timeout: Option<u32>,
+    started: bool,
+    delay: crate::delay::Delay<CLOCK>,
+}
+
+impl<CLOCK, SDAPIN, SCLPIN> SoftI2c<CLOCK, SDAPIN, SCLPIN>
+where
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: DelayNs,
+    SDAPIN: port::PinOps,
+    SCLPIN: port::PinOps,
+{
+    /// Set up a software I2C bus on the given pins with the given `speed` in Hz.
+    pub fn new(
+        sda: port::Pin<port::mode::OpenDrain, SDAPIN>,
+        scl: port::Pin<port::mode::OpenDrain, SCLPIN>,
+        speed: u32,
+    ) -> Self {
+        let mut i2c = Self {
+            sda,
+            scl,
+            half_period: (500_000 / speed).clamp(1, u16::MAX as u32) as u16,
+            timeout: None,
+            started: false,
+            delay: crate::delay::Delay::new(),
+        };
+        i2c.sda.set_high();
+        i2c.scl.set_high();
+        i2c
+    }
+
+    /// Release ownership of the pins.
+    pub fn release(
+        self,
+    ) -> (
+        port::Pin<port::mode::OpenDrain, SDAPIN>,
+        port::Pin<port::mode::OpenDrain, SCLPIN>,
+    ) {
+        (self.sda, self.scl)
+    }
+
+    /// Set the timeout for clock stretching in microseconds.
+    ///
+    /// If a device holds SCL low for longer than this, the operation is aborted with
+    /// [`Error::Timeout`].  `None` (the default) waits forever.  The timeout is only approximate
+    /// as it is implemented by counting loop iterations.
+    pub fn set_timeout(&mut self, timeout_us: Option<u32>) {
+        self.timeout = timeout_us
+            .map(|us| us.saturating_mul(CLOCK::FREQ / 1_000_000) / crate::i2c::WAIT_LOOP_CYCLES);
+    }
+
+    /// Test whether a device answers on a certain address.
+    pub fn ping_device(&mut self, address: u8, direction: Direction) -> Result<bool, Error> {
+        match self.start(address, direction) {
+            Ok(_) => {
+                self.stop()?;
+                Ok(true)
+            }
+            Err(Error::AddressNack) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn wait_half_period(&mut self) {
+        self.delay.delay_us(self.half_period.into());
+    }
+
+    /// Release SCL and wait until devices stop stretching the clock.
+    fn release_scl(&mut self) -> Result<(), Error> {
+        self.scl.set_high();
+        let scl = &self.scl;
+        crate::i2c::wait_until(self.timeout, || scl.is_high())
+    }
+
+    /// Release all lines after an error so the bus does not stay blocked.
+    fn abort(&mut self, error: Error) -> Error {
+        match error {
+            Error::AddressNack | Error::DataNack => {
+                if let Err(e) = self.stop() {
+                    return e;
+                }
+            }
+            _ => {
+                self.sda.set_high();
+                self.scl.set_high();
+                self.started = false;
+            }
+        }
+        error
+    }
+
+    fn write_bit(&mut self, bit: bool) -> Result<(), Error> {
+        if bit {
+            self.sda.set_high();
+        } else {
+            self.sda.set_low();
+        }
+        self.wait_half_period();
+        self.release_scl()?;
+        // Another controller pulling SDA low while we send a one means we lost arbitration.
+        if bit && self.sda.is_low() {
+            return Err(Error::ArbitrationLost);
+        }
+        self.wait_half_period();
+        self.scl.set_low();
+        Ok(())
+    }
+
+    fn read_bit(&mut self) -> Result<bool, Error> {
+        self.sda.set_high();
+        self.wait_half_period();
+        self.release_scl()?;
+        let bit = self.sda.is_high();
+        self.wait_half_period();
+        self.scl.set_low();
+        Ok(bit)
+    }
+
+    /// Send a byte and return whether it was acknowledged.
+    fn write_byte(&mut self, byte: u8) -> Result<bool, Error> {
+        for i in 0..8 {
+            self.write_bit(byte & (0x80 >> i) != 0)?;
+        }
+        Ok(!self.read_bit()?)
+    }
+
+    fn read_byte(&mut self, ack: bool) -> Result<u8, Error> {
+        let mut byte = 0;
+        for _ in 0..8 {
+            byte = (byte << 1) | self.read_bit()? as u8;
+        }
+        self.write_bit(!ack)?;
+        Ok(byte)
+    }
+
+    fn start(&mut self, address: u8, direction: Direction) -> Result<(), Error> {
+        self.try_start(address, direction)
+            .map_err(|e| self.abort(e))
+    }
+
+    fn try_start(&mut self, address: u8, direction: Direction) -> Result<(), Error> {
+        if self.started {
+            // Repeated start: Bring both lines high again first.
+            self.sda.set_high();
+            self.wait_half_period();
+            self.release_scl()?;
+            self.wait_half_period();
+        } else if self.sda.is_low() || self.scl.is_low() {
+            return Err(Error::BusError);
+        }
+
+        // Start condition: SDA falls while SCL is high.
+        self.sda.set_low();
+        self.wait_half_period();
+        self.scl.set_low();
+        self.started = true;
+
+        let dirbit = match direction {
+            Direction::Write => 0,
+            Direction::Read => 1,
+        };
+        if self.write_byte((address << 1) | dirbit)? {
+            Ok(())
+        } else {
+            Err(Error::AddressNack)
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        for byte in bytes {
+            match self.write_byte(*byte) {
+                Ok(true) => (),
+                Ok(false) => return Err(self.abort(Error::DataNack)),
+                Err(e) => return Err(self.abort(e)),
+            }
+        }
+        Ok(())
+    }
+
+    fn read(&mut self, buffer: &mut [u8], last_read: bool) -> Result<(), Error> {
+        let last = buffer.len().saturating_sub(1);
+        for (i, byte) in buffer.iter_mut().enumerate() {
+            // The last byte of a read is not acknowledged so the device releases the bus.
+            match self.read_byte(!(last_read && i == last)) {
+                Ok(b) => *byte = b,
+                Err(e) => return Err(self.abort(e)),
+            }
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), Error> {
+        // Stop condition: SDA rises while SCL is high.
+        self.sda.set_low();
+        self.wait_half_period();
+        self.release_scl()?;
+        self.wait_half_period();
+        self.sda.set_high();
+        self.wait_half_period();
+        self.started = false;
+        if self.sda.is_low() {
+            return Err(Error::ArbitrationLost);
+        }
+        Ok(())
+    }
+}
+
+impl<CLOCK, SDAPIN, SCLPIN> SoftI2c<CLOCK, SDAPIN, SCLPIN>
+where
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: DelayNs,
+    SDAPIN: port::PinOps,
+    SCLPIN: port::PinOps,
+{
+    /// Scan the bus for connected devices.  This method will output an summary in the format known
+    /// from [`i2cdetect(8)`][i2cdetect-linux] on the selected serial connection.
+    ///
+    /// See [`I2c::i2cdetect`][crate::i2c::I2c::i2cdetect] for an example of the output.
+    ///
+    /// [i2cdetect-linux]: https://man.archlinux.org/man/community/i2c-tools/i2cdetect.8.en
+    pub fn i2cdetect<W: ufmt::uWrite>(
+        &mut self,
+        w: &mut W,
+        direction: Direction,
+    ) -> Result<(), W::Error> {
+        crate::i2c::i2cdetect::<CLOCK, W, _>(w, |address| self.ping_device(address, direction))
+    }
+}
+
+impl<CLOCK, SDAPIN, SCLPIN> embedded_hal::i2c::ErrorType for SoftI2c<CLOCK, SDAPIN, SCLPIN> {
+    type Error = Error;
+}
+
+impl<CLOCK, SDAPIN, SCLPIN> embedded_hal::i2c::I2c<SevenBitAddress>
+    for SoftI2c<CLOCK, SDAPIN, SCLPIN>
+where
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: DelayNs,
+    SDAPIN: port::PinOps,
+    SCLPIN: port::PinOps,
+{
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut previous_direction = Direction::Read;
+        let count = operations.len();
+        for idx in 0..count {
+            // Only the last byte before a new start or the stop is not acknowledged.
+            let last_read = !matches!(
+                operations.get(idx + 1),
+                Some(embedded_hal::i2c::Operation::Read(_))
+            );
+            match &mut operations[idx] {
+                embedded_hal::i2c::Operation::Read(buffer) => {
+                    if idx == 0 || previous_direction != Direction::Read {
+                        self.start(address, Direction::Read)?;
+                    }
+                    self.read(buffer, last_read)?;
+                    previous_direction = Direction::Read;
+                }
+                embedded_hal::i2c::Operation::Write(bytes) => {
+                    if idx == 0 || previous_direction != Direction::Write {
+                        self.start(address, Direction::Write)?;
+                    }
+                    self.write(bytes)?;
+                    previous_direction = Direction::Write;
+                }
+            }
+        }
+        if count > 0 {
+            self.stop()?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+//! Software (bit-banged) SPI controller
+//!
+//! When the hardware SPI peripheral is not available on the required pins, [`SoftSpi`] can drive
+//! an SPI bus from any GPIO pins, including [downgraded][crate::port::Pin::downgrade] dynamic
+//! pins.  Check the documentation of [`SoftSpi`] for details.
+use crate::port;
+use crate::spi::{DataOrder, Settings};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::spi::{self, SpiBus};
+
+/// Bit-banged SPI controller on arbitrary pins
+///
+/// All four SPI modes and both bit orders are supported.  The serial clock is derived from the
+/// `clock` field of the [`Settings`] and the core clock `CLOCK`, just like for the hardware
+/// peripheral.  Note however that the overhead of toggling the pins in software limits the
+/// achievable clock rate.  Settings which are faster than that limit run the bus as fast as
+/// possible.
+///
+/// Chip-select is not handled by this driver.  Any output pin can be used for that purpose (for
+/// example using `embedded_hal_bus::spi::ExclusiveDevice`).
+///
+/// # Example
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let mut spi = arduino_hal::spi::SoftSpi::new(
+///     pins.d4.into_output(),
+///     pins.d5.into_output(),
+///     pins.d6.into_pull_up_input(),
+///     spi::Settings::default(),
+/// );
+/// let cs = pins.d7.into_output();
+/// let mut device = ExclusiveDevice::new(spi, cs, arduino_hal::Delay::new());
+/// ```
+pub struct SoftSpi<CLOCK, SCLKPIN, MOSIPIN, MISOPIN> {
+    sclk: port::Pin<port::mode::Output, SCLKPIN>,
+    mosi: port::Pin<port::mode::Output, MOSIPIN>,
+    miso: port::Pin<port::mode::Input, MISOPIN>,
+    settings: Settings,
+    half_period: u16,
+    delay: crate::delay::Delay<CLOCK>,
+}
+
+impl<CLOCK, SCLKPIN, MOSIPIN, MISOPIN> SoftSpi<CLOCK, SCLKPIN, MOSIPIN, MISOPIN>
+where
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: DelayNs,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+{
+    /// Set up a software SPI bus on the given pins.
+    pub fn new<IMODE: port::mode::InputMode>(
+        sclk: port::Pin<port::mode::Output, SCLKPIN>,
+        mosi: port::Pin<port::mode::Output, MOSIPIN>,
+        miso: port::Pin<port::mode::Input<IMODE>, MISOPIN>,
+        settings: Settings,
+    ) -> Self {
+        let mut spi = Self {
+            sclk,
+            mosi,
+            miso: miso.forget_imode(),
+            settings: Settings::default(),
+            half_period: 0,
+            delay: crate::delay::Delay::new(),
+        };
+        spi.reconfigure(settings);
+        spi
+    }
+
+    /// Reconfigure the bus after initializing.
+    pub fn reconfigure(&mut self, settings: Settings) {
+        // Half of the SCLK period in microseconds, rounded down
+        self.half_period = (settings.clock.into_divider() as u32 * 500_000 / CLOCK::FREQ) as u16;
+        self.settings = settings;
+        self.set_sclk(false);
+    }
+
+    /// Release ownership of the pins.
+    pub fn release(
+        self,
+    ) -> (
+        port::Pin<port::mode::Output, SCLKPIN>,
+        port::Pin<port::mode::Output, MOSIPIN>,
+        port::Pin<port::mode::Input, MISOPIN>,
+    ) {
+        (self.sclk, self.mosi, self.miso)
+    }
+
+    fn wait_half_period(&mut self) {
+        if self.half_period > 0 {
+            self.delay.delay_us(self.half_period.into());
+        }
+    }
+
+    fn set_sclk(&mut self, active: bool) {
+        let high = match self.settings.mode.polarity {
+            spi::Polarity::IdleLow => active,
+            spi::Polarity::IdleHigh => !active,
+        };
+        if high {
+            self.sclk.set_high();
+        } else {
+            self.sclk.set_low();
+        }
+    }
+
+    fn set_mosi(&mut self, high: bool) {
+        if high {
+            self.mosi.set_high();
+        } else {
+            self.mosi.set_low();
+        }
+    }
+
+    fn transaction(&mut self, byte: u8) -> u8 {
+        let mut received = 0;
+        for i in 0..8 {
+            let mask = match self.settings.data_order {
+                DataOrder::MostSignificantFirst => 0x80 >> i,
+                DataOrder::LeastSignificantFirst => 0x01 << i,
+            };
+            let sample = match self.settings.mode.phase {
+                spi::Phase::CaptureOnFirstTransition => {
+                    self.set_mosi(byte & mask != 0);
+                    self.wait_half_period();
+                    self.set_sclk(true);
+                    let sample = self.miso.is_high();
+                    self.wait_half_period();
+                    self.set_sclk(false);
+                    sample
+                }
+                spi::Phase::CaptureOnSecondTransition => {
+                    self.set_sclk(true);
+                    self.set_mosi(byte & mask != 0);
+                    self.wait_half_period();
+                    self.set_sclk(false);
+                    let sample = self.miso.is_high();
+                    self.wait_half_period();
+                    sample
+                }
+            };
+            if sample {
+                received |= mask;
+            }
+        }
+        received
+    }
+}
+
+impl<CLOCK, SCLKPIN, MOSIPIN, MISOPIN> spi::ErrorType for SoftSpi<CLOCK, SCLKPIN, MOSIPIN, MISOPIN>
+where
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: DelayNs,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+{
+    type Error = core::convert::Infallible;
+}
+
+impl<CLOCK, SCLKPIN, MOSIPIN, MISOPIN> SpiBus for SoftSpi<CLOCK, SCLKPIN, MOSIPIN, MISOPIN>
+where
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: DelayNs,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+{
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        // All transactions are blocking so there is never anything left to flush.
+        Ok(())
+    }
+
+    fn read(&mut self, read: &mut [u8]) -> Result<(), Self::Error> {
+        for b in read.iter_mut() {
+            // We send 0x00 on MOSI during "pure" reading
+            *b = self.transaction(0x00);
+        }
+
+        Ok(())
+    }
+
+    fn write(&mut self, write: &[u8]) -> Result<(), Self::Error> {
+        for b in write.iter() {
+            self.transaction(*b);
+        }
+
+        Ok(())
+    }
+
+    fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        let longest = read.len().max(write.len());
+        for i in 0..longest {
+            let r = self.transaction(*write.get(i).unwrap_or(&0x00));
+            if i < read.len() {
+                read[i] = r;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn transfer_in_place(&mut self, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        for b in buffer.iter_mut() {
+            *b = self.transaction(*b)
+        }
+
+        Ok(())
+    }
+}
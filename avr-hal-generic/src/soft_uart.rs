@@ -0,0 +1,518 @@
+//! Software (bit-banged) UART
+//!
+//! For MCUs without a USART or when another serial port is needed, [`SoftUart`] can transmit and
+//! receive 8N1 frames on any two GPIO pins.  Check the documentation of [`SoftUart`] for details.
+use crate::delay::delay_cycles;
+use crate::port;
+use crate::usart::{Error, RingBuffer};
+use core::marker;
+
+/// Number of CPU cycles spent per bit outside of the delay loop in `transmit()`.
+///
+/// Counted from the code generated for the ATmega328P.  Together with the rounding of
+/// `delay_cycles()` to whole loop iterations, each bit stays within 3 cycles of the nominal bit
+/// time.
+const TX_BIT_OVERHEAD_CYCLES: u16 = 14;
+
+/// Number of CPU cycles spent per bit outside of the delay loop in `receive()`.
+///
+/// Counted in the same way as [`TX_BIT_OVERHEAD_CYCLES`].
+const RX_BIT_OVERHEAD_CYCLES: u16 = 17;
+
+/// Bit-banged UART on arbitrary pins
+///
+/// Frames are always 8N1.  The bit timing is derived from the core clock `CLOCK` and generated
+/// by counting CPU cycles.  Interrupts are disabled while a frame is transmitted or received so
+/// the timing is not disturbed.  This means a `SoftUart` cannot transmit and receive at the same
+/// time.
+///
+/// Receiving is possible in two ways:
+///
+/// - **Blocking**: [`SoftUart::read_byte`] waits for the start bit by polling the RX pin.
+/// - **Interrupt-driven**: After [`SoftUart::into_buffered`], the start bit is detected by an
+///   external (`INTn`) or pin-change (`PCINTn`) interrupt on the RX pin.  The interrupt handler
+///   must forward to [`SoftUartBuffers::on_start_bit`], which receives the frame into the buffer.
+///   Configure `INTn` for a falling edge where possible; pin-change interrupts also work as the
+///   handler ignores the rising edges.
+///
+/// # Example
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let mut serial = arduino_hal::usart::SoftUart::new(
+///     pins.d4.into_pull_up_input(),
+///     pins.d5.into_output(),
+///     9600,
+/// );
+///
+/// ufmt::uwriteln!(&mut serial, "Hello from the SoftUart!\r").unwrap_infallible();
+/// let b = serial.read_byte().unwrap();
+/// ```
+pub struct SoftUart<CLOCK, RXPIN, TXPIN> {
+    rx: port::Pin<port::mode::Input, RXPIN>,
+    tx: port::Pin<port::mode::Output, TXPIN>,
+    bit_cycles: u16,
+    _clock: marker::PhantomData<CLOCK>,
+}
+
+impl<CLOCK, RXPIN, TXPIN> SoftUart<CLOCK, RXPIN, TXPIN>
+where
+    CLOCK: crate::clock::Clock,
+    RXPIN: port::PinOps,
+    TXPIN: port::PinOps,
+{
+    /// Set up a software UART on the given pins with the given `baudrate`.
+    ///
+    /// # Panics
+    /// Panics if the baudrate is too low for the core clock (more than 65535 cycles per bit).
+    pub fn new<IMODE: port::mode::InputMode>(
+        rx: port::Pin<port::mode::Input<IMODE>, RXPIN>,
+        mut tx: port::Pin<port::mode::Output, TXPIN>,
+        baudrate: u32,
+    ) -> Self {
+        let bit_cycles = CLOCK::FREQ / baudrate;
+        assert!(
+            bit_cycles <= u16::MAX as u32,
+            "baudrate too low for SoftUart"
+        );
+        // The line idles high.
+        tx.set_high();
+        Self {
+            rx: rx.forget_imode(),
+            tx,
+            bit_cycles: bit_cycles as u16,
+            _clock: marker::PhantomData,
+        }
+    }
+
+    /// Release ownership of the pins.
+    pub fn release(
+        self,
+    ) -> (
+        port::Pin<port::mode::Input, RXPIN>,
+        port::Pin<port::mode::Output, TXPIN>,
+    ) {
+        (self.rx, self.tx)
+    }
+
+    /// Transmit a byte.
+    ///
+    /// This method blocks until the whole frame was sent.
+    pub fn write_byte(&mut self, byte: u8) {
+        transmit(&mut self.tx, self.bit_cycles, byte);
+    }
+
+    /// Receive a byte.
+    ///
+    /// This method blocks until a start bit is detected and the frame was received.  A missing
+    /// stop bit is reported as [`Error::Framing`].
+    pub fn read_byte(&mut self) -> Result<u8, Error> {
+        loop {
+            while self.rx.is_high() {}
+            let result = avr_device::interrupt::free(|_| {
+                // Check again in case an interrupt delayed us until after the start bit.
+                if self.rx.is_high() {
+                    return None;
+                }
+                Some(receive(&self.rx, self.bit_cycles))
+            });
+            if let Some(result) = result {
+                return result;
+            }
+        }
+    }
+
+    /// Convert this UART into a [`BufferedSoftUart`] which receives data in the background.
+    ///
+    /// The RX pin is moved into `buffers`.  The start-bit interrupt must be configured and
+    /// enabled by the user.  Any data left in the buffer from a previous use is discarded.
+    pub fn into_buffered<const N: usize>(
+        self,
+        buffers: &'static SoftUartBuffers<RXPIN, N>,
+    ) -> BufferedSoftUart<CLOCK, RXPIN, TXPIN, N> {
+        buffers.with(|inner| {
+            inner.buffer.clear();
+            inner.error = None;
+            inner.bit_cycles = self.bit_cycles;
+            inner.rx = Some(self.rx);
+        });
+
+        BufferedSoftUart {
+            buffers,
+            tx: self.tx,
+            bit_cycles: self.bit_cycles,
+            _clock: marker::PhantomData,
+        }
+    }
+}
+
+fn transmit<TXPIN: port::PinOps>(
+    tx: &mut port::Pin<port::mode::Output, TXPIN>,
+    bit_cycles: u16,
+    byte: u8,
+) {
+    let delay = bit_cycles.saturating_sub(TX_BIT_OVERHEAD_CYCLES);
+    avr_device::interrupt::free(|_| {
+        // Start bit
+        tx.set_low();
+        delay_cycles(delay);
+
+        // Data bits, LSB first
+        let mut data = byte;
+        for _ in 0..8u8 {
+            if data & 0x01 != 0 {
+                tx.set_high();
+            } else {
+                tx.set_low();
+            }
+            data >>= 1;
+            delay_cycles(delay);
+        }
+
+        // Stop bit
+        tx.set_high();
+        delay_cycles(delay);
+    });
+}
+
+/// Receive a frame whose start bit was just detected.  Must be called with interrupts disabled.
+fn receive<RXPIN: port::PinOps>(
+    rx: &port::Pin<port::mode::Input, RXPIN>,
+    bit_cycles: u16,
+) -> Result<u8, Error> {
+    let delay = bit_cycles.saturating_sub(RX_BIT_OVERHEAD_CYCLES);
+
+    // Sample in the middle of each bit.  A start bit which is gone by then was only a glitch.
+    delay_cycles(delay / 2);
+    if rx.is_high() {
+        return Err(Error::Framing);
+    }
+
+    let mut data = 0u8;
+    for _ in 0..8u8 {
+        delay_cycles(delay);
+        data >>= 1;
+        if rx.is_high() {
+            data |= 0x80;
+        }
+    }
+
+    delay_cycles(delay);
+    if rx.is_low() {
+        return Err(Error::Framing);
+    }
+    Ok(data)
+}
+
+impl<CLOCK, RXPIN, TXPIN> ufmt::uWrite for SoftUart<CLOCK, RXPIN, TXPIN>
+where
+    CLOCK: crate::clock::Clock,
+    RXPIN: port::PinOps,
+    TXPIN: port::PinOps,
+{
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        for b in s.as_bytes().iter() {
+            self.write_byte(*b);
+        }
+        Ok(())
+    }
+}
+
+impl<CLOCK, RXPIN, TXPIN> embedded_io::ErrorType for SoftUart<CLOCK, RXPIN, TXPIN> {
+    type Error = Error;
+}
+
+impl<CLOCK, RXPIN, TXPIN> embedded_io::Read for SoftUart<CLOCK, RXPIN, TXPIN>
+where
+    CLOCK: crate::clock::Clock,
+    RXPIN: port::PinOps,
+    TXPIN: port::PinOps,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        // Without a buffer, only a single frame can be received at a time.
+        let Some(first) = buf.first_mut() else {
+            return Ok(0);
+        };
+        *first = self.read_byte()?;
+        Ok(1)
+    }
+}
+
+impl<CLOCK, RXPIN, TXPIN> embedded_io::Write for SoftUart<CLOCK, RXPIN, TXPIN>
+where
+    CLOCK: crate::clock::Clock,
+    RXPIN: port::PinOps,
+    TXPIN: port::PinOps,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        for b in buf.iter() {
+            self.write_byte(*b);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        // All frames are sent synchronously so there is never anything left to flush.
+        Ok(())
+    }
+}
+
+struct SoftUartBuffersInner<RXPIN, const N: usize> {
+    rx: Option<port::Pin<port::mode::Input, RXPIN>>,
+    bit_cycles: u16,
+    buffer: RingBuffer<N>,
+    error: Option<Error>,
+}
+
+/// Receive buffer for a [`BufferedSoftUart`].
+///
+/// The buffer must be placed in a `static` so it can be shared with the interrupt handler which
+/// detects the start bit.  The handler must be defined by the user and forward to
+/// [`SoftUartBuffers::on_start_bit`]:
+///
+/// ```
+/// static SOFT_SERIAL: SoftUartBuffers<PD4, 64> = SoftUartBuffers::new();
+///
+/// #[avr_device::interrupt(atmega328p)]
+/// fn PCINT2() {
+///     SOFT_SERIAL.on_start_bit();
+/// }
+/// ```
+pub struct SoftUartBuffers<RXPIN, const N: usize> {
+    inner: avr_device::interrupt::Mutex<core::cell::RefCell<SoftUartBuffersInner<RXPIN, N>>>,
+}
+
+impl<RXPIN, const N: usize> SoftUartBuffers<RXPIN, N> {
+    /// Create empty buffers.
+    pub const fn new() -> Self {
+        Self {
+            inner: avr_device::interrupt::Mutex::new(core::cell::RefCell::new(
+                SoftUartBuffersInner {
+                    rx: None,
+                    bit_cycles: 0,
+                    buffer: RingBuffer::new(),
+                    error: None,
+                },
+            )),
+        }
+    }
+
+    fn with<R, F: FnOnce(&mut SoftUartBuffersInner<RXPIN, N>) -> R>(&self, f: F) -> R {
+        avr_device::interrupt::free(|cs| f(&mut self.inner.borrow(cs).borrow_mut()))
+    }
+}
+
+impl<RXPIN: port::PinOps, const N: usize> SoftUartBuffers<RXPIN, N> {
+    /// Receive a frame after the start bit was detected.
+    ///
+    /// Must be called from the external or pin-change interrupt handler of the RX pin.  The
+    /// handler blocks for the duration of the whole frame.  Calls while the RX pin is high (for
+    /// example on rising edges) are ignored.
+    pub fn on_start_bit(&self) {
+        self.with(|inner| {
+            let Some(rx) = inner.rx.as_ref() else {
+                return;
+            };
+            if rx.is_high() {
+                return;
+            }
+            match receive(rx, inner.bit_cycles) {
+                Ok(b) => {
+                    if inner.buffer.push(b).is_err() {
+                        inner.error = Some(Error::Overrun);
+                    }
+                }
+                Err(e) => inner.error = Some(e),
+            }
+        })
+    }
+}
+
+impl<RXPIN, const N: usize> Default for SoftUartBuffers<RXPIN, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Interrupt-driven [`SoftUart`]
+///
+/// Created using [`SoftUart::into_buffered`].  Received bytes are collected in the
+/// [`SoftUartBuffers`] by the start-bit interrupt.  Transmitting still happens synchronously.
+///
+/// # Example
+/// ```
+/// static SOFT_SERIAL: SoftUartBuffers<PD4, 64> = SoftUartBuffers::new();
+///
+/// let mut serial = arduino_hal::usart::SoftUart::new(
+///     pins.d4.into_pull_up_input(),
+///     pins.d5.into_output(),
+///     9600,
+/// )
+/// .into_buffered(&SOFT_SERIAL);
+///
+/// // Enable the pin-change interrupt for PD4 (PCINT20)
+/// dp.EXINT.pcicr.write(|w| unsafe { w.bits(0b100) });
+/// dp.EXINT.pcmsk2.write(|w| w.bits(0b10000));
+/// unsafe { avr_device::interrupt::enable() };
+///
+/// loop {
+///     if let Ok(b) = serial.read_byte() {
+///         serial.write_byte(b);
+///     }
+/// }
+/// ```
+pub struct BufferedSoftUart<CLOCK, RXPIN: 'static, TXPIN, const N: usize> {
+    buffers: &'static SoftUartBuffers<RXPIN, N>,
+    tx: port::Pin<port::mode::Output, TXPIN>,
+    bit_cycles: u16,
+    _clock: marker::PhantomData<CLOCK>,
+}
+
+impl<CLOCK, RXPIN, TXPIN, const N: usize> BufferedSoftUart<CLOCK, RXPIN, TXPIN, N>
+where
+    CLOCK: crate::clock::Clock,
+    RXPIN: port::PinOps,
+    TXPIN: port::PinOps,
+{
+    /// Convert back into a polled [`SoftUart`].
+    ///
+    /// The RX pin is taken back from the buffers.  Data still in the buffer is discarded.  The
+    /// start-bit interrupt should be disabled by the user beforehand.
+    pub fn into_unbuffered(self) -> SoftUart<CLOCK, RXPIN, TXPIN> {
+        let rx = self
+            .buffers
+            .with(|inner| inner.rx.take())
+            .expect("buffers of a BufferedSoftUart without RX pin");
+        SoftUart {
+            rx,
+            tx: self.tx,
+            bit_cycles: self.bit_cycles,
+            _clock: marker::PhantomData,
+        }
+    }
+
+    /// Transmit a byte.
+    ///
+    /// This method blocks until the whole frame was sent.  No data can be received meanwhile.
+    pub fn write_byte(&mut self, byte: u8) {
+        transmit(&mut self.tx, self.bit_cycles, byte);
+    }
+
+    /// Receive a byte.
+    ///
+    /// This method will block until a byte is available in the receive buffer.  If bytes were
+    /// lost or received with errors since the last read, the most recent [`Error`] is returned
+    /// once.
+    pub fn read_byte(&mut self) -> Result<u8, Error> {
+        nb::block!(self.try_read())
+    }
+
+    /// Number of bytes waiting in the receive buffer.
+    pub fn available(&self) -> usize {
+        self.buffers.with(|inner| inner.buffer.len)
+    }
+
+    /// Discard all data in the receive buffer.
+    pub fn clear_rx(&mut self) {
+        self.buffers.with(|inner| {
+            inner.buffer.clear();
+            inner.error = None;
+        })
+    }
+
+    fn try_read(&mut self) -> nb::Result<u8, Error> {
+        self.buffers.with(|inner| {
+            if let Some(e) = inner.error.take() {
+                return Err(nb::Error::Other(e));
+            }
+            inner.buffer.pop().ok_or(nb::Error::WouldBlock)
+        })
+    }
+}
+
+impl<CLOCK, RXPIN, TXPIN, const N: usize> ufmt::uWrite for BufferedSoftUart<CLOCK, RXPIN, TXPIN, N>
+where
+    CLOCK: crate::clock::Clock,
+    RXPIN: port::PinOps,
+    TXPIN: port::PinOps,
+{
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        for b in s.as_bytes().iter() {
+            self.write_byte(*b);
+        }
+        Ok(())
+    }
+}
+
+impl<CLOCK, RXPIN, TXPIN, const N: usize> embedded_io::ErrorType
+    for BufferedSoftUart<CLOCK, RXPIN, TXPIN, N>
+{
+    type Error = Error;
+}
+
+impl<CLOCK, RXPIN, TXPIN, const N: usize> embedded_io::Read
+    for BufferedSoftUart<CLOCK, RXPIN, TXPIN, N>
+where
+    CLOCK: crate::clock::Clock,
+    RXPIN: port::PinOps,
+    TXPIN: port::PinOps,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        // Block for the first byte, then take whatever else is already buffered.
+        buf[0] = self.read_byte()?;
+        let mut count = 1;
+        for b in buf[1..].iter_mut() {
+            // Stop at a pending error without taking it, so the next call reports it.
+            let byte = self.buffers.with(|inner| match inner.error {
+                Some(_) => None,
+                None => inner.buffer.pop(),
+            });
+            match byte {
+                Some(byte) => *b = byte,
+                None => break,
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+impl<CLOCK, RXPIN, TXPIN, const N: usize> embedded_io::ReadReady
+    for BufferedSoftUart<CLOCK, RXPIN, TXPIN, N>
+where
+    CLOCK: crate::clock::Clock,
+    RXPIN: port::PinOps,
+    TXPIN: port::PinOps,
+{
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.available() > 0)
+    }
+}
+
+impl<CLOCK, RXPIN, TXPIN, const N: usize> embedded_io::Write
+    for BufferedSoftUart<CLOCK, RXPIN, TXPIN, N>
+where
+    CLOCK: crate::clock::Clock,
+    RXPIN: port::PinOps,
+    TXPIN: port::PinOps,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        for b in buf.iter() {
+            self.write_byte(*b);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        // All frames are sent synchronously so there is never anything left to flush.
+        Ok(())
+    }
+}
@@ -91,6 +91,11 @@ pub trait SpiOps<H, SCLK, MOSI, MISO, CS> {
     fn raw_write(&mut self, byte: u8);
     /// Perform a transaction of a single byte
     fn raw_transaction(&mut self, byte: u8) -> u8;
+
+    /// Sets up the control/status registers for operation as a peripheral (slave) device
+    fn raw_setup_peripheral(&mut self, settings: &Settings);
+    /// Enable/Disable the transfer complete interrupt
+    fn raw_interrupt(&mut self, state: bool);
 }
 
 /// Wrapper for the CS pin
@@ -444,6 +449,691 @@ where
 {
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum IsrPhase {
+    Idle,
+    Busy,
+    Done,
+}
+
+struct IsrSpiInner<SPI, const N: usize> {
+    p: Option<SPI>,
+    buffer: [u8; N],
+    len: usize,
+    pos: usize,
+    capture: bool,
+    phase: IsrPhase,
+    callback: Option<fn(usize)>,
+}
+
+/// Shared state of an [`IsrSpi`] and its interrupt handler.
+///
+/// The state holds the peripheral and a transfer buffer of `N` bytes.  It must be placed in a
+/// `static` so it can be shared with the `SPI_STC` interrupt handler which must be defined by the
+/// user and forward to [`IsrSpiState::on_transfer_complete`]:
+///
+/// ```
+/// static SPI_STATE: IsrSpiState<pac::SPI, 64> = IsrSpiState::new();
+///
+/// #[avr_device::interrupt(atmega328p)]
+/// fn SPI_STC() {
+///     SPI_STATE.on_transfer_complete();
+/// }
+/// ```
+pub struct IsrSpiState<SPI, const N: usize> {
+    inner: avr_device::interrupt::Mutex<core::cell::RefCell<IsrSpiInner<SPI, N>>>,
+}
+
+impl<SPI, const N: usize> IsrSpiState<SPI, N> {
+    /// Create an empty state.
+    pub const fn new() -> Self {
+        Self {
+            inner: avr_device::interrupt::Mutex::new(core::cell::RefCell::new(IsrSpiInner {
+                p: None,
+                buffer: [0; N],
+                len: 0,
+                pos: 0,
+                capture: false,
+                phase: IsrPhase::Idle,
+                callback: None,
+            })),
+        }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut IsrSpiInner<SPI, N>) -> R) -> R {
+        avr_device::interrupt::free(|cs| f(&mut self.inner.borrow(cs).borrow_mut()))
+    }
+
+    /// Handler for the `SPI_STC`/`SPI#_STC` interrupt.
+    ///
+    /// Stores the received byte (for [`IsrSpi::transfer`]) and sends the next one.  When the
+    /// transfer is finished, the callback set with [`IsrSpi::set_callback`] is invoked from here.
+    pub fn on_transfer_complete<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>(&self)
+    where
+        SPI: SpiOps<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>,
+    {
+        self.with(|inner| {
+            let p = match inner.p.as_mut() {
+                Some(p) => p,
+                None => return,
+            };
+            let byte = p.raw_read();
+            if inner.phase != IsrPhase::Busy {
+                return;
+            }
+            if inner.capture {
+                inner.buffer[inner.pos] = byte;
+            }
+            inner.pos += 1;
+            if inner.pos < inner.len {
+                p.raw_write(inner.buffer[inner.pos]);
+            } else {
+                inner.phase = IsrPhase::Done;
+                if let Some(callback) = inner.callback {
+                    callback(inner.len);
+                }
+            }
+        })
+    }
+}
+
+impl<SPI, const N: usize> Default for IsrSpiState<SPI, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Interrupt-driven, non-blocking SPI controller
+///
+/// Created by calling [`Spi::into_isr_driven`].  A transfer is started using [`IsrSpi::write`]
+/// or [`IsrSpi::transfer`] and then carried out byte by byte by the `SPI_STC` interrupt while the
+/// application continues running.  The data is copied into the transfer buffer of the
+/// [`IsrSpiState`], so at most `N` bytes can be sent per transfer.
+///
+/// The number of bytes sent so far is reported by [`IsrSpi::progress`].  Completion can be
+/// checked with [`IsrSpi::is_done`] and the received data is collected with [`IsrSpi::result`].
+/// Alternatively, a callback can be registered using [`IsrSpi::set_callback`].
+///
+/// Chip-select is not handled by this driver, it must be asserted before starting a transfer and
+/// released after it completed.
+///
+/// **Note**: Interrupts must be enabled globally for transfers to make progress.  At the fastest
+/// clock rates, a byte is shifted out quicker than the interrupt is serviced, so the throughput
+/// is limited by the interrupt overhead instead of the SPI clock.  The CPU is still free for other
+/// work while waiting for each byte.
+///
+/// # Example
+/// ```
+/// static SPI_STATE: IsrSpiState<pac::SPI, 64> = IsrSpiState::new();
+///
+/// let (spi, mut cs) = arduino_hal::Spi::new(
+///     dp.SPI,
+///     pins.d13.into_output(),
+///     pins.d11.into_output(),
+///     pins.d12.into_pull_up_input(),
+///     pins.d10.into_output(),
+///     spi::Settings::default(),
+/// );
+/// let mut spi = spi.into_isr_driven(&SPI_STATE);
+/// unsafe { avr_device::interrupt::enable() };
+///
+/// cs.set_low().unwrap();
+/// spi.write(&framebuffer_row).unwrap_infallible();
+/// while !spi.is_done() {
+///     do_other_work();
+/// }
+/// cs.set_high().unwrap();
+/// ```
+pub struct IsrSpi<
+    H,
+    SPI: SpiOps<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN> + 'static,
+    SCLKPIN,
+    MOSIPIN,
+    MISOPIN,
+    CSPIN,
+    const N: usize,
+> {
+    state: &'static IsrSpiState<SPI, N>,
+    sclk: port::Pin<port::mode::Output, SCLKPIN>,
+    mosi: port::Pin<port::mode::Output, MOSIPIN>,
+    miso: port::Pin<port::mode::Input, MISOPIN>,
+    _cs: PhantomData<CSPIN>,
+    _h: PhantomData<H>,
+}
+
+impl<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN> Spi<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>
+where
+    SPI: SpiOps<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+    CSPIN: port::PinOps,
+{
+    /// Convert this SPI controller into an interrupt-driven [`IsrSpi`].
+    ///
+    /// Any in-flight write is completed first.  The peripheral is then moved into `state` and the
+    /// transfer-complete interrupt is enabled.
+    pub fn into_isr_driven<const N: usize>(
+        mut self,
+        state: &'static IsrSpiState<SPI, N>,
+    ) -> IsrSpi<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN, N> {
+        nb::block!(Spi::flush(&mut self)).ok();
+        state.with(|inner| {
+            inner.phase = IsrPhase::Idle;
+            self.p.raw_interrupt(true);
+            inner.p = Some(self.p);
+        });
+
+        IsrSpi {
+            state,
+            sclk: self.sclk,
+            mosi: self.mosi,
+            miso: self.miso,
+            _cs: PhantomData,
+            _h: PhantomData,
+        }
+    }
+}
+
+impl<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN, const N: usize>
+    IsrSpi<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN, N>
+where
+    SPI: SpiOps<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+    CSPIN: port::PinOps,
+{
+    /// Wait for the ongoing transfer to finish and convert back into a blocking [`Spi`].
+    pub fn into_blocking(self) -> Spi<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN> {
+        while !self.is_done() {}
+        let mut p = self
+            .state
+            .with(|inner| {
+                inner.phase = IsrPhase::Idle;
+                inner.p.take()
+            })
+            .expect("state of an IsrSpi without peripheral");
+        p.raw_interrupt(false);
+
+        Spi {
+            p,
+            sclk: self.sclk,
+            mosi: self.mosi,
+            miso: self.miso,
+            write_in_progress: false,
+            _cs: PhantomData,
+            _h: PhantomData,
+        }
+    }
+
+    /// Set a function to be called from the interrupt handler when a transfer finished.
+    ///
+    /// The callback runs inside the `SPI_STC` interrupt and receives the number of bytes which
+    /// were transferred.  Use [`IsrSpi::result`] to fetch data which was received.
+    pub fn set_callback(&mut self, callback: Option<fn(usize)>) {
+        self.state.with(|inner| inner.callback = callback);
+    }
+
+    /// Start sending `bytes`, discarding everything received meanwhile.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] while another transfer is still ongoing.
+    ///
+    /// # Panics
+    /// Panics if more than `N` bytes should be sent.
+    pub fn write(&mut self, bytes: &[u8]) -> nb::Result<(), core::convert::Infallible> {
+        self.submit(bytes, false)
+    }
+
+    /// Start sending `bytes` while storing the received bytes.
+    ///
+    /// Once done, the received bytes can be fetched with [`IsrSpi::result`].  Returns
+    /// [`nb::Error::WouldBlock`] while another transfer is still ongoing.
+    ///
+    /// # Panics
+    /// Panics if more than `N` bytes should be sent.
+    pub fn transfer(&mut self, bytes: &[u8]) -> nb::Result<(), core::convert::Infallible> {
+        self.submit(bytes, true)
+    }
+
+    /// Number of bytes of the current (or last) transfer which were already sent.
+    pub fn progress(&self) -> usize {
+        self.state.with(|inner| inner.pos)
+    }
+
+    /// Check whether the last transfer finished.
+    pub fn is_done(&self) -> bool {
+        self.state.with(|inner| inner.phase != IsrPhase::Busy)
+    }
+
+    /// Get the data received during the last transfer.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] while the transfer is still ongoing.  Once finished, the
+    /// received data is copied into `buffer` (as far as it fits) and the number of bytes copied
+    /// is returned.  Nothing is copied after a [`IsrSpi::write`].
+    pub fn result(&mut self, buffer: &mut [u8]) -> nb::Result<usize, core::convert::Infallible> {
+        self.state.with(|inner| match inner.phase {
+            IsrPhase::Busy => Err(nb::Error::WouldBlock),
+            IsrPhase::Idle | IsrPhase::Done => {
+                if !inner.capture {
+                    return Ok(0);
+                }
+                let len = buffer.len().min(inner.len);
+                buffer[..len].copy_from_slice(&inner.buffer[..len]);
+                Ok(len)
+            }
+        })
+    }
+
+    fn submit(&mut self, bytes: &[u8], capture: bool) -> nb::Result<(), core::convert::Infallible> {
+        assert!(bytes.len() <= N, "SPI transfer too long");
+        self.state.with(|inner| {
+            if inner.phase == IsrPhase::Busy {
+                return Err(nb::Error::WouldBlock);
+            }
+            inner.buffer[..bytes.len()].copy_from_slice(bytes);
+            inner.len = bytes.len();
+            inner.pos = 0;
+            inner.capture = capture;
+            if bytes.is_empty() {
+                inner.phase = IsrPhase::Done;
+                if let Some(callback) = inner.callback {
+                    callback(0);
+                }
+            } else {
+                inner.phase = IsrPhase::Busy;
+                if let Some(p) = inner.p.as_mut() {
+                    p.raw_write(inner.buffer[0]);
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+/// SPI peripheral (slave) driver
+///
+/// In peripheral mode, the clock is generated by the controller on the other side of the bus.
+/// SCLK, MOSI and SS are inputs and MISO is driven by this device.  The transfer only happens
+/// while the controller pulls SS low.  The `clock` field of the [`Settings`] is ignored.
+///
+/// Each transfer shifts in one byte from the controller while shifting out the byte which was
+/// loaded beforehand.  Thus, the response for a transfer must be loaded using
+/// [`SpiPeripheral::preload`] before the controller starts it.  [`SpiPeripheral::exchange`] does
+/// both in one step.
+///
+/// For interrupt-driven operation with a preloaded response buffer, convert this driver into a
+/// [`BufferedSpiPeripheral`] using [`SpiPeripheral::into_buffered`].
+///
+/// # Example
+/// ```
+/// let mut spi = SpiPeripheral::new(
+///     dp.SPI,
+///     pins.d13.into_floating_input(),
+///     pins.d11.into_floating_input(),
+///     pins.d12.into_output(),
+///     pins.d10.into_pull_up_input(),
+///     spi::Settings::default(),
+/// );
+///
+/// let mut last = 0;
+/// loop {
+///     // Answer each byte with the previous one
+///     last = spi.exchange(last);
+/// }
+/// ```
+pub struct SpiPeripheral<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN> {
+    p: SPI,
+    sclk: port::Pin<port::mode::Input, SCLKPIN>,
+    mosi: port::Pin<port::mode::Input, MOSIPIN>,
+    miso: port::Pin<port::mode::Output, MISOPIN>,
+    cs: port::Pin<port::mode::Input, CSPIN>,
+    _h: PhantomData<H>,
+}
+
+impl<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>
+    SpiPeripheral<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>
+where
+    SPI: SpiOps<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+    CSPIN: port::PinOps,
+{
+    /// Instantiate an SPI peripheral with the registers, SCLK/MOSI/MISO/SS pins, and settings.
+    ///
+    /// The pins are not actually used directly, but they are moved into the struct in order to
+    /// enforce that they are in the correct mode, and cannot be used by anyone else while SPI is
+    /// active.
+    pub fn new<SCLKMODE, MOSIMODE, CSMODE>(
+        p: SPI,
+        sclk: port::Pin<port::mode::Input<SCLKMODE>, SCLKPIN>,
+        mosi: port::Pin<port::mode::Input<MOSIMODE>, MOSIPIN>,
+        miso: port::Pin<port::mode::Output, MISOPIN>,
+        cs: port::Pin<port::mode::Input<CSMODE>, CSPIN>,
+        settings: Settings,
+    ) -> Self
+    where
+        SCLKMODE: port::mode::InputMode,
+        MOSIMODE: port::mode::InputMode,
+        CSMODE: port::mode::InputMode,
+    {
+        let mut spi = Self {
+            p,
+            sclk: sclk.forget_imode(),
+            mosi: mosi.forget_imode(),
+            miso,
+            cs: cs.forget_imode(),
+            _h: PhantomData,
+        };
+        spi.p.raw_setup_peripheral(&settings);
+        spi
+    }
+
+    /// Reconfigure the SPI peripheral after initializing
+    pub fn reconfigure(&mut self, settings: Settings) {
+        self.p.raw_setup_peripheral(&settings);
+    }
+
+    /// Disable the SPI device and release ownership of the peripheral and pins.
+    pub fn release(
+        mut self,
+    ) -> (
+        SPI,
+        port::Pin<port::mode::Input, SCLKPIN>,
+        port::Pin<port::mode::Input, MOSIPIN>,
+        port::Pin<port::mode::Output, MISOPIN>,
+        port::Pin<port::mode::Input, CSPIN>,
+    ) {
+        self.p.raw_release();
+        (self.p, self.sclk, self.mosi, self.miso, self.cs)
+    }
+
+    /// Check whether the controller currently selects this device (SS is low).
+    pub fn is_selected(&self) -> bool {
+        self.cs.is_low()
+    }
+
+    /// Load the byte to be sent during the next transfer.
+    pub fn preload(&mut self, byte: u8) {
+        self.p.raw_write(byte);
+    }
+
+    /// Receive the byte of the last transfer.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] if no transfer completed since the last call.
+    pub fn receive(&mut self) -> nb::Result<u8, core::convert::Infallible> {
+        if !self.p.raw_check_iflag() {
+            return Err(nb::Error::WouldBlock);
+        }
+        Ok(self.p.raw_read())
+    }
+
+    /// Preload `response` and block until the controller transferred a byte.
+    ///
+    /// Returns the byte received from the controller.
+    pub fn exchange(&mut self, response: u8) -> u8 {
+        self.preload(response);
+        match nb::block!(self.receive()) {
+            Ok(byte) => byte,
+            Err(e) => match e {},
+        }
+    }
+}
+
+struct PeripheralBuffersInner<SPI, const N: usize> {
+    p: Option<SPI>,
+    rx: [u8; N],
+    rx_len: usize,
+    tx: [u8; N],
+    tx_len: usize,
+    tx_pos: usize,
+    fill: u8,
+}
+
+impl<SPI, const N: usize> PeripheralBuffersInner<SPI, N> {
+    fn next_response(&mut self) -> u8 {
+        if self.tx_pos < self.tx_len {
+            self.tx_pos += 1;
+            self.tx[self.tx_pos - 1]
+        } else {
+            self.fill
+        }
+    }
+}
+
+/// Receive and response buffers for a [`BufferedSpiPeripheral`].
+///
+/// The buffers must be placed in a `static` so they can be shared with the `SPI_STC` interrupt
+/// handler which must be defined by the user and forward to
+/// [`SpiPeripheralBuffers::on_transfer_complete`]:
+///
+/// ```
+/// static SPI_BUFFERS: SpiPeripheralBuffers<pac::SPI, 16> = SpiPeripheralBuffers::new();
+///
+/// #[avr_device::interrupt(atmega328p)]
+/// fn SPI_STC() {
+///     SPI_BUFFERS.on_transfer_complete();
+/// }
+/// ```
+pub struct SpiPeripheralBuffers<SPI, const N: usize> {
+    inner: avr_device::interrupt::Mutex<core::cell::RefCell<PeripheralBuffersInner<SPI, N>>>,
+}
+
+impl<SPI, const N: usize> SpiPeripheralBuffers<SPI, N> {
+    /// Create empty buffers.
+    pub const fn new() -> Self {
+        Self {
+            inner: avr_device::interrupt::Mutex::new(core::cell::RefCell::new(
+                PeripheralBuffersInner {
+                    p: None,
+                    rx: [0; N],
+                    rx_len: 0,
+                    tx: [0; N],
+                    tx_len: 0,
+                    tx_pos: 0,
+                    fill: 0x00,
+                },
+            )),
+        }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut PeripheralBuffersInner<SPI, N>) -> R) -> R {
+        avr_device::interrupt::free(|cs| f(&mut self.inner.borrow(cs).borrow_mut()))
+    }
+
+    /// Handler for the `SPI_STC`/`SPI#_STC` interrupt.
+    ///
+    /// Stores the received byte and loads the next byte of the response buffer.  If the receive
+    /// buffer is full, the byte is dropped.
+    pub fn on_transfer_complete<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>(&self)
+    where
+        SPI: SpiOps<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>,
+    {
+        self.with(|inner| {
+            let byte = match inner.p.as_mut() {
+                Some(p) => p.raw_read(),
+                None => return,
+            };
+            if inner.rx_len < N {
+                inner.rx[inner.rx_len] = byte;
+                inner.rx_len += 1;
+            }
+            let next = inner.next_response();
+            if let Some(p) = inner.p.as_mut() {
+                p.raw_write(next);
+            }
+        })
+    }
+}
+
+impl<SPI, const N: usize> Default for SpiPeripheralBuffers<SPI, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Interrupt-driven SPI peripheral (slave) driver
+///
+/// Created by calling [`SpiPeripheral::into_buffered`].  Bytes sent by the controller are
+/// collected in a receive buffer by the `SPI_STC` interrupt.  The bytes of the response buffer
+/// (see [`BufferedSpiPeripheral::set_response`]) are sent back one after the other; once it is
+/// exhausted, the fill byte is sent.  See [`SpiPeripheralBuffers`] for how to hook up the
+/// interrupt handler.
+///
+/// **Note**: Interrupts must be enabled globally for the buffers to be serviced.
+///
+/// # Example
+/// ```
+/// static SPI_BUFFERS: SpiPeripheralBuffers<pac::SPI, 16> = SpiPeripheralBuffers::new();
+///
+/// let mut spi = SpiPeripheral::new(
+///     dp.SPI,
+///     pins.d13.into_floating_input(),
+///     pins.d11.into_floating_input(),
+///     pins.d12.into_output(),
+///     pins.d10.into_pull_up_input(),
+///     spi::Settings::default(),
+/// )
+/// .into_buffered(&SPI_BUFFERS);
+/// unsafe { avr_device::interrupt::enable() };
+///
+/// spi.set_response(b"status: ok");
+/// loop {
+///     let mut command = [0u8; 16];
+///     let len = spi.take_received(&mut command);
+///     // ...
+/// }
+/// ```
+pub struct BufferedSpiPeripheral<
+    H,
+    SPI: SpiOps<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN> + 'static,
+    SCLKPIN,
+    MOSIPIN,
+    MISOPIN,
+    CSPIN,
+    const N: usize,
+> {
+    buffers: &'static SpiPeripheralBuffers<SPI, N>,
+    sclk: port::Pin<port::mode::Input, SCLKPIN>,
+    mosi: port::Pin<port::mode::Input, MOSIPIN>,
+    miso: port::Pin<port::mode::Output, MISOPIN>,
+    cs: port::Pin<port::mode::Input, CSPIN>,
+    _h: PhantomData<H>,
+}
+
+impl<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>
+    SpiPeripheral<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>
+where
+    SPI: SpiOps<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+    CSPIN: port::PinOps,
+{
+    /// Convert this SPI peripheral into an interrupt-driven [`BufferedSpiPeripheral`].
+    ///
+    /// The peripheral is moved into `buffers` and the transfer-complete interrupt is enabled.
+    /// Any data left in the buffers from a previous use is discarded.
+    pub fn into_buffered<const N: usize>(
+        mut self,
+        buffers: &'static SpiPeripheralBuffers<SPI, N>,
+    ) -> BufferedSpiPeripheral<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN, N> {
+        buffers.with(|inner| {
+            inner.rx_len = 0;
+            inner.tx_len = 0;
+            inner.tx_pos = 0;
+            self.p.raw_write(inner.fill);
+            self.p.raw_interrupt(true);
+            inner.p = Some(self.p);
+        });
+
+        BufferedSpiPeripheral {
+            buffers,
+            sclk: self.sclk,
+            mosi: self.mosi,
+            miso: self.miso,
+            cs: self.cs,
+            _h: PhantomData,
+        }
+    }
+}
+
+impl<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN, const N: usize>
+    BufferedSpiPeripheral<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN, N>
+where
+    SPI: SpiOps<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+    CSPIN: port::PinOps,
+{
+    /// Disable the interrupt and convert back into a polled [`SpiPeripheral`].
+    pub fn into_unbuffered(self) -> SpiPeripheral<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN> {
+        let mut p = self
+            .buffers
+            .with(|inner| inner.p.take())
+            .expect("buffers of a BufferedSpiPeripheral without peripheral");
+        p.raw_interrupt(false);
+
+        SpiPeripheral {
+            p,
+            sclk: self.sclk,
+            mosi: self.mosi,
+            miso: self.miso,
+            cs: self.cs,
+            _h: PhantomData,
+        }
+    }
+
+    /// Check whether the controller currently selects this device (SS is low).
+    pub fn is_selected(&self) -> bool {
+        self.cs.is_low()
+    }
+
+    /// Replace the response buffer.
+    ///
+    /// The bytes are sent to the controller starting with the next transfer.  This should be
+    /// called while the device is not selected.
+    ///
+    /// # Panics
+    /// Panics if `data` is longer than the buffer.
+    pub fn set_response(&mut self, data: &[u8]) {
+        assert!(data.len() <= N, "SPI response longer than buffer");
+        self.buffers.with(|inner| {
+            inner.tx[..data.len()].copy_from_slice(data);
+            inner.tx_len = data.len();
+            inner.tx_pos = 0;
+            let first = inner.next_response();
+            if let Some(p) = inner.p.as_mut() {
+                p.raw_write(first);
+            }
+        })
+    }
+
+    /// Set the byte which is sent once the response buffer is exhausted (default `0x00`).
+    pub fn set_fill(&mut self, fill: u8) {
+        self.buffers.with(|inner| inner.fill = fill)
+    }
+
+    /// Number of bytes waiting in the receive buffer.
+    pub fn available(&self) -> usize {
+        self.buffers.with(|inner| inner.rx_len)
+    }
+
+    /// Copy the received bytes into `buffer` and clear the receive buffer.
+    ///
+    /// Returns the number of bytes copied.  Bytes which don't fit into `buffer` are discarded.
+    pub fn take_received(&mut self, buffer: &mut [u8]) -> usize {
+        self.buffers.with(|inner| {
+            let len = inner.rx_len.min(buffer.len());
+            buffer[..len].copy_from_slice(&inner.rx[..len]);
+            inner.rx_len = 0;
+            len
+        })
+    }
+}
+
 /// Implement traits for a SPI interface
 #[macro_export]
 macro_rules! impl_spi {
@@ -524,6 +1214,37 @@ macro_rules! impl_spi {
                 while !self.raw_check_iflag() {}
                 self.raw_read()
             }
+
+            fn raw_setup_peripheral(&mut self, settings: &Settings) {
+                use $crate::hal::spi;
+
+                // set up control register
+                self.spcr.write(|w| {
+                    // enable SPI
+                    w.spe().set_bit();
+                    // Set to secondary mode
+                    w.mstr().clear_bit();
+                    // set up data order control bit
+                    match settings.data_order {
+                        DataOrder::MostSignificantFirst => w.dord().clear_bit(),
+                        DataOrder::LeastSignificantFirst => w.dord().set_bit(),
+                    };
+                    // set up polarity control bit
+                    match settings.mode.polarity {
+                        spi::Polarity::IdleHigh => w.cpol().set_bit(),
+                        spi::Polarity::IdleLow => w.cpol().clear_bit(),
+                    };
+                    // set up phase control bit
+                    match settings.mode.phase {
+                        spi::Phase::CaptureOnFirstTransition => w.cpha().clear_bit(),
+                        spi::Phase::CaptureOnSecondTransition => w.cpha().set_bit(),
+                    }
+                });
+            }
+
+            fn raw_interrupt(&mut self, state: bool) {
+                self.spcr.modify(|_, w| w.spie().bit(state));
+            }
         }
     };
 }
@@ -0,0 +1,503 @@
+//! Timer/Counter Implementation
+//!
+//! Check the documentation of [`Timer`] for details.
+
+use core::marker::PhantomData;
+
+pub use crate::simple_pwm::Prescaler;
+
+/// Waveform generation mode of a timer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// The counter runs from zero up to its maximum value and then overflows back to zero.
+    Normal,
+    /// Clear Timer on Compare match: The counter is reset to zero when it matches the value of
+    /// compare register A (`OCR#A`).
+    Ctc,
+}
+
+/// Output compare channels of a timer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    /// Output compare register A (`OCR#A`).
+    A,
+    /// Output compare register B (`OCR#B`).
+    B,
+}
+
+/// Events/Interrupts for timer peripherals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Event {
+    /// The counter overflowed.
+    ///
+    /// Corresponds to the `TIMER#_OVF` interrupt.  Please refer to the datasheet for your MCU for
+    /// details.
+    Overflow,
+
+    /// The counter matched the value of compare register A.
+    ///
+    /// Corresponds to the `TIMER#_COMPA` interrupt.  Please refer to the datasheet for your MCU
+    /// for details.
+    CompareMatchA,
+
+    /// The counter matched the value of compare register B.
+    ///
+    /// Corresponds to the `TIMER#_COMPB` interrupt.  Please refer to the datasheet for your MCU
+    /// for details.
+    CompareMatchB,
+}
+
+impl From<Channel> for Event {
+    fn from(channel: Channel) -> Self {
+        match channel {
+            Channel::A => Event::CompareMatchA,
+            Channel::B => Event::CompareMatchB,
+        }
+    }
+}
+
+/// Timer Error
+#[derive(ufmt::derive::uDebug, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Error {
+    /// The requested period cannot be reached with any prescaler and the counter width of this
+    /// timer.
+    PeriodOutOfRange,
+}
+
+/// Representation of a timer period
+///
+/// Precalculated prescaler and compare value to make a timer in [`Mode::Ctc`] reach a certain
+/// period.  The resulting period is
+///
+/// ```text
+/// T = Prescaler * (top + 1) / CLK_io
+/// ```
+#[derive(Debug)]
+pub struct Period<CLOCK> {
+    /// Clock prescaler for the timer
+    pub prescaler: Prescaler,
+    /// Value of the `OCR#A` register
+    pub top: u16,
+    /// The period calculation depends on the configured clock rate, thus a `CLOCK` generic
+    /// parameter is needed.
+    pub _clock: PhantomData<CLOCK>,
+}
+
+// Implemented manually as the derives would require `CLOCK` to implement these traits as well.
+impl<CLOCK> Clone for Period<CLOCK> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<CLOCK> Copy for Period<CLOCK> {}
+
+impl<CLOCK> PartialEq for Period<CLOCK> {
+    fn eq(&self, other: &Self) -> bool {
+        self.prescaler == other.prescaler && self.top == other.top
+    }
+}
+
+impl<CLOCK> Eq for Period<CLOCK> {}
+
+impl<CLOCK: crate::clock::Clock> Period<CLOCK> {
+    /// Calculate parameters for a period of `cycles` core clock cycles.
+    ///
+    /// The smallest prescaler which allows reaching the period with a top value of at most
+    /// `max_count` is selected to get the best possible resolution.
+    pub fn from_cycles(cycles: u32, max_count: u16) -> Result<Period<CLOCK>, Error> {
+        let prescalers = [
+            Prescaler::Direct,
+            Prescaler::Prescale8,
+            Prescaler::Prescale64,
+            Prescaler::Prescale256,
+            Prescaler::Prescale1024,
+        ];
+
+        for prescaler in prescalers {
+            let divider = prescaler.divider();
+            // Round to the nearest number of timer ticks, without overflowing for large `cycles`.
+            let ticks = cycles / divider + (cycles % divider >= divider - divider / 2) as u32;
+            if ticks == 0 {
+                return Err(Error::PeriodOutOfRange);
+            }
+            if ticks - 1 <= max_count as u32 {
+                return Ok(Period::with_exact(prescaler, (ticks - 1) as u16));
+            }
+        }
+
+        Err(Error::PeriodOutOfRange)
+    }
+
+    /// Calculate parameters for a period of `us` microseconds.
+    pub fn from_micros(us: u32, max_count: u16) -> Result<Period<CLOCK>, Error> {
+        let cycles = CLOCK::FREQ as u64 * us as u64 / 1_000_000;
+        if cycles > u32::MAX as u64 {
+            return Err(Error::PeriodOutOfRange);
+        }
+        Self::from_cycles(cycles as u32, max_count)
+    }
+
+    /// Calculate parameters for a frequency of `hz` Hertz.
+    pub fn from_hz(hz: u32, max_count: u16) -> Result<Period<CLOCK>, Error> {
+        if hz == 0 {
+            return Err(Error::PeriodOutOfRange);
+        }
+        Self::from_cycles(CLOCK::FREQ / hz, max_count)
+    }
+
+    /// Construct a `Period` from given prescaler and `OCR#A` values.
+    ///
+    /// This provides exact control over the resulting period.
+    pub fn with_exact(prescaler: Prescaler, top: u16) -> Period<CLOCK> {
+        Period {
+            prescaler,
+            top,
+            _clock: PhantomData,
+        }
+    }
+
+    /// Number of core clock cycles in one period.
+    pub fn cycles(&self) -> u32 {
+        (self.top as u32 + 1) * self.prescaler.divider()
+    }
+
+    /// Length of this period in microseconds (rounded down).
+    pub fn micros(&self) -> u32 {
+        (self.cycles() as u64 * 1_000_000 / CLOCK::FREQ as u64) as u32
+    }
+}
+
+/// Internal trait for low-level timer peripherals.
+///
+/// This trait defines the common interface for all timer peripheral variants.  It is used as an
+/// intermediate abstraction ontop of which the [`Timer`] API is built.  **Prefer using the
+/// [`Timer`] API instead of this trait.**
+pub trait TimerOps<H> {
+    /// Largest value the counter and compare registers can hold.
+    ///
+    /// This is `0xff` for 8-bit timers and `0xffff` for 16-bit timers.
+    const MAX_COUNT: u16;
+
+    /// Configure the waveform generation mode and start the timer with the given prescaler.
+    ///
+    /// The output compare pins must be disconnected from the timer.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_setup(&mut self, mode: Mode, prescaler: Prescaler);
+
+    /// Stop the timer by disconnecting its clock source.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_stop(&mut self);
+
+    /// Read the current value of the counter register (`TCNT#`).
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_counter(&self) -> u16;
+
+    /// Overwrite the value of the counter register (`TCNT#`).
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_write_counter(&mut self, value: u16);
+
+    /// Read the value of an output compare register (`OCR#x`).
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_compare(&self, channel: Channel) -> u16;
+
+    /// Write the value of an output compare register (`OCR#x`).
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_write_compare(&mut self, channel: Channel, value: u16);
+
+    /// Enable/Disable a certain interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_interrupt(&mut self, event: Event, state: bool);
+
+    /// Check whether the flag for a certain event is set.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_is_pending(&self, event: Event) -> bool;
+
+    /// Clear the flag for a certain event.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_pending(&mut self, event: Event);
+}
+
+/// Timer/Counter driver
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+///
+/// let mut timer = arduino_hal::timer::Timer::new(dp.TC1);
+///
+/// // Fire the TIMER1_COMPA interrupt every 250 ms.
+/// timer.start_ctc_us(250_000).unwrap();
+/// timer.listen(arduino_hal::timer::Event::CompareMatchA);
+///
+/// unsafe { avr_device::interrupt::enable() };
+/// ```
+///
+/// Without interrupts, the compare match flag can also be polled:
+///
+/// ```
+/// timer.start_ctc_us(1_000).unwrap();
+///
+/// loop {
+///     if timer.is_pending(arduino_hal::timer::Event::CompareMatchA) {
+///         timer.clear(arduino_hal::timer::Event::CompareMatchA);
+///         // one millisecond has passed
+///     }
+/// }
+/// ```
+pub struct Timer<H, TC, CLOCK> {
+    p: TC,
+    _clock: PhantomData<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, TC: TimerOps<H>, CLOCK: crate::clock::Clock> Timer<H, TC, CLOCK> {
+    /// Take ownership of a timer peripheral.
+    ///
+    /// The timer is stopped and all of its interrupts are disabled.
+    pub fn new(p: TC) -> Self {
+        let mut timer = Self {
+            p,
+            _clock: PhantomData,
+            _h: PhantomData,
+        };
+        timer.stop();
+        for event in [Event::Overflow, Event::CompareMatchA, Event::CompareMatchB] {
+            timer.unlisten(event);
+            timer.clear(event);
+        }
+        timer
+    }
+
+    /// Stop the timer and release the peripheral.
+    pub fn release(mut self) -> TC {
+        self.stop();
+        self.p
+    }
+
+    /// Start the timer in [`Mode::Normal`] with the given prescaler.
+    ///
+    /// The counter runs freely and overflows after `MAX_COUNT + 1` timer ticks.
+    pub fn start_normal(&mut self, prescaler: Prescaler) {
+        self.p.raw_setup(Mode::Normal, prescaler);
+    }
+
+    /// Start the timer in [`Mode::Ctc`] with a precalculated [`Period`].
+    ///
+    /// [`Event::CompareMatchA`] fires once per period.
+    pub fn start_ctc(&mut self, period: Period<CLOCK>) -> Result<(), Error> {
+        if period.top > TC::MAX_COUNT {
+            return Err(Error::PeriodOutOfRange);
+        }
+        self.p.raw_write_counter(0);
+        self.p.raw_write_compare(Channel::A, period.top);
+        self.p.raw_setup(Mode::Ctc, period.prescaler);
+        Ok(())
+    }
+
+    /// Start the timer in [`Mode::Ctc`] with a period of `us` microseconds.
+    ///
+    /// The prescaler and compare value are calculated from the `CLOCK` speed.  As the period is
+    /// rounded to the nearest timer tick, the actually configured period is returned.
+    pub fn start_ctc_us(&mut self, us: u32) -> Result<Period<CLOCK>, Error> {
+        let period = Period::from_micros(us, TC::MAX_COUNT)?;
+        self.start_ctc(period)?;
+        Ok(period)
+    }
+
+    /// Start the timer in [`Mode::Ctc`] with a frequency of `hz` Hertz.
+    ///
+    /// The actually configured period is returned.
+    pub fn start_ctc_hz(&mut self, hz: u32) -> Result<Period<CLOCK>, Error> {
+        let period = Period::from_hz(hz, TC::MAX_COUNT)?;
+        self.start_ctc(period)?;
+        Ok(period)
+    }
+
+    /// Stop the timer.
+    ///
+    /// The counter keeps its current value.
+    pub fn stop(&mut self) {
+        self.p.raw_stop();
+    }
+
+    /// Read the current counter value.
+    pub fn counter(&self) -> u16 {
+        self.p.raw_read_counter()
+    }
+
+    /// Overwrite the current counter value.
+    pub fn set_counter(&mut self, value: u16) {
+        self.p.raw_write_counter(value);
+    }
+
+    /// Read the value of an output compare register.
+    pub fn compare(&self, channel: Channel) -> u16 {
+        self.p.raw_read_compare(channel)
+    }
+
+    /// Set the value of an output compare register.
+    ///
+    /// In [`Mode::Ctc`], the value of [`Channel::A`] defines the period of the timer.
+    pub fn set_compare(&mut self, channel: Channel, value: u16) {
+        self.p.raw_write_compare(channel, value);
+    }
+
+    /// Enable the interrupt for [`Event`].
+    pub fn listen(&mut self, event: Event) {
+        self.p.raw_interrupt(event, true);
+    }
+
+    /// Disable the interrupt for [`Event`].
+    pub fn unlisten(&mut self, event: Event) {
+        self.p.raw_interrupt(event, false);
+    }
+
+    /// Check whether the flag for [`Event`] is set.
+    ///
+    /// The flag is cleared automatically when the corresponding interrupt is executed.
+    pub fn is_pending(&self, event: Event) -> bool {
+        self.p.raw_is_pending(event)
+    }
+
+    /// Clear the flag for [`Event`].
+    pub fn clear(&mut self, event: Event) {
+        self.p.raw_clear_pending(event);
+    }
+}
+
+/// Implement [`TimerOps`] for a timer peripheral
+///
+/// The `setup` block must configure the waveform generation mode and the prescaler, the `stop`
+/// block must disconnect the clock source of the timer.
+#[macro_export]
+macro_rules! impl_timer {
+    (
+        hal: $HAL:ty,
+        peripheral: $TC:ty,
+        counter: $Count:ty,
+        tcnt: $tcnt:ident,
+        ocr: [$ocra:ident, $ocrb:ident],
+        timsk: $timsk:ident {
+            overflow: $toie:ident,
+            compare_a: $ociea:ident,
+            compare_b: $ocieb:ident,
+        },
+        tifr: $tifr:ident {
+            overflow: $tov:ident,
+            compare_a: $ocfa:ident,
+            compare_b: $ocfb:ident,
+        },
+        setup: |$setup_timer:ident, $mode:ident, $prescaler:ident| $setup_block:block,
+        stop: |$stop_timer:ident| $stop_block:block,
+    ) => {
+        impl $crate::timer::TimerOps<$HAL> for $TC {
+            const MAX_COUNT: u16 = <$Count>::MAX as u16;
+
+            #[inline]
+            fn raw_setup(
+                &mut self,
+                mode: $crate::timer::Mode,
+                prescaler: $crate::timer::Prescaler,
+            ) {
+                let $setup_timer = &mut *self;
+                let $mode = mode;
+                let $prescaler = prescaler;
+                $setup_block
+            }
+
+            #[inline]
+            fn raw_stop(&mut self) {
+                let $stop_timer = &mut *self;
+                $stop_block
+            }
+
+            #[inline]
+            fn raw_read_counter(&self) -> u16 {
+                // SAFETY: 16-bit registers are accessed through a shared temporary register.  The
+                // critical section ensures an ISR cannot clobber it between the two byte accesses.
+                $crate::avr_device::interrupt::free(|_| self.$tcnt.read().bits() as u16)
+            }
+
+            #[inline]
+            fn raw_write_counter(&mut self, value: u16) {
+                $crate::avr_device::interrupt::free(|_| {
+                    self.$tcnt.write(|w| unsafe { w.bits(value as $Count) });
+                })
+            }
+
+            #[inline]
+            fn raw_read_compare(&self, channel: $crate::timer::Channel) -> u16 {
+                $crate::avr_device::interrupt::free(|_| match channel {
+                    $crate::timer::Channel::A => self.$ocra.read().bits() as u16,
+                    $crate::timer::Channel::B => self.$ocrb.read().bits() as u16,
+                })
+            }
+
+            #[inline]
+            fn raw_write_compare(&mut self, channel: $crate::timer::Channel, value: u16) {
+                $crate::avr_device::interrupt::free(|_| match channel {
+                    $crate::timer::Channel::A => {
+                        self.$ocra.write(|w| unsafe { w.bits(value as $Count) })
+                    }
+                    $crate::timer::Channel::B => {
+                        self.$ocrb.write(|w| unsafe { w.bits(value as $Count) })
+                    }
+                })
+            }
+
+            #[inline]
+            fn raw_interrupt(&mut self, event: $crate::timer::Event, state: bool) {
+                // SAFETY: The interrupt mask register might be shared with other timers, so the
+                // read-modify-write sequence is wrapped in a critical section.
+                $crate::avr_device::interrupt::free(|_| match event {
+                    $crate::timer::Event::Overflow => {
+                        self.$timsk.modify(|_, w| w.$toie().bit(state))
+                    }
+                    $crate::timer::Event::CompareMatchA => {
+                        self.$timsk.modify(|_, w| w.$ociea().bit(state))
+                    }
+                    $crate::timer::Event::CompareMatchB => {
+                        self.$timsk.modify(|_, w| w.$ocieb().bit(state))
+                    }
+                })
+            }
+
+            #[inline]
+            fn raw_is_pending(&self, event: $crate::timer::Event) -> bool {
+                let flags = self.$tifr.read();
+                match event {
+                    $crate::timer::Event::Overflow => flags.$tov().bit_is_set(),
+                    $crate::timer::Event::CompareMatchA => flags.$ocfa().bit_is_set(),
+                    $crate::timer::Event::CompareMatchB => flags.$ocfb().bit_is_set(),
+                }
+            }
+
+            #[inline]
+            fn raw_clear_pending(&mut self, event: $crate::timer::Event) {
+                // Flags are cleared by writing a logical one.  This must be a plain write (not a
+                // read-modify-write), otherwise all other pending flags would be cleared as well.
+                match event {
+                    $crate::timer::Event::Overflow => self.$tifr.write(|w| w.$tov().set_bit()),
+                    $crate::timer::Event::CompareMatchA => {
+                        self.$tifr.write(|w| w.$ocfa().set_bit())
+                    }
+                    $crate::timer::Event::CompareMatchB => {
+                        self.$tifr.write(|w| w.$ocfb().set_bit())
+                    }
+                }
+            }
+        }
+    };
+}
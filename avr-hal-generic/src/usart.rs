@@ -141,6 +141,147 @@ impl BaudrateArduinoExt for u32 {
     }
 }
 
+/// Number of data bits in a USART frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataBits {
+    Five,
+    Six,
+    Seven,
+    Eight,
+    /// 9-bit frames need to be transmitted and received using the `write_9bit()` and
+    /// `read_9bit()` methods.
+    Nine,
+}
+
+/// Parity mode of a USART frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    None,
+    Even,
+    Odd,
+}
+
+/// Number of stop bits in a USART frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopBits {
+    One,
+    Two,
+}
+
+/// Format of a USART frame
+///
+/// The default format is 8 data bits, no parity and one stop bit (8N1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameFormat {
+    pub data_bits: DataBits,
+    pub parity: Parity,
+    pub stop_bits: StopBits,
+}
+
+impl FrameFormat {
+    /// Construct a frame format from its parts.
+    pub const fn new(data_bits: DataBits, parity: Parity, stop_bits: StopBits) -> FrameFormat {
+        FrameFormat {
+            data_bits,
+            parity,
+            stop_bits,
+        }
+    }
+}
+
+impl Default for FrameFormat {
+    fn default() -> Self {
+        FrameFormat::new(DataBits::Eight, Parity::None, StopBits::One)
+    }
+}
+
+/// Settings to pass to [`Usart::new`].
+///
+/// A plain [`Baudrate`] can be passed to [`Usart::new`] as well, which selects the default 8N1
+/// [`FrameFormat`].
+///
+/// # Example
+/// ```
+/// // Modbus RTU: 8 data bits, even parity, 1 stop bit
+/// let settings = UsartSettings::new(
+///     Baudrate::new(19200),
+///     FrameFormat::new(DataBits::Eight, Parity::Even, StopBits::One),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct UsartSettings<CLOCK> {
+    pub baudrate: Baudrate<CLOCK>,
+    pub frame_format: FrameFormat,
+}
+
+impl<CLOCK> UsartSettings<CLOCK> {
+    /// Combine a baudrate and a frame format.
+    pub fn new(baudrate: Baudrate<CLOCK>, frame_format: FrameFormat) -> UsartSettings<CLOCK> {
+        UsartSettings {
+            baudrate,
+            frame_format,
+        }
+    }
+}
+
+impl<CLOCK> From<Baudrate<CLOCK>> for UsartSettings<CLOCK> {
+    fn from(baudrate: Baudrate<CLOCK>) -> Self {
+        UsartSettings::new(baudrate, FrameFormat::default())
+    }
+}
+
+impl<CLOCK: crate::clock::Clock> From<u32> for UsartSettings<CLOCK> {
+    fn from(baud: u32) -> Self {
+        Baudrate::new(baud).into()
+    }
+}
+
+/// USART receive error
+///
+/// Reported when the hardware flagged the received data as faulty.  The byte which was
+/// received along with the error is discarded.
+#[derive(ufmt::derive::uDebug, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Error {
+    /// The stop bit of the received frame was not detected (`FE#` flag).
+    ///
+    /// This usually means the baudrate or frame format do not match the remote side or that
+    /// there is noise on the line.
+    Framing,
+    /// The receive buffer was full and data was lost (`DOR#` flag).
+    ///
+    /// This happens when received data is not read quickly enough.
+    Overrun,
+    /// The parity bit of the received frame did not match its data (`UPE#` flag).
+    Parity,
+}
+
+impl Error {
+    /// Select the error to report for the state of the receive error flags.
+    ///
+    /// **Warning**: This is a low-level method used by the [`UsartOps`] implementations.
+    pub fn from_flags(frame_error: bool, data_overrun: bool, parity_error: bool) -> Option<Error> {
+        if frame_error {
+            Some(Error::Framing)
+        } else if parity_error {
+            Some(Error::Parity)
+        } else if data_overrun {
+            Some(Error::Overrun)
+        } else {
+            None
+        }
+    }
+}
+
+impl embedded_hal_nb::serial::Error for Error {
+    fn kind(&self) -> embedded_hal_nb::serial::ErrorKind {
+        match self {
+            Error::Framing => embedded_hal_nb::serial::ErrorKind::FrameFormat,
+            Error::Overrun => embedded_hal_nb::serial::ErrorKind::Overrun,
+            Error::Parity => embedded_hal_nb::serial::ErrorKind::Parity,
+        }
+    }
+}
+
 /// Events/Interrupts for USART peripherals
 #[repr(u8)]
 pub enum Event {
@@ -169,10 +310,10 @@ pub enum Event {
 /// intermediate abstraction ontop of which the [`Usart`] API is built.  **Prefer using the
 /// [`Usart`] API instead of this trait.**
 pub trait UsartOps<H, RX, TX> {
-    /// Enable & initialize this USART peripheral to the given baudrate.
+    /// Enable & initialize this USART peripheral to the given baudrate and frame format.
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
-    fn raw_init<CLOCK>(&mut self, baudrate: Baudrate<CLOCK>);
+    fn raw_init<CLOCK>(&mut self, settings: UsartSettings<CLOCK>);
     /// Disable this USART peripheral such that the pins can be used for other purposes again.
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
@@ -195,10 +336,42 @@ pub trait UsartOps<H, RX, TX> {
     /// Read a byte from the RX buffer.
     ///
     /// This operation must be non-blocking and return [`nb::Error::WouldBlock`] if no incoming
-    /// byte is available.
+    /// byte is available.  If the hardware flagged the byte as faulty, it must be discarded and
+    /// the corresponding [`Error`] returned.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read(&mut self) -> nb::Result<u8, Error>;
+    /// Check whether a received byte is waiting in the RX buffer.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_ready(&mut self) -> bool;
+
+    /// Write a 9-bit word to the TX buffer.
+    ///
+    /// Same as [`raw_write`][UsartOps::raw_write] but also sets the 9th data bit.  Only
+    /// meaningful if the peripheral was initialized with [`DataBits::Nine`].
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_write_9bit(&mut self, word: u16) -> nb::Result<(), core::convert::Infallible>;
+    /// Read a 9-bit word from the RX buffer.
+    ///
+    /// Same as [`raw_read`][UsartOps::raw_read] but also returns the 9th data bit.  Only
+    /// meaningful if the peripheral was initialized with [`DataBits::Nine`].
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_9bit(&mut self) -> nb::Result<u16, Error>;
+
+    /// Check whether the TX complete flag is set.
+    ///
+    /// The flag is set once the last frame was shifted out completely and no further data is
+    /// waiting in the TX buffer.
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
-    fn raw_read(&mut self) -> nb::Result<u8, core::convert::Infallible>;
+    fn raw_tx_complete(&mut self) -> bool;
+    /// Clear the TX complete flag.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_tx_complete(&mut self);
 
     /// Enable/Disable a certain interrupt.
     ///
@@ -206,6 +379,22 @@ pub trait UsartOps<H, RX, TX> {
     fn raw_interrupt(&mut self, event: Event, state: bool);
 }
 
+/// Internal trait for USART peripherals with LIN support.
+///
+/// Implemented in addition to [`UsartOps`] by peripherals which can generate the header of a LIN
+/// frame in hardware, like the LIN/UART of the ATtiny167.  **Prefer using
+/// [`Usart::send_lin_header`] instead of this trait.**
+pub trait UsartLinOps<H, RX, TX>: UsartOps<H, RX, TX> {
+    /// Send a LIN header: A break field, the sync field (`0x55`) and the protected identifier for
+    /// the 6-bit `identifier`.
+    ///
+    /// This operation must block until the header was sent and leave the peripheral in UART mode
+    /// afterwards.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_send_lin_header(&mut self, identifier: u8);
+}
+
 /// USART/Serial driver
 ///
 /// # Example
@@ -231,6 +420,8 @@ pub struct Usart<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> {
     p: USART,
     rx: RX,
     tx: TX,
+    /// Receive error which was held back by `embedded_io::Read::read()`.
+    rx_error: Option<Error>,
     _clock: marker::PhantomData<CLOCK>,
     _h: marker::PhantomData<H>,
 }
@@ -252,20 +443,24 @@ where
     ///
     /// Note that the RX and TX pins are hardwired for each USART peripheral and you *must* pass
     /// the correct ones.  This is enforced at compile time.
+    ///
+    /// `settings` is either a [`Baudrate`] for an 8N1 frame format or [`UsartSettings`] to
+    /// select a different [`FrameFormat`].
     pub fn new<IMODE: port::mode::InputMode>(
         p: USART,
         rx: port::Pin<port::mode::Input<IMODE>, RXPIN>,
         tx: port::Pin<port::mode::Output, TXPIN>,
-        baudrate: Baudrate<CLOCK>,
+        settings: impl Into<UsartSettings<CLOCK>>,
     ) -> Self {
         let mut usart = Self {
             p,
             rx: rx.forget_imode(),
             tx,
+            rx_error: None,
             _clock: marker::PhantomData,
             _h: marker::PhantomData,
         };
-        usart.p.raw_init(baudrate);
+        usart.p.raw_init(settings.into());
         usart
     }
 }
@@ -293,8 +488,35 @@ impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> Usart<H, USART, RX, TX, CLOCK
     /// Receive a byte.
     ///
     /// This method will block until a byte could be received.
-    pub fn read_byte(&mut self) -> u8 {
-        nb::block!(self.p.raw_read()).unwrap_infallible()
+    pub fn read_byte(&mut self) -> Result<u8, Error> {
+        nb::block!(self.try_read())
+    }
+
+    /// Report an error held back by `embedded_io::Read::read()` before reading further.
+    fn try_read(&mut self) -> nb::Result<u8, Error> {
+        if let Some(e) = self.rx_error.take() {
+            return Err(nb::Error::Other(e));
+        }
+        self.p.raw_read()
+    }
+
+    /// Transmit a 9-bit word.
+    ///
+    /// Requires the peripheral to be configured for [`DataBits::Nine`].  Like
+    /// [`write_byte`][Usart::write_byte], this method only blocks until the word was enqueued.
+    pub fn write_9bit(&mut self, word: u16) {
+        nb::block!(self.p.raw_write_9bit(word)).unwrap_infallible()
+    }
+
+    /// Receive a 9-bit word.
+    ///
+    /// Requires the peripheral to be configured for [`DataBits::Nine`].  This method will block
+    /// until a word could be received.
+    pub fn read_9bit(&mut self) -> Result<u16, Error> {
+        if let Some(e) = self.rx_error.take() {
+            return Err(e);
+        }
+        nb::block!(self.p.raw_read_9bit())
     }
 
     /// Enable the interrupt for [`Event`].
@@ -307,6 +529,20 @@ impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> Usart<H, USART, RX, TX, CLOCK
         self.p.raw_interrupt(event, false);
     }
 
+    /// Send a LIN header for the 6-bit `identifier`.
+    ///
+    /// The header consists of a break field, the sync field and the protected identifier (the
+    /// parity bits are computed by the hardware).  The response, including the checksum, can
+    /// then be sent or received using the normal UART methods.
+    ///
+    /// This method blocks until all pending data and the header were sent.
+    pub fn send_lin_header(&mut self, identifier: u8)
+    where
+        USART: UsartLinOps<H, RX, TX>,
+    {
+        self.p.raw_send_lin_header(identifier);
+    }
+
     /// Split this USART into a [`UsartReader`] and a [`UsartWriter`].
     ///
     /// This allows concurrently receiving and transmitting data from different contexts.
@@ -320,6 +556,7 @@ impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> Usart<H, USART, RX, TX, CLOCK
             UsartReader {
                 p: unsafe { core::ptr::read(&self.p) },
                 rx: self.rx,
+                rx_error: self.rx_error,
                 _tx: marker::PhantomData,
                 _clock: marker::PhantomData,
                 _h: marker::PhantomData,
@@ -363,10 +600,10 @@ impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_v0::serial::Writ
 impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_v0::serial::Read<u8>
     for Usart<H, USART, RX, TX, CLOCK>
 {
-    type Error = core::convert::Infallible;
+    type Error = Error;
 
     fn read(&mut self) -> nb::Result<u8, Self::Error> {
-        self.p.raw_read()
+        self.try_read()
     }
 }
 
@@ -394,6 +631,7 @@ pub struct UsartWriter<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> {
 pub struct UsartReader<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> {
     p: USART,
     rx: RX,
+    rx_error: Option<Error>,
     _tx: marker::PhantomData<TX>,
     _clock: marker::PhantomData<CLOCK>,
     _h: marker::PhantomData<H>,
@@ -409,6 +647,7 @@ impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> UsartWriter<H, USART, RX, TX,
             p: self.p,
             rx: other.rx,
             tx: self.tx,
+            rx_error: other.rx_error,
             _clock: marker::PhantomData,
             _h: marker::PhantomData,
         }
@@ -425,12 +664,42 @@ impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> UsartReader<H, USART, RX, TX,
             p: self.p,
             rx: self.rx,
             tx: other.tx,
+            rx_error: self.rx_error,
             _clock: marker::PhantomData,
             _h: marker::PhantomData,
         }
     }
 }
 
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> UsartWriter<H, USART, RX, TX, CLOCK> {
+    /// Transmit a 9-bit word.
+    ///
+    /// See [`Usart::write_9bit`].
+    pub fn write_9bit(&mut self, word: u16) {
+        nb::block!(self.p.raw_write_9bit(word)).unwrap_infallible()
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> UsartReader<H, USART, RX, TX, CLOCK> {
+    /// Receive a 9-bit word.
+    ///
+    /// See [`Usart::read_9bit`].
+    pub fn read_9bit(&mut self) -> Result<u16, Error> {
+        if let Some(e) = self.rx_error.take() {
+            return Err(e);
+        }
+        nb::block!(self.p.raw_read_9bit())
+    }
+
+    /// Report an error held back by `embedded_io::Read::read()` before reading further.
+    fn try_read(&mut self) -> nb::Result<u8, Error> {
+        if let Some(e) = self.rx_error.take() {
+            return Err(nb::Error::Other(e));
+        }
+        self.p.raw_read()
+    }
+}
+
 impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> ufmt::uWrite
     for UsartWriter<H, USART, RX, TX, CLOCK>
 {
@@ -461,76 +730,1049 @@ impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_v0::serial::Writ
 impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_v0::serial::Read<u8>
     for UsartReader<H, USART, RX, TX, CLOCK>
 {
-    type Error = core::convert::Infallible;
+    type Error = Error;
 
     fn read(&mut self) -> nb::Result<u8, Self::Error> {
-        self.p.raw_read()
+        self.try_read()
     }
 }
 
-#[macro_export]
-macro_rules! impl_usart_traditional {
-    (
-        hal: $HAL:ty,
-        peripheral: $USART:ty,
-        register_suffix: $n:expr,
-        rx: $rxpin:ty,
-        tx: $txpin:ty,
-    ) => {
-        $crate::paste::paste! {
-            impl $crate::usart::UsartOps<
-                $HAL,
-                $crate::port::Pin<$crate::port::mode::Input, $rxpin>,
-                $crate::port::Pin<$crate::port::mode::Output, $txpin>,
-            > for $USART {
-                fn raw_init<CLOCK>(&mut self, baudrate: $crate::usart::Baudrate<CLOCK>) {
-                    self.[<ubrr $n>].write(|w| unsafe { w.bits(baudrate.ubrr) });
-                    self.[<ucsr $n a>].write(|w| w.[<u2x $n>]().bit(baudrate.u2x));
+impl embedded_io::Error for Error {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        match self {
+            Error::Framing | Error::Parity => embedded_io::ErrorKind::InvalidData,
+            Error::Overrun => embedded_io::ErrorKind::Other,
+        }
+    }
+}
 
-                    // Enable receiver and transmitter but leave interrupts disabled.
-                    self.[<ucsr $n b>].write(|w| w
-                        .[<txen $n>]().set_bit()
-                        .[<rxen $n>]().set_bit()
-                    );
+/// Blocking read of at least one byte for the `embedded_io::Read` implementations.
+///
+/// An error after some bytes were already read is held back in `rx_error` and reported by the
+/// next call, so the bytes are not lost.
+fn io_read<H, USART: UsartOps<H, RX, TX>, RX, TX>(
+    p: &mut USART,
+    rx_error: &mut Option<Error>,
+    buf: &mut [u8],
+) -> Result<usize, Error> {
+    let Some((first, rest)) = buf.split_first_mut() else {
+        return Ok(0);
+    };
+    if let Some(e) = rx_error.take() {
+        return Err(e);
+    }
+    *first = nb::block!(p.raw_read())?;
 
-                    // Set frame format to 8n1 for now.  At some point, this should be made
-                    // configurable, similar to what is done in other HALs.
-                    self.[<ucsr $n c>].write(|w| w
-                        .[<umsel $n>]().usart_async()
-                        .[<ucsz $n>]().chr8()
-                        .[<usbs $n>]().stop1()
-                        .[<upm $n>]().disabled()
-                    );
-                }
+    let mut count = 1;
+    for byte in rest.iter_mut() {
+        match p.raw_read() {
+            Ok(b) => *byte = b,
+            Err(nb::Error::WouldBlock) => break,
+            Err(nb::Error::Other(e)) => {
+                *rx_error = Some(e);
+                break;
+            }
+        }
+        count += 1;
+    }
+    Ok(count)
+}
 
-                fn raw_deinit(&mut self) {
-                    // Wait for any ongoing transfer to finish.
-                    $crate::nb::block!(self.raw_flush()).ok();
-                    self.[<ucsr $n b>].reset();
-                }
+/// Blocking write of at least one byte for the `embedded_io::Write` implementations.
+fn io_write<H, USART: UsartOps<H, RX, TX>, RX, TX>(p: &mut USART, buf: &[u8]) -> usize {
+    let Some((first, rest)) = buf.split_first() else {
+        return 0;
+    };
+    nb::block!(p.raw_write(*first)).unwrap_infallible();
 
-                fn raw_flush(&mut self) -> $crate::nb::Result<(), core::convert::Infallible> {
-                    if self.[<ucsr $n a>].read().[<udre $n>]().bit_is_clear() {
-                        Err($crate::nb::Error::WouldBlock)
-                    } else {
-                        Ok(())
-                    }
-                }
+    let mut count = 1;
+    for byte in rest.iter() {
+        if p.raw_write(*byte).is_err() {
+            break;
+        }
+        count += 1;
+    }
+    count
+}
 
-                fn raw_write(&mut self, byte: u8) -> $crate::nb::Result<(), core::convert::Infallible> {
-                    // Call flush to make sure the data-register is empty
-                    self.raw_flush()?;
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::ErrorType
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    type Error = Error;
+}
 
-                    self.[<udr $n>].write(|w| unsafe { w.bits(byte) });
-                    Ok(())
-                }
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::Read
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        io_read(&mut self.p, &mut self.rx_error, buf)
+    }
+}
 
-                fn raw_read(&mut self) -> $crate::nb::Result<u8, core::convert::Infallible> {
-                    if self.[<ucsr $n a>].read().[<rxc $n>]().bit_is_clear() {
-                        return Err($crate::nb::Error::WouldBlock);
-                    }
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::ReadReady
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.p.raw_read_ready())
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::Write
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        Ok(io_write(&mut self.p, buf))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        nb::block!(self.p.raw_flush()).unwrap_infallible();
+        Ok(())
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::WriteReady
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.p.raw_flush().is_ok())
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::ErrorType
+    for UsartReader<H, USART, RX, TX, CLOCK>
+{
+    type Error = Error;
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::Read
+    for UsartReader<H, USART, RX, TX, CLOCK>
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        io_read(&mut self.p, &mut self.rx_error, buf)
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::ReadReady
+    for UsartReader<H, USART, RX, TX, CLOCK>
+{
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.p.raw_read_ready())
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::ErrorType
+    for UsartWriter<H, USART, RX, TX, CLOCK>
+{
+    type Error = core::convert::Infallible;
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::Write
+    for UsartWriter<H, USART, RX, TX, CLOCK>
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        Ok(io_write(&mut self.p, buf))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        nb::block!(self.p.raw_flush())
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::WriteReady
+    for UsartWriter<H, USART, RX, TX, CLOCK>
+{
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.p.raw_flush().is_ok())
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_nb::serial::ErrorType
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    type Error = Error;
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_nb::serial::Read<u8>
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        self.try_read()
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_nb::serial::Write<u8>
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        self.p.raw_write(word).map_err(|e| e.map(|e| match e {}))
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        self.p.raw_flush().map_err(|e| e.map(|e| match e {}))
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_nb::serial::ErrorType
+    for UsartReader<H, USART, RX, TX, CLOCK>
+{
+    type Error = Error;
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_nb::serial::Read<u8>
+    for UsartReader<H, USART, RX, TX, CLOCK>
+{
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        self.try_read()
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_nb::serial::ErrorType
+    for UsartWriter<H, USART, RX, TX, CLOCK>
+{
+    type Error = core::convert::Infallible;
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_nb::serial::Write<u8>
+    for UsartWriter<H, USART, RX, TX, CLOCK>
+{
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        self.p.raw_write(word)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        self.p.raw_flush()
+    }
+}
+
+/// Fixed-size FIFO of bytes used by [`UsartBuffers`].
+pub(crate) struct RingBuffer<const N: usize> {
+    data: [u8; N],
+    head: usize,
+    pub(crate) len: usize,
+}
+
+impl<const N: usize> RingBuffer<N> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            data: [0; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub(crate) fn push(&mut self, byte: u8) -> Result<(), u8> {
+        if self.len == N {
+            return Err(byte);
+        }
+        let mut tail = self.head + self.len;
+        if tail >= N {
+            tail -= N;
+        }
+        self.data[tail] = byte;
+        self.len += 1;
+        Ok(())
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+        let byte = self.data[self.head];
+        self.head += 1;
+        if self.head == N {
+            self.head = 0;
+        }
+        self.len -= 1;
+        Some(byte)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+}
+
+struct BuffersInner<USART, const RX_SIZE: usize, const TX_SIZE: usize, DE> {
+    p: Option<USART>,
+    rx: RingBuffer<RX_SIZE>,
+    tx: RingBuffer<TX_SIZE>,
+    error: Option<Error>,
+    de: Option<DE>,
+    transmitting: bool,
+}
+
+/// Placeholder for the driver-enable pin of a [`BufferedUsart`] which is not used for RS-485.
+pub struct NoDriverEnable;
+
+impl embedded_hal::digital::ErrorType for NoDriverEnable {
+    type Error = core::convert::Infallible;
+}
+
+impl embedded_hal::digital::OutputPin for NoDriverEnable {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Receive and transmit buffers for a [`BufferedUsart`].
+///
+/// The buffers must be placed in a `static` so they can be shared with the interrupt handlers of
+/// the USART peripheral.  The two handlers must be defined by the user and forward to
+/// [`UsartBuffers::on_rx_complete`] and [`UsartBuffers::on_data_register_empty`]:
+///
+/// ```
+/// static SERIAL_BUFFERS: UsartBuffers<pac::USART0, 64, 32> = UsartBuffers::new();
+///
+/// #[avr_device::interrupt(atmega328p)]
+/// fn USART_RX() {
+///     SERIAL_BUFFERS.on_rx_complete();
+/// }
+///
+/// #[avr_device::interrupt(atmega328p)]
+/// fn USART_UDRE() {
+///     SERIAL_BUFFERS.on_data_register_empty();
+/// }
+/// ```
+///
+/// For RS-485 operation (see [`Rs485Usart::into_buffered`]), the type of the driver-enable pin
+/// is passed as the last parameter and the `USART_TX`/`USART#_TX` interrupt must be forwarded to
+/// [`UsartBuffers::on_tx_complete`] as well:
+///
+/// ```
+/// static SERIAL_BUFFERS: UsartBuffers<pac::USART0, 64, 32, Pin<Output, PD2>> =
+///     UsartBuffers::new();
+///
+/// #[avr_device::interrupt(atmega328p)]
+/// fn USART_TX() {
+///     SERIAL_BUFFERS.on_tx_complete();
+/// }
+/// ```
+pub struct UsartBuffers<USART, const RX_SIZE: usize, const TX_SIZE: usize, DE = NoDriverEnable> {
+    inner: avr_device::interrupt::Mutex<
+        core::cell::RefCell<BuffersInner<USART, RX_SIZE, TX_SIZE, DE>>,
+    >,
+}
+
+impl<USART, const RX_SIZE: usize, const TX_SIZE: usize, DE>
+    UsartBuffers<USART, RX_SIZE, TX_SIZE, DE>
+{
+    /// Create empty buffers.
+    pub const fn new() -> Self {
+        Self {
+            inner: avr_device::interrupt::Mutex::new(core::cell::RefCell::new(BuffersInner {
+                p: None,
+                rx: RingBuffer::new(),
+                tx: RingBuffer::new(),
+                error: None,
+                de: None,
+                transmitting: false,
+            })),
+        }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut BuffersInner<USART, RX_SIZE, TX_SIZE, DE>) -> R) -> R {
+        avr_device::interrupt::free(|cs| f(&mut self.inner.borrow(cs).borrow_mut()))
+    }
+
+    /// Handler for the `USART_RX`/`USART#_RX` interrupt.
+    ///
+    /// Moves the received byte into the receive buffer.  If the buffer is full, the byte is
+    /// dropped and [`Error::Overrun`] is reported on the next read.  Receive errors flagged by
+    /// the hardware are reported on the next read as well.
+    pub fn on_rx_complete<H, RX, TX>(&self)
+    where
+        USART: UsartOps<H, RX, TX>,
+    {
+        self.with(|inner| {
+            if let Some(p) = inner.p.as_mut() {
+                loop {
+                    match p.raw_read() {
+                        Ok(byte) => {
+                            if inner.rx.push(byte).is_err() {
+                                inner.error = Some(Error::Overrun);
+                            }
+                        }
+                        Err(nb::Error::Other(e)) => inner.error = Some(e),
+                        Err(nb::Error::WouldBlock) => break,
+                    }
+                }
+            }
+        })
+    }
+
+    /// Handler for the `USART_UDRE`/`USART#_UDRE` interrupt.
+    ///
+    /// Moves the next byte from the transmit buffer into the data register.  Once the buffer is
+    /// drained, the interrupt is disabled again.
+    pub fn on_data_register_empty<H, RX, TX>(&self)
+    where
+        USART: UsartOps<H, RX, TX>,
+    {
+        self.with(|inner| {
+            if let Some(p) = inner.p.as_mut() {
+                match inner.tx.pop() {
+                    // The data register is empty when this interrupt fires so the write
+                    // cannot block.
+                    Some(byte) => {
+                        if inner.de.is_some() {
+                            // Make sure the TX complete flag only signals the end of this byte.
+                            p.raw_clear_tx_complete();
+                        }
+                        p.raw_write(byte).ok();
+                    }
+                    None => p.raw_interrupt(Event::DataRegisterEmpty, false),
+                }
+            }
+        })
+    }
+
+    /// Handler for the `USART_TX`/`USART#_TX` interrupt.
+    ///
+    /// Only needed for RS-485 operation.  Releases the driver-enable pin once the last byte of
+    /// the transmit buffer has left the shift register.
+    pub fn on_tx_complete<H, RX, TX>(&self)
+    where
+        USART: UsartOps<H, RX, TX>,
+        DE: embedded_hal::digital::OutputPin<Error = core::convert::Infallible>,
+    {
+        self.with(|inner| {
+            if inner.tx.len != 0 {
+                return;
+            }
+            if let (Some(p), Some(de)) = (inner.p.as_mut(), inner.de.as_mut()) {
+                de.set_low().unwrap_infallible();
+                p.raw_interrupt(Event::TxComplete, false);
+                inner.transmitting = false;
+            }
+        })
+    }
+}
+
+impl<USART, const RX_SIZE: usize, const TX_SIZE: usize, DE> Default
+    for UsartBuffers<USART, RX_SIZE, TX_SIZE, DE>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Interrupt-driven USART driver with receive and transmit buffers
+///
+/// Created by calling [`Usart::into_buffered`].  Received bytes are moved into the receive buffer
+/// by the `USART_RX` interrupt and bytes to transmit are fed to the peripheral by the
+/// `USART_UDRE` interrupt, so no data is lost while the application is busy.  See
+/// [`UsartBuffers`] for how to hook up the interrupt handlers.
+///
+/// **Note**: Interrupts must be enabled globally for the buffers to be serviced.  Otherwise,
+/// blocking writes will hang once the transmit buffer is full.
+///
+/// # Example
+/// ```
+/// static SERIAL_BUFFERS: UsartBuffers<pac::USART0, 64, 32> = UsartBuffers::new();
+///
+/// let mut serial = Usart::new(
+///     dp.USART0,
+///     pins.d0,
+///     pins.d1.into_output(),
+///     57600.into_baudrate(),
+/// )
+/// .into_buffered(&SERIAL_BUFFERS);
+/// unsafe { avr_device::interrupt::enable() };
+///
+/// loop {
+///     match serial.read_byte() {
+///         Ok(b) => ufmt::uwriteln!(&mut serial, "Got {}!\r", b).unwrap_infallible(),
+///         Err(e) => ufmt::uwriteln!(&mut serial, "Error: {:?}\r", e).unwrap_infallible(),
+///     }
+/// }
+/// ```
+pub struct BufferedUsart<
+    H,
+    USART: UsartOps<H, RX, TX> + 'static,
+    RX,
+    TX,
+    CLOCK,
+    const RX_SIZE: usize,
+    const TX_SIZE: usize,
+    DE: 'static = NoDriverEnable,
+> {
+    buffers: &'static UsartBuffers<USART, RX_SIZE, TX_SIZE, DE>,
+    rx: RX,
+    tx: TX,
+    _clock: marker::PhantomData<CLOCK>,
+    _h: marker::PhantomData<H>,
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> Usart<H, USART, RX, TX, CLOCK> {
+    /// Convert this USART into an interrupt-driven [`BufferedUsart`].
+    ///
+    /// The peripheral is moved into `buffers` and the receive interrupt is enabled.  Any data
+    /// left in the buffers from a previous use is discarded.
+    pub fn into_buffered<const RX_SIZE: usize, const TX_SIZE: usize>(
+        self,
+        buffers: &'static UsartBuffers<USART, RX_SIZE, TX_SIZE>,
+    ) -> BufferedUsart<H, USART, RX, TX, CLOCK, RX_SIZE, TX_SIZE> {
+        self.into_buffered_with(buffers, None)
+    }
+
+    fn into_buffered_with<const RX_SIZE: usize, const TX_SIZE: usize, DE>(
+        self,
+        buffers: &'static UsartBuffers<USART, RX_SIZE, TX_SIZE, DE>,
+        de: Option<DE>,
+    ) -> BufferedUsart<H, USART, RX, TX, CLOCK, RX_SIZE, TX_SIZE, DE> {
+        buffers.with(|inner| {
+            inner.rx.clear();
+            inner.tx.clear();
+            inner.error = None;
+            inner.de = de;
+            inner.transmitting = false;
+            // The interrupt handler must find the peripheral as soon as the interrupt is enabled.
+            inner
+                .p
+                .insert(self.p)
+                .raw_interrupt(Event::RxComplete, true);
+        });
+
+        BufferedUsart {
+            buffers,
+            rx: self.rx,
+            tx: self.tx,
+            _clock: marker::PhantomData,
+            _h: marker::PhantomData,
+        }
+    }
+}
+
+impl<
+        H,
+        USART: UsartOps<H, RX, TX>,
+        RX,
+        TX,
+        CLOCK,
+        const RX_SIZE: usize,
+        const TX_SIZE: usize,
+        DE: embedded_hal::digital::OutputPin<Error = core::convert::Infallible>,
+    > BufferedUsart<H, USART, RX, TX, CLOCK, RX_SIZE, TX_SIZE, DE>
+{
+    /// Transmit all buffered data and convert back into a polled [`Usart`].
+    ///
+    /// The USART interrupts are disabled again.  Data still in the receive buffer is discarded.
+    /// For RS-485 operation, use [`BufferedUsart::into_rs485`] instead to get the driver-enable
+    /// pin back.
+    pub fn into_unbuffered(self) -> Usart<H, USART, RX, TX, CLOCK> {
+        self.into_parts().0
+    }
+
+    fn into_parts(mut self) -> (Usart<H, USART, RX, TX, CLOCK>, Option<DE>) {
+        self.flush();
+        let (p, de) = self.buffers.with(|inner| (inner.p.take(), inner.de.take()));
+        let mut p = p.expect("buffers of a BufferedUsart without peripheral");
+        p.raw_interrupt(Event::RxComplete, false);
+        p.raw_interrupt(Event::DataRegisterEmpty, false);
+        p.raw_interrupt(Event::TxComplete, false);
+
+        let usart = Usart {
+            p,
+            rx: self.rx,
+            tx: self.tx,
+            rx_error: None,
+            _clock: marker::PhantomData,
+            _h: marker::PhantomData,
+        };
+        (usart, de)
+    }
+
+    /// Block until all buffered data has been transmitted.
+    ///
+    /// For RS-485 operation, this also waits until the driver-enable pin was released.
+    pub fn flush(&mut self) {
+        nb::block!(self.try_flush()).unwrap_infallible()
+    }
+
+    /// Enqueue a byte for transmission.
+    ///
+    /// This method will block while the transmit buffer is full.
+    pub fn write_byte(&mut self, byte: u8) {
+        nb::block!(self.try_write(byte)).unwrap_infallible()
+    }
+
+    /// Receive a byte.
+    ///
+    /// This method will block until a byte is available in the receive buffer.  If bytes were
+    /// lost or received with errors since the last read, the most recent [`Error`] is returned
+    /// once.  [`Error::Overrun`] is also reported when the receive buffer was full.  The bytes
+    /// received without errors are still available to subsequent reads.
+    pub fn read_byte(&mut self) -> Result<u8, Error> {
+        nb::block!(self.try_read())
+    }
+
+    /// Number of bytes waiting in the receive buffer.
+    pub fn available(&self) -> usize {
+        self.buffers.with(|inner| inner.rx.len)
+    }
+
+    /// Discard all data in the receive buffer.
+    pub fn clear_rx(&mut self) {
+        self.buffers.with(|inner| {
+            inner.rx.clear();
+            inner.error = None;
+        })
+    }
+
+    fn try_flush(&mut self) -> nb::Result<(), core::convert::Infallible> {
+        self.buffers.with(|inner| {
+            if inner.tx.len != 0 || inner.transmitting {
+                return Err(nb::Error::WouldBlock);
+            }
+            match inner.p.as_mut() {
+                Some(p) => p.raw_flush(),
+                None => Ok(()),
+            }
+        })
+    }
+
+    fn try_write(&mut self, byte: u8) -> nb::Result<(), core::convert::Infallible> {
+        self.buffers.with(|inner| {
+            inner.tx.push(byte).map_err(|_| nb::Error::WouldBlock)?;
+            if let Some(p) = inner.p.as_mut() {
+                if let Some(de) = inner.de.as_mut() {
+                    de.set_high().unwrap_infallible();
+                    inner.transmitting = true;
+                    p.raw_interrupt(Event::TxComplete, true);
+                }
+                p.raw_interrupt(Event::DataRegisterEmpty, true);
+            }
+            Ok(())
+        })
+    }
+
+    fn try_read(&mut self) -> nb::Result<u8, Error> {
+        self.buffers.with(|inner| {
+            if let Some(e) = inner.error.take() {
+                return Err(nb::Error::Other(e));
+            }
+            inner.rx.pop().ok_or(nb::Error::WouldBlock)
+        })
+    }
+}
+
+impl<
+        H,
+        USART: UsartOps<H, RX, TX>,
+        RX,
+        TX,
+        CLOCK,
+        const RX_SIZE: usize,
+        const TX_SIZE: usize,
+        DE: embedded_hal::digital::OutputPin<Error = core::convert::Infallible>,
+    > ufmt::uWrite for BufferedUsart<H, USART, RX, TX, CLOCK, RX_SIZE, TX_SIZE, DE>
+{
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        for b in s.as_bytes().iter() {
+            self.write_byte(*b);
+        }
+        Ok(())
+    }
+}
+
+impl<
+        H,
+        USART: UsartOps<H, RX, TX>,
+        RX,
+        TX,
+        CLOCK,
+        const RX_SIZE: usize,
+        const TX_SIZE: usize,
+        DE: embedded_hal::digital::OutputPin<Error = core::convert::Infallible>,
+    > embedded_hal_v0::serial::Write<u8>
+    for BufferedUsart<H, USART, RX, TX, CLOCK, RX_SIZE, TX_SIZE, DE>
+{
+    type Error = core::convert::Infallible;
+
+    fn write(&mut self, byte: u8) -> nb::Result<(), Self::Error> {
+        self.try_write(byte)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        self.try_flush()
+    }
+}
+
+impl<
+        H,
+        USART: UsartOps<H, RX, TX>,
+        RX,
+        TX,
+        CLOCK,
+        const RX_SIZE: usize,
+        const TX_SIZE: usize,
+        DE: embedded_hal::digital::OutputPin<Error = core::convert::Infallible>,
+    > embedded_hal_v0::serial::Read<u8>
+    for BufferedUsart<H, USART, RX, TX, CLOCK, RX_SIZE, TX_SIZE, DE>
+{
+    type Error = Error;
+
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        self.try_read()
+    }
+}
+
+/// RS-485 half-duplex USART driver with automatic driver-enable pin
+///
+/// Created by calling [`Usart::into_rs485`].  The DE/RE pin of the transceiver is asserted before
+/// the first byte of a message is written and released once the TX complete flag signals that
+/// the last stop bit has left the shift register.  The blocking methods ([`Rs485Usart::write_byte`]
+/// and `ufmt::uWrite`) wait for the flag and free the bus again before returning.  With the
+/// non-blocking `embedded-hal` traits, the flag is only checked by `flush()`, so **`flush()` must
+/// be called after each message** written through them.
+///
+/// For interrupt-driven transmission, convert the driver into a [`BufferedUsart`] using
+/// [`Rs485Usart::into_buffered`].  The pin is then released directly from the `USART_TX`
+/// interrupt (see [`UsartBuffers::on_tx_complete`]).
+///
+/// # Example
+/// ```
+/// let mut serial = Usart::new(
+///     dp.USART0,
+///     pins.d0,
+///     pins.d1.into_output(),
+///     57600.into_baudrate(),
+/// )
+/// .into_rs485(pins.d2.into_output());
+///
+/// loop {
+///     let request = serial.read_byte().unwrap();
+///     // The bus is released again once the reply was sent.
+///     ufmt::uwriteln!(&mut serial, "Got {}!\r", request).unwrap_infallible();
+/// }
+/// ```
+pub struct Rs485Usart<H, USART: UsartOps<H, RX, TX>, RX, TX, DE, CLOCK> {
+    usart: Usart<H, USART, RX, TX, CLOCK>,
+    de: DE,
+    transmitting: bool,
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> Usart<H, USART, RX, TX, CLOCK> {
+    /// Switch to RS-485 half-duplex operation using `de` as the driver-enable pin.
+    ///
+    /// The pin is driven low (receive mode) until data is written.
+    pub fn into_rs485<DE>(self, mut de: DE) -> Rs485Usart<H, USART, RX, TX, DE, CLOCK>
+    where
+        DE: embedded_hal::digital::OutputPin<Error = core::convert::Infallible>,
+    {
+        de.set_low().unwrap_infallible();
+        Rs485Usart {
+            usart: self,
+            de,
+            transmitting: false,
+        }
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, DE, CLOCK> Rs485Usart<H, USART, RX, TX, DE, CLOCK>
+where
+    DE: embedded_hal::digital::OutputPin<Error = core::convert::Infallible>,
+{
+    /// Finish the ongoing transmission and return the plain [`Usart`] and the driver-enable pin.
+    pub fn release(mut self) -> (Usart<H, USART, RX, TX, CLOCK>, DE) {
+        self.flush();
+        (self.usart, self.de)
+    }
+
+    /// Block until all data was transmitted and release the driver-enable pin.
+    pub fn flush(&mut self) {
+        nb::block!(self.try_flush()).unwrap_infallible()
+    }
+
+    /// Transmit a byte.
+    ///
+    /// The driver-enable pin is asserted if it was not already.  This method will block until
+    /// the byte was sent and the driver-enable pin was released again.
+    pub fn write_byte(&mut self, byte: u8) {
+        nb::block!(self.try_write(byte)).unwrap_infallible();
+        self.flush();
+    }
+
+    /// Receive a byte.
+    ///
+    /// This method will block until a byte could be received.
+    pub fn read_byte(&mut self) -> Result<u8, Error> {
+        self.usart.read_byte()
+    }
+
+    /// Convert into an interrupt-driven [`BufferedUsart`] which releases the driver-enable pin
+    /// from the `USART_TX` interrupt.
+    ///
+    /// Any ongoing transmission is finished first.  The driver-enable pin is moved into `buffers`
+    /// together with the peripheral.
+    pub fn into_buffered<const RX_SIZE: usize, const TX_SIZE: usize>(
+        mut self,
+        buffers: &'static UsartBuffers<USART, RX_SIZE, TX_SIZE, DE>,
+    ) -> BufferedUsart<H, USART, RX, TX, CLOCK, RX_SIZE, TX_SIZE, DE> {
+        self.flush();
+        self.usart.into_buffered_with(buffers, Some(self.de))
+    }
+
+    fn try_flush(&mut self) -> nb::Result<(), core::convert::Infallible> {
+        if self.transmitting {
+            if !self.usart.p.raw_tx_complete() {
+                return Err(nb::Error::WouldBlock);
+            }
+            self.de.set_low().unwrap_infallible();
+            self.transmitting = false;
+        }
+        Ok(())
+    }
+
+    fn try_write(&mut self, byte: u8) -> nb::Result<(), core::convert::Infallible> {
+        self.usart.p.raw_flush()?;
+        if !self.transmitting {
+            self.de.set_high().unwrap_infallible();
+            self.transmitting = true;
+        }
+        // Clear the TX complete flag right before filling the data register so it only signals
+        // the end of this byte.
+        self.usart.p.raw_clear_tx_complete();
+        self.usart.p.raw_write(byte)
+    }
+}
+
+impl<
+        H,
+        USART: UsartOps<H, RX, TX>,
+        RX,
+        TX,
+        CLOCK,
+        const RX_SIZE: usize,
+        const TX_SIZE: usize,
+        DE: embedded_hal::digital::OutputPin<Error = core::convert::Infallible>,
+    > BufferedUsart<H, USART, RX, TX, CLOCK, RX_SIZE, TX_SIZE, DE>
+{
+    /// Transmit all buffered data and convert back into a polled [`Rs485Usart`].
+    ///
+    /// # Panics
+    /// Panics if this [`BufferedUsart`] was not created by [`Rs485Usart::into_buffered`].
+    pub fn into_rs485(self) -> Rs485Usart<H, USART, RX, TX, DE, CLOCK> {
+        let (usart, de) = self.into_parts();
+        usart.into_rs485(de.expect("BufferedUsart without driver-enable pin"))
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, DE, CLOCK> ufmt::uWrite
+    for Rs485Usart<H, USART, RX, TX, DE, CLOCK>
+where
+    DE: embedded_hal::digital::OutputPin<Error = core::convert::Infallible>,
+{
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        // Keep the driver enabled in between the bytes and only release it at the end.
+        for b in s.as_bytes().iter() {
+            nb::block!(self.try_write(*b)).unwrap_infallible();
+        }
+        self.flush();
+        Ok(())
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, DE, CLOCK> embedded_hal_v0::serial::Write<u8>
+    for Rs485Usart<H, USART, RX, TX, DE, CLOCK>
+where
+    DE: embedded_hal::digital::OutputPin<Error = core::convert::Infallible>,
+{
+    type Error = core::convert::Infallible;
+
+    fn write(&mut self, byte: u8) -> nb::Result<(), Self::Error> {
+        self.try_write(byte)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        self.try_flush()
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, DE, CLOCK> embedded_hal_v0::serial::Read<u8>
+    for Rs485Usart<H, USART, RX, TX, DE, CLOCK>
+where
+    DE: embedded_hal::digital::OutputPin<Error = core::convert::Infallible>,
+{
+    type Error = Error;
+
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        self.usart.try_read()
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, DE, CLOCK> embedded_hal_nb::serial::ErrorType
+    for Rs485Usart<H, USART, RX, TX, DE, CLOCK>
+where
+    DE: embedded_hal::digital::OutputPin<Error = core::convert::Infallible>,
+{
+    type Error = Error;
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, DE, CLOCK> embedded_hal_nb::serial::Read<u8>
+    for Rs485Usart<H, USART, RX, TX, DE, CLOCK>
+where
+    DE: embedded_hal::digital::OutputPin<Error = core::convert::Infallible>,
+{
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        self.usart.try_read()
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, DE, CLOCK> embedded_hal_nb::serial::Write<u8>
+    for Rs485Usart<H, USART, RX, TX, DE, CLOCK>
+where
+    DE: embedded_hal::digital::OutputPin<Error = core::convert::Infallible>,
+{
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        self.try_write(word).map_err(|e| e.map(|e| match e {}))
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        self.try_flush().map_err(|e| e.map(|e| match e {}))
+    }
+}
+
+#[macro_export]
+macro_rules! impl_usart_traditional {
+    (
+        hal: $HAL:ty,
+        peripheral: $USART:ty,
+        register_suffix: $n:expr,
+        rx: $rxpin:ty,
+        tx: $txpin:ty,
+    ) => {
+        $crate::paste::paste! {
+            impl $crate::usart::UsartOps<
+                $HAL,
+                $crate::port::Pin<$crate::port::mode::Input, $rxpin>,
+                $crate::port::Pin<$crate::port::mode::Output, $txpin>,
+            > for $USART {
+                fn raw_init<CLOCK>(&mut self, settings: $crate::usart::UsartSettings<CLOCK>) {
+                    let baudrate = settings.baudrate;
+                    let frame = settings.frame_format;
+                    self.[<ubrr $n>].write(|w| unsafe { w.bits(baudrate.ubrr) });
+                    self.[<ucsr $n a>].write(|w| w.[<u2x $n>]().bit(baudrate.u2x));
+
+                    // Enable receiver and transmitter but leave interrupts disabled.
+                    self.[<ucsr $n b>].write(|w| w
+                        .[<txen $n>]().set_bit()
+                        .[<rxen $n>]().set_bit()
+                        .[<ucsz $n 2>]().bit(frame.data_bits == $crate::usart::DataBits::Nine)
+                    );
+
+                    self.[<ucsr $n c>].write(|w| {
+                        let w = w.[<umsel $n>]().usart_async();
+                        let w = match frame.data_bits {
+                            $crate::usart::DataBits::Five => w.[<ucsz $n>]().chr5(),
+                            $crate::usart::DataBits::Six => w.[<ucsz $n>]().chr6(),
+                            $crate::usart::DataBits::Seven => w.[<ucsz $n>]().chr7(),
+                            // 9-bit frames are selected by the additional UCSZn2 bit.
+                            $crate::usart::DataBits::Eight | $crate::usart::DataBits::Nine =>
+                                w.[<ucsz $n>]().chr8(),
+                        };
+                        let w = match frame.stop_bits {
+                            $crate::usart::StopBits::One => w.[<usbs $n>]().stop1(),
+                            $crate::usart::StopBits::Two => w.[<usbs $n>]().stop2(),
+                        };
+                        match frame.parity {
+                            $crate::usart::Parity::None => w.[<upm $n>]().disabled(),
+                            $crate::usart::Parity::Even => w.[<upm $n>]().parity_even(),
+                            $crate::usart::Parity::Odd => w.[<upm $n>]().parity_odd(),
+                        }
+                    });
+                }
+
+                fn raw_deinit(&mut self) {
+                    // Wait for any ongoing transfer to finish.
+                    $crate::nb::block!(self.raw_flush()).ok();
+                    self.[<ucsr $n b>].reset();
+                }
+
+                fn raw_flush(&mut self) -> $crate::nb::Result<(), core::convert::Infallible> {
+                    if self.[<ucsr $n a>].read().[<udre $n>]().bit_is_clear() {
+                        Err($crate::nb::Error::WouldBlock)
+                    } else {
+                        Ok(())
+                    }
+                }
+
+                fn raw_write(&mut self, byte: u8) -> $crate::nb::Result<(), core::convert::Infallible> {
+                    // Call flush to make sure the data-register is empty
+                    self.raw_flush()?;
+
+                    self.[<udr $n>].write(|w| unsafe { w.bits(byte) });
+                    Ok(())
+                }
+
+                fn raw_read(&mut self) -> $crate::nb::Result<u8, $crate::usart::Error> {
+                    let status = self.[<ucsr $n a>].read();
+                    if status.[<rxc $n>]().bit_is_clear() {
+                        return Err($crate::nb::Error::WouldBlock);
+                    }
+
+                    // The error flags belong to the byte in the receive buffer, so they must be
+                    // read before it.  Reading the data register clears them again.
+                    let byte = self.[<udr $n>].read().bits();
+                    match $crate::usart::Error::from_flags(
+                        status.[<fe $n>]().bit_is_set(),
+                        status.[<dor $n>]().bit_is_set(),
+                        status.[<upe $n>]().bit_is_set(),
+                    ) {
+                        Some(e) => Err($crate::nb::Error::Other(e)),
+                        None => Ok(byte),
+                    }
+                }
+
+                fn raw_read_ready(&mut self) -> bool {
+                    self.[<ucsr $n a>].read().[<rxc $n>]().bit_is_set()
+                }
+
+                fn raw_write_9bit(&mut self, word: u16) -> $crate::nb::Result<(), core::convert::Infallible> {
+                    // Call flush to make sure the data-register is empty
+                    self.raw_flush()?;
+
+                    // The 9th bit must be written before the low byte.
+                    self.[<ucsr $n b>].modify(|_, w| w.[<txb8 $n>]().bit(word & 0x100 != 0));
+                    self.[<udr $n>].write(|w| unsafe { w.bits(word as u8) });
+                    Ok(())
+                }
+
+                fn raw_read_9bit(&mut self) -> $crate::nb::Result<u16, $crate::usart::Error> {
+                    let status = self.[<ucsr $n a>].read();
+                    if status.[<rxc $n>]().bit_is_clear() {
+                        return Err($crate::nb::Error::WouldBlock);
+                    }
+
+                    // The error flags and the 9th bit must be read before the low byte.
+                    let high = self.[<ucsr $n b>].read().[<rxb8 $n>]().bit() as u16;
+                    let word = high << 8 | self.[<udr $n>].read().bits() as u16;
+                    match $crate::usart::Error::from_flags(
+                        status.[<fe $n>]().bit_is_set(),
+                        status.[<dor $n>]().bit_is_set(),
+                        status.[<upe $n>]().bit_is_set(),
+                    ) {
+                        Some(e) => Err($crate::nb::Error::Other(e)),
+                        None => Ok(word),
+                    }
+                }
+
+                fn raw_tx_complete(&mut self) -> bool {
+                    self.[<ucsr $n a>].read().[<txc $n>]().bit_is_set()
+                }
 
-                    Ok(self.[<udr $n>].read().bits())
+                fn raw_clear_tx_complete(&mut self) {
+                    // TXCn is cleared by writing a one.  Only U2Xn and MPCMn are preserved as the
+                    // other flags must be written as zero.
+                    self.[<ucsr $n a>].modify(|r, w| unsafe { w.bits(r.bits() & 0x03 | 0x40) });
                 }
 
                 fn raw_interrupt(&mut self, event: $crate::usart::Event, state: bool) {
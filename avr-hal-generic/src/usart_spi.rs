@@ -0,0 +1,238 @@
+//! USART in Master SPI Mode (MSPIM)
+//!
+//! Most USART peripherals can be switched into a mode where they act as an additional SPI
+//! controller.  The `XCK` pin of the USART provides the serial clock, `TXD` is used as MOSI and
+//! `RXD` as MISO.  There is no dedicated chip-select pin, any output pin can be used for that
+//! purpose (for example using `embedded_hal_bus::spi::ExclusiveDevice`).
+//!
+//! Check the documentation of [`UsartSpi`] for details.
+use crate::port;
+use crate::spi::Settings;
+use core::marker::PhantomData;
+use embedded_hal::spi::SpiBus;
+
+/// Internal trait for low-level USART peripherals in Master SPI Mode.
+///
+/// This trait defines the common interface for all USART peripheral variants supporting MSPIM.
+/// It is used as an intermediate abstraction ontop of which the [`UsartSpi`] API is built.
+/// **Prefer using the [`UsartSpi`] API instead of this trait.**
+pub trait UsartSpiOps<H, SCLK, MOSI, MISO> {
+    /// Switch the USART into Master SPI Mode with the given settings.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_setup(&mut self, settings: &Settings);
+    /// Disable the peripheral.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_release(&mut self);
+    /// Perform a transaction of a single byte.
+    ///
+    /// This method blocks until the byte was sent and the response was received.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_transaction(&mut self, byte: u8) -> u8;
+}
+
+/// SPI controller using a USART peripheral in Master SPI Mode
+///
+/// The USART peripheral and its `XCK`, `TXD` and `RXD` pins are used for SCLK, MOSI and MISO
+/// respectively.  The pins are hardwired for each USART peripheral and you *must* pass the
+/// correct ones.  This is enforced at compile time.
+///
+/// The same [`Settings`] as for the hardware SPI peripheral are used.  The clock rate is
+/// derived from the USART baudrate generator, so all [`SerialClockRate`] values are supported.
+///
+/// [`SerialClockRate`]: crate::spi::SerialClockRate
+///
+/// # Example
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let spi = arduino_hal::usart::UsartSpi::new(
+///     dp.USART0,
+///     pins.d4.into_output(),
+///     pins.d1.into_output(),
+///     pins.d0,
+///     spi::Settings::default(),
+/// );
+/// let cs = pins.d7.into_output();
+/// let mut device = ExclusiveDevice::new(spi, cs, arduino_hal::Delay::new());
+/// ```
+pub struct UsartSpi<H, USART, SCLKPIN, MOSIPIN, MISOPIN> {
+    p: USART,
+    sclk: port::Pin<port::mode::Output, SCLKPIN>,
+    mosi: port::Pin<port::mode::Output, MOSIPIN>,
+    miso: port::Pin<port::mode::Input, MISOPIN>,
+    _h: PhantomData<H>,
+}
+
+impl<H, USART, SCLKPIN, MOSIPIN, MISOPIN> UsartSpi<H, USART, SCLKPIN, MOSIPIN, MISOPIN>
+where
+    USART: UsartSpiOps<H, SCLKPIN, MOSIPIN, MISOPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+{
+    /// Switch a USART peripheral into Master SPI Mode on the given pins.
+    pub fn new<IMODE: port::mode::InputMode>(
+        p: USART,
+        sclk: port::Pin<port::mode::Output, SCLKPIN>,
+        mosi: port::Pin<port::mode::Output, MOSIPIN>,
+        miso: port::Pin<port::mode::Input<IMODE>, MISOPIN>,
+        settings: Settings,
+    ) -> Self {
+        let mut spi = Self {
+            p,
+            sclk,
+            mosi,
+            miso: miso.forget_imode(),
+            _h: PhantomData,
+        };
+        spi.p.raw_setup(&settings);
+        spi
+    }
+
+    /// Reconfigure the peripheral after initializing.
+    pub fn reconfigure(&mut self, settings: Settings) {
+        self.p.raw_setup(&settings);
+    }
+
+    /// Disable the peripheral and release ownership of the peripheral and pins.
+    pub fn release(
+        mut self,
+    ) -> (
+        USART,
+        port::Pin<port::mode::Output, SCLKPIN>,
+        port::Pin<port::mode::Output, MOSIPIN>,
+        port::Pin<port::mode::Input, MISOPIN>,
+    ) {
+        self.p.raw_release();
+        (self.p, self.sclk, self.mosi, self.miso)
+    }
+}
+
+impl<H, USART, SCLKPIN, MOSIPIN, MISOPIN> embedded_hal::spi::ErrorType
+    for UsartSpi<H, USART, SCLKPIN, MOSIPIN, MISOPIN>
+where
+    USART: UsartSpiOps<H, SCLKPIN, MOSIPIN, MISOPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+{
+    type Error = core::convert::Infallible;
+}
+
+impl<H, USART, SCLKPIN, MOSIPIN, MISOPIN> SpiBus for UsartSpi<H, USART, SCLKPIN, MOSIPIN, MISOPIN>
+where
+    USART: UsartSpiOps<H, SCLKPIN, MOSIPIN, MISOPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+{
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        // All transactions are blocking so there is never anything left to flush.
+        Ok(())
+    }
+
+    fn read(&mut self, read: &mut [u8]) -> Result<(), Self::Error> {
+        for b in read.iter_mut() {
+            // We send 0x00 on MOSI during "pure" reading
+            *b = self.p.raw_transaction(0x00);
+        }
+
+        Ok(())
+    }
+
+    fn write(&mut self, write: &[u8]) -> Result<(), Self::Error> {
+        for b in write.iter() {
+            self.p.raw_transaction(*b);
+        }
+
+        Ok(())
+    }
+
+    fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        let longest = read.len().max(write.len());
+        for i in 0..longest {
+            let r = self.p.raw_transaction(*write.get(i).unwrap_or(&0x00));
+            if i < read.len() {
+                read[i] = r;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn transfer_in_place(&mut self, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        for b in buffer.iter_mut() {
+            *b = self.p.raw_transaction(*b)
+        }
+
+        Ok(())
+    }
+}
+
+/// Implement traits for a USART peripheral in Master SPI Mode
+#[macro_export]
+macro_rules! impl_usart_spi {
+    (
+        hal: $HAL:ty,
+        peripheral: $USART:ty,
+        register_suffix: $n:expr,
+        sclk: $sclkpin:ty,
+        mosi: $mosipin:ty,
+        miso: $misopin:ty,
+    ) => {
+        $crate::paste::paste! {
+            impl $crate::usart_spi::UsartSpiOps<$HAL, $sclkpin, $mosipin, $misopin> for $USART {
+                fn raw_setup(&mut self, settings: &$crate::spi::Settings) {
+                    use $crate::hal::spi;
+
+                    // The baudrate register must be zero while the transmitter is enabled.
+                    self.[<ubrr $n>].write(|w| unsafe { w.bits(0) });
+
+                    // UMSELn = 0b11 selects Master SPI Mode.  In this mode, the UCSZn bits are
+                    // reused as UDORDn (bit 2) and UCPHAn (bit 1), which is why the register is
+                    // written as raw bits.
+                    let mut ucsrc = 0b1100_0000;
+                    match settings.data_order {
+                        $crate::spi::DataOrder::MostSignificantFirst => (),
+                        $crate::spi::DataOrder::LeastSignificantFirst => ucsrc |= 1 << 2,
+                    }
+                    match settings.mode.phase {
+                        spi::Phase::CaptureOnFirstTransition => (),
+                        spi::Phase::CaptureOnSecondTransition => ucsrc |= 1 << 1,
+                    }
+                    match settings.mode.polarity {
+                        spi::Polarity::IdleLow => (),
+                        spi::Polarity::IdleHigh => ucsrc |= 1 << 0,
+                    }
+                    self.[<ucsr $n c>].write(|w| unsafe { w.bits(ucsrc) });
+
+                    self.[<ucsr $n b>].write(|w| w
+                        .[<txen $n>]().set_bit()
+                        .[<rxen $n>]().set_bit()
+                    );
+
+                    // F_sck = F_osc / (2 * (UBRRn + 1))
+                    let ubrr = settings.clock.into_divider() as u16 / 2 - 1;
+                    self.[<ubrr $n>].write(|w| unsafe { w.bits(ubrr) });
+                }
+
+                fn raw_release(&mut self) {
+                    self.[<ucsr $n b>].reset();
+                    self.[<ucsr $n c>].reset();
+                }
+
+                fn raw_transaction(&mut self, byte: u8) -> u8 {
+                    while self.[<ucsr $n a>].read().[<udre $n>]().bit_is_clear() {}
+                    self.[<udr $n>].write(|w| unsafe { w.bits(byte) });
+
+                    while self.[<ucsr $n a>].read().[<rxc $n>]().bit_is_clear() {}
+                    self.[<udr $n>].read().bits()
+                }
+            }
+        }
+    };
+}
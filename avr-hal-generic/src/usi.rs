@@ -0,0 +1,938 @@
+//! Universal Serial Interface (USI)
+//!
+//! Many ATtiny devices have no dedicated TWI or SPI peripheral.  Instead, their USI provides the
+//! basic hardware for both protocols:
+//!
+//! - In **two-wire mode**, it is used as an I2C controller ([`UsiI2c`]) or target
+//!   ([`UsiI2cTarget`]).  The `DI`/`SDA` and `USCK`/`SCL` pins are used.
+//! - In **three-wire mode**, it is used as an SPI controller ([`UsiSpi`]).  `USCK` is the serial
+//!   clock, `DO` is MOSI and `DI` is MISO.
+//!
+//! The USI only shifts the data, so the protocol itself (start/stop conditions, acknowledge bits,
+//! clock generation) is implemented in software by these drivers.
+use crate::i2c::{Direction, Error, TargetEvent};
+use crate::port;
+use crate::spi::Settings;
+use core::marker::PhantomData;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::SevenBitAddress;
+use embedded_hal::spi::{self, SpiBus};
+
+// USICR bits
+const USISIE: u8 = 1 << 7;
+const USIOIE: u8 = 1 << 6;
+const USIWM1: u8 = 1 << 5;
+const USIWM0: u8 = 1 << 4;
+const USICS1: u8 = 1 << 3;
+const USICS0: u8 = 1 << 2;
+const USICLK: u8 = 1 << 1;
+const USITC: u8 = 1 << 0;
+
+// USISR bits
+const USISIF: u8 = 1 << 7;
+const USIOIF: u8 = 1 << 6;
+const USIPF: u8 = 1 << 5;
+const USIDC: u8 = 1 << 4;
+
+/// Counter value for shifting a single bit (the counter counts both clock edges).
+const USICNT_1BIT: u8 = 0x0e;
+
+/// Internal trait for low-level USI peripherals.
+///
+/// This trait gives raw access to the registers of the USI.  It is used as an intermediate
+/// abstraction ontop of which the [`UsiI2c`], [`UsiI2cTarget`] and [`UsiSpi`] APIs are built.
+/// **Prefer using these APIs instead of this trait.**
+pub trait UsiOps<H> {
+    /// Write the control register (`USICR`).
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_write_control(&mut self, value: u8);
+    /// Read the status register (`USISR`).
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_status(&self) -> u8;
+    /// Write the status register (`USISR`).  Flags are cleared by writing a one.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_write_status(&mut self, value: u8);
+    /// Read the data register (`USIDR`).
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_data(&self) -> u8;
+    /// Write the data register (`USIDR`).
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_write_data(&mut self, value: u8);
+}
+
+/// Pins of a USI peripheral in two-wire (I2C) mode.
+pub trait UsiTwoWirePins<H, SDAPIN, SCLPIN>: UsiOps<H> {}
+
+/// Pins of a USI peripheral in three-wire (SPI) mode.
+pub trait UsiThreeWirePins<H, SCLKPIN, MOSIPIN, MISOPIN>: UsiOps<H> {}
+
+/// I2C controller using the USI in two-wire mode
+///
+/// This driver offers the same API as the TWI based [`I2c`][crate::i2c::I2c] driver.  The USI
+/// does not drive the lines high, so external pull-up resistors are required on both of them.
+/// Devices which stretch the clock are supported; use [`UsiI2c::set_timeout`] to limit how long
+/// the controller waits for them.
+///
+/// # Example
+/// ```
+/// let dp = attiny_hal::Peripherals::take().unwrap();
+/// let pins = attiny_hal::pins!(dp);
+///
+/// let mut i2c = attiny_hal::i2c::I2c::<CoreClock>::with_external_pullup(
+///     dp.USI,
+///     pins.pb0.into_floating_input(),
+///     pins.pb2.into_floating_input(),
+///     100_000,
+/// );
+///
+/// let mut buffer = [0u8; 2];
+/// i2c.write_read(0x48, &[0x00], &mut buffer).unwrap();
+/// ```
+pub struct UsiI2c<H, USI, SDAPIN, SCLPIN, CLOCK> {
+    p: USI,
+    sda: port::Pin<port::mode::Input, SDAPIN>,
+    scl: port::Pin<port::mode::Input, SCLPIN>,
+    half_period: u16,
+    timeout: Option<u32>,
+    delay: crate::delay::Delay<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, USI, SDAPIN, SCLPIN, CLOCK> UsiI2c<H, USI, SDAPIN, SCLPIN, CLOCK>
+where
+    USI: UsiTwoWirePins<H, SDAPIN, SCLPIN>,
+    SDAPIN: port::PinOps,
+    SCLPIN: port::PinOps,
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: DelayNs,
+{
+    /// Initialize an I2C controller on the USI at a certain `speed` in Hz.
+    ///
+    /// **Note**: The internal pull-ups are disabled by the USI, external pull-up resistors are
+    /// still required.
+    pub fn new(
+        p: USI,
+        sda: port::Pin<port::mode::Input<port::mode::PullUp>, SDAPIN>,
+        scl: port::Pin<port::mode::Input<port::mode::PullUp>, SCLPIN>,
+        speed: u32,
+    ) -> Self {
+        Self::setup(p, sda.forget_imode(), scl.forget_imode(), speed)
+    }
+
+    /// Initialize an I2C controller on the USI at a certain `speed` in Hz, with external
+    /// pull-up resistors on SDA and SCL.
+    pub fn with_external_pullup(
+        p: USI,
+        sda: port::Pin<port::mode::Input<port::mode::Floating>, SDAPIN>,
+        scl: port::Pin<port::mode::Input<port::mode::Floating>, SCLPIN>,
+        speed: u32,
+    ) -> Self {
+        Self::setup(p, sda.forget_imode(), scl.forget_imode(), speed)
+    }
+
+    fn setup(
+        mut p: USI,
+        mut sda: port::Pin<port::mode::Input, SDAPIN>,
+        mut scl: port::Pin<port::mode::Input, SCLPIN>,
+        speed: u32,
+    ) -> Self {
+        // Two-wire mode, shift register clocked by software strobes.  In this mode, the output
+        // drivers of both pins only ever pull the lines low.
+        p.raw_write_data(0xff);
+        p.raw_write_control(USIWM1 | USICS1 | USICLK);
+        p.raw_write_status(USISIF | USIOIF | USIPF | USIDC);
+        unsafe {
+            sda.pin.out_set();
+            sda.pin.make_output();
+            scl.pin.out_set();
+            scl.pin.make_output();
+        }
+
+        Self {
+            p,
+            sda,
+            scl,
+            half_period: (500_000 / speed).clamp(1, u16::MAX as u32) as u16,
+            timeout: None,
+            delay: crate::delay::Delay::new(),
+            _h: PhantomData,
+        }
+    }
+
+    /// Disable the USI and release ownership of the peripheral and pins.
+    pub fn release(
+        mut self,
+    ) -> (
+        USI,
+        port::Pin<port::mode::Input, SDAPIN>,
+        port::Pin<port::mode::Input, SCLPIN>,
+    ) {
+        self.p.raw_write_control(0);
+        unsafe {
+            self.sda.pin.make_input(false);
+            self.scl.pin.make_input(false);
+        }
+        (self.p, self.sda, self.scl)
+    }
+
+    /// Set the timeout for clock stretching in microseconds.
+    ///
+    /// If a device holds SCL low for longer than this, the operation is aborted with
+    /// [`Error::Timeout`].  `None` (the default) waits forever.  The timeout is only approximate
+    /// as it is implemented by counting loop iterations.
+    pub fn set_timeout(&mut self, timeout_us: Option<u32>) {
+        self.timeout = timeout_us
+            .map(|us| us.saturating_mul(CLOCK::FREQ / 1_000_000) / crate::i2c::WAIT_LOOP_CYCLES);
+    }
+
+    /// Test whether a device answers on a certain address.
+    pub fn ping_device(&mut self, address: u8, direction: Direction) -> Result<bool, Error> {
+        match self.start(address, direction) {
+            Ok(_) => {
+                self.stop()?;
+                Ok(true)
+            }
+            Err(Error::AddressNack) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn wait_half_period(&mut self) {
+        self.delay.delay_us(self.half_period.into());
+    }
+
+    fn wait_scl_high(&mut self) -> Result<(), Error> {
+        let scl = &self.scl;
+        crate::i2c::wait_until(self.timeout, || scl.is_high())
+    }
+
+    /// Clock `USIDR` out (and SDA in) until the counter overflows.
+    fn transfer(&mut self, counter: u8) -> Result<u8, Error> {
+        self.p
+            .raw_write_status(USISIF | USIOIF | USIPF | USIDC | counter);
+        let strobe = USIWM1 | USICS1 | USICLK | USITC;
+        loop {
+            self.wait_half_period();
+            // Toggle SCL high and wait for devices stretching the clock
+            self.p.raw_write_control(strobe);
+            self.wait_scl_high()?;
+            self.wait_half_period();
+            // Toggle SCL low
+            self.p.raw_write_control(strobe);
+            if self.p.raw_read_status() & USIOIF != 0 {
+                break;
+            }
+        }
+        self.wait_half_period();
+        let data = self.p.raw_read_data();
+        self.p.raw_write_data(0xff);
+        unsafe { self.sda.pin.make_output() };
+        Ok(data)
+    }
+
+    /// Send a byte and return whether it was acknowledged.
+    fn write_byte(&mut self, byte: u8) -> Result<bool, Error> {
+        self.p.raw_write_data(byte);
+        self.transfer(0)?;
+        unsafe { self.sda.pin.make_input(false) };
+        Ok(self.transfer(USICNT_1BIT)? & 0x01 == 0)
+    }
+
+    fn read_byte(&mut self, ack: bool) -> Result<u8, Error> {
+        unsafe { self.sda.pin.make_input(false) };
+        let byte = self.transfer(0)?;
+        self.p.raw_write_data(if ack { 0x00 } else { 0xff });
+        self.transfer(USICNT_1BIT)?;
+        Ok(byte)
+    }
+
+    fn start(&mut self, address: u8, direction: Direction) -> Result<(), Error> {
+        // Start condition: SDA falls while SCL is high.  This also serves as a repeated start.
+        unsafe { self.scl.pin.out_set() };
+        self.wait_scl_high()?;
+        self.wait_half_period();
+        unsafe { self.sda.pin.out_clear() };
+        self.wait_half_period();
+        unsafe {
+            self.scl.pin.out_clear();
+            self.sda.pin.out_set();
+        }
+        if self.p.raw_read_status() & USISIF == 0 {
+            return Err(Error::BusError);
+        }
+
+        let dirbit = match direction {
+            Direction::Write => 0,
+            Direction::Read => 1,
+        };
+        if self.write_byte((address << 1) | dirbit)? {
+            Ok(())
+        } else {
+            self.stop()?;
+            Err(Error::AddressNack)
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        for byte in bytes {
+            if !self.write_byte(*byte)? {
+                self.stop()?;
+                return Err(Error::DataNack);
+            }
+        }
+        Ok(())
+    }
+
+    fn read(&mut self, buffer: &mut [u8], last_read: bool) -> Result<(), Error> {
+        let last = buffer.len().saturating_sub(1);
+        for (i, byte) in buffer.iter_mut().enumerate() {
+            // The last byte of a read is not acknowledged so the device releases the bus.
+            *byte = self.read_byte(!(last_read && i == last))?;
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), Error> {
+        // Stop condition: SDA rises while SCL is high.
+        unsafe {
+            self.sda.pin.out_clear();
+            self.scl.pin.out_set();
+        }
+        self.wait_scl_high()?;
+        self.wait_half_period();
+        unsafe { self.sda.pin.out_set() };
+        self.wait_half_period();
+        if self.p.raw_read_status() & USIPF == 0 {
+            return Err(Error::ArbitrationLost);
+        }
+        Ok(())
+    }
+}
+
+impl<H, USI, SDAPIN, SCLPIN, CLOCK> UsiI2c<H, USI, SDAPIN, SCLPIN, CLOCK>
+where
+    USI: UsiTwoWirePins<H, SDAPIN, SCLPIN>,
+    SDAPIN: port::PinOps,
+    SCLPIN: port::PinOps,
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: DelayNs,
+{
+    /// Scan the bus for connected devices.  This method will output an summary in the format known
+    /// from [`i2cdetect(8)`][i2cdetect-linux] on the selected serial connection.
+    ///
+    /// See [`I2c::i2cdetect`][crate::i2c::I2c::i2cdetect] for an example of the output.
+    ///
+    /// [i2cdetect-linux]: https://man.archlinux.org/man/community/i2c-tools/i2cdetect.8.en
+    pub fn i2cdetect<W: ufmt::uWrite>(
+        &mut self,
+        w: &mut W,
+        direction: Direction,
+    ) -> Result<(), W::Error> {
+        crate::i2c::i2cdetect::<CLOCK, W, _>(w, |address| self.ping_device(address, direction))
+    }
+}
+
+impl<H, USI, SDAPIN, SCLPIN, CLOCK> embedded_hal::i2c::ErrorType
+    for UsiI2c<H, USI, SDAPIN, SCLPIN, CLOCK>
+{
+    type Error = Error;
+}
+
+impl<H, USI, SDAPIN, SCLPIN, CLOCK> embedded_hal::i2c::I2c<SevenBitAddress>
+    for UsiI2c<H, USI, SDAPIN, SCLPIN, CLOCK>
+where
+    USI: UsiTwoWirePins<H, SDAPIN, SCLPIN>,
+    SDAPIN: port::PinOps,
+    SCLPIN: port::PinOps,
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: DelayNs,
+{
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut previous_direction = Direction::Read;
+        let count = operations.len();
+        for idx in 0..count {
+            // Only the last byte before a new start or the stop is not acknowledged.
+            let last_read = !matches!(
+                operations.get(idx + 1),
+                Some(embedded_hal::i2c::Operation::Read(_))
+            );
+            match &mut operations[idx] {
+                embedded_hal::i2c::Operation::Read(buffer) => {
+                    if idx == 0 || previous_direction != Direction::Read {
+                        self.start(address, Direction::Read)?;
+                    }
+                    self.read(buffer, last_read)?;
+                    previous_direction = Direction::Read;
+                }
+                embedded_hal::i2c::Operation::Write(bytes) => {
+                    if idx == 0 || previous_direction != Direction::Write {
+                        self.start(address, Direction::Write)?;
+                    }
+                    self.write(bytes)?;
+                    previous_direction = Direction::Write;
+                }
+            }
+        }
+        if count > 0 {
+            self.stop()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TargetState {
+    /// Waiting for a start condition
+    Idle,
+    /// Receiving the address byte
+    Address,
+    /// Acknowledging our address for a write
+    AckWrite { general_call: bool },
+    /// Receiving a data byte
+    Receive,
+    /// Acknowledging a received byte
+    AckReceive,
+    /// Acknowledging our address for a read
+    AckRead,
+    /// Waiting for the application to call `respond()`
+    AwaitResponse,
+    /// Sending a data byte
+    Send,
+    /// Receiving the acknowledge bit of the controller
+    ReadAck,
+}
+
+/// I2C target (slave) using the USI in two-wire mode
+///
+/// This driver offers the same API as the TWI based [`I2cTarget`][crate::i2c::I2cTarget] driver:
+///
+/// - **Polling**: Call [`UsiI2cTarget::poll`] regularly and handle the returned
+///   [`TargetEvent`]s.  On [`TargetEvent::ReadRequest`], the next byte must be passed to
+///   [`UsiI2cTarget::respond`].
+/// - **Interrupt-driven**: Enable the USI interrupts using [`UsiI2cTarget::listen`] and call
+///   [`UsiI2cTarget::handle_interrupt`] from both the `USI_START` and `USI_OVF` interrupt
+///   handlers.
+///
+/// While the target waits for the application, the bus is held by stretching SCL.  External
+/// pull-up resistors are required on both lines.
+///
+/// **Note**: The USI can only detect a stop condition by polling.  When interrupt-driven, the
+/// [`TargetEvent::Stop`] after a write is only reported once the next transaction starts.
+///
+/// # Example
+/// ```
+/// let mut target = attiny_hal::i2c::I2cTarget::new(
+///     dp.USI,
+///     pins.pb0.into_floating_input(),
+///     pins.pb2.into_floating_input(),
+///     0x42,
+/// );
+///
+/// let mut register = 0u8;
+/// loop {
+///     match nb::block!(target.poll()) {
+///         Ok(TargetEvent::Received(b)) => register = b,
+///         Ok(TargetEvent::ReadRequest) => target.respond(register),
+///         _ => (),
+///     }
+/// }
+/// ```
+pub struct UsiI2cTarget<H, USI, SDAPIN, SCLPIN> {
+    p: USI,
+    sda: port::Pin<port::mode::Input, SDAPIN>,
+    scl: port::Pin<port::mode::Input, SCLPIN>,
+    address: u8,
+    general_call: bool,
+    listening: bool,
+    hold: bool,
+    state: TargetState,
+    _h: PhantomData<H>,
+}
+
+impl<H, USI, SDAPIN, SCLPIN> UsiI2cTarget<H, USI, SDAPIN, SCLPIN>
+where
+    USI: UsiTwoWirePins<H, SDAPIN, SCLPIN>,
+    SDAPIN: port::PinOps,
+    SCLPIN: port::PinOps,
+{
+    /// Initialize an I2C target on the USI which answers to the 7-bit `address`.
+    pub fn new<SDAMODE, SCLMODE>(
+        p: USI,
+        sda: port::Pin<port::mode::Input<SDAMODE>, SDAPIN>,
+        scl: port::Pin<port::mode::Input<SCLMODE>, SCLPIN>,
+        address: u8,
+    ) -> Self
+    where
+        SDAMODE: port::mode::InputMode,
+        SCLMODE: port::mode::InputMode,
+    {
+        let mut target = Self {
+            p,
+            sda: sda.forget_imode(),
+            scl: scl.forget_imode(),
+            address,
+            general_call: false,
+            listening: false,
+            hold: false,
+            state: TargetState::Idle,
+            _h: PhantomData,
+        };
+        unsafe {
+            target.sda.pin.out_set();
+            target.sda.pin.make_input(false);
+            target.scl.pin.out_set();
+            target.scl.pin.make_output();
+        }
+        target.start_condition_mode();
+        target.p.raw_write_status(USISIF | USIOIF | USIPF | USIDC);
+        target
+    }
+
+    /// Disable the USI and release ownership of the peripheral and pins.
+    pub fn release(
+        mut self,
+    ) -> (
+        USI,
+        port::Pin<port::mode::Input, SDAPIN>,
+        port::Pin<port::mode::Input, SCLPIN>,
+    ) {
+        self.p.raw_write_control(0);
+        unsafe {
+            self.sda.pin.make_input(false);
+            self.scl.pin.make_input(false);
+        }
+        (self.p, self.sda, self.scl)
+    }
+
+    /// Enable/Disable answering to the general call address (0x00).
+    pub fn set_general_call(&mut self, enable: bool) {
+        self.general_call = enable;
+    }
+
+    /// Enable the `USI_START` and `USI_OVF` interrupts.
+    ///
+    /// The interrupt handlers must call [`UsiI2cTarget::handle_interrupt`], otherwise they will
+    /// fire continuously.
+    pub fn listen(&mut self) {
+        self.listening = true;
+        self.write_control();
+    }
+
+    /// Disable the `USI_START` and `USI_OVF` interrupts.
+    pub fn unlisten(&mut self) {
+        self.listening = false;
+        self.write_control();
+    }
+
+    /// Check for the next event.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] if nothing happened on the bus.  After a
+    /// [`TargetEvent::ReadRequest`], the bus stays blocked until [`UsiI2cTarget::respond`] is
+    /// called.  All other events are acknowledged automatically.
+    pub fn poll(&mut self) -> nb::Result<TargetEvent, Error> {
+        let status = self.p.raw_read_status();
+
+        if status & USISIF != 0 {
+            let ended_write = self.in_write();
+            self.on_start();
+            return if ended_write {
+                Ok(TargetEvent::Stop)
+            } else {
+                Err(nb::Error::WouldBlock)
+            };
+        }
+
+        if status & USIOIF != 0 {
+            return self.on_overflow();
+        }
+
+        if status & USIPF != 0 {
+            self.p.raw_write_status(USIPF);
+            let ended_write = self.in_write();
+            if self.state != TargetState::AwaitResponse {
+                self.state = TargetState::Idle;
+                self.start_condition_mode();
+            }
+            if ended_write {
+                return Ok(TargetEvent::Stop);
+            }
+        }
+
+        Err(nb::Error::WouldBlock)
+    }
+
+    /// Send a byte in response to a [`TargetEvent::ReadRequest`].
+    pub fn respond(&mut self, byte: u8) {
+        self.p.raw_write_data(byte);
+        unsafe { self.sda.pin.make_output() };
+        self.state = TargetState::Send;
+        self.p.raw_write_status(USIOIF | USIPF | USIDC);
+    }
+
+    /// Handle the event which triggered the `USI_START` or `USI_OVF` interrupt.
+    ///
+    /// `f` is called with the event and must return the byte to send for a
+    /// [`TargetEvent::ReadRequest`].  The return value is ignored for all other events.  Errors
+    /// are handled internally and not reported.
+    pub fn handle_interrupt<F: FnOnce(TargetEvent) -> u8>(&mut self, f: F) {
+        if let Ok(event) = self.poll() {
+            let response = f(event);
+            if event == TargetEvent::ReadRequest {
+                self.respond(response);
+            }
+        }
+    }
+
+    fn in_write(&self) -> bool {
+        matches!(
+            self.state,
+            TargetState::AckWrite { .. } | TargetState::Receive | TargetState::AckReceive
+        )
+    }
+
+    fn on_start(&mut self) {
+        unsafe { self.sda.pin.make_input(false) };
+        // Wait until the controller finished the start condition (SCL low) or sent a stop
+        // condition instead (SDA high).
+        while self.scl.is_high() && self.sda.is_low() {}
+        self.hold = self.sda.is_low();
+        self.write_control();
+        self.state = TargetState::Address;
+        self.p.raw_write_status(USISIF | USIOIF | USIPF | USIDC);
+    }
+
+    fn on_overflow(&mut self) -> nb::Result<TargetEvent, Error> {
+        let data = self.p.raw_read_data();
+        match self.state {
+            TargetState::Address => {
+                let general_call = self.general_call && data == 0x00;
+                if general_call || data >> 1 == self.address {
+                    self.state = if data & 0x01 != 0 {
+                        TargetState::AckRead
+                    } else {
+                        TargetState::AckWrite { general_call }
+                    };
+                    self.send_ack();
+                } else {
+                    self.state = TargetState::Idle;
+                    self.start_condition_mode();
+                }
+                Err(nb::Error::WouldBlock)
+            }
+            TargetState::AckWrite { general_call } => {
+                self.state = TargetState::Receive;
+                self.read_data();
+                Ok(TargetEvent::WriteRequest { general_call })
+            }
+            TargetState::Receive => {
+                self.state = TargetState::AckReceive;
+                self.send_ack();
+                Ok(TargetEvent::Received(data))
+            }
+            TargetState::AckReceive => {
+                self.state = TargetState::Receive;
+                self.read_data();
+                Err(nb::Error::WouldBlock)
+            }
+            TargetState::AckRead => {
+                // Leave the bus blocked until respond() was called.
+                self.state = TargetState::AwaitResponse;
+                Ok(TargetEvent::ReadRequest)
+            }
+            TargetState::AwaitResponse => Err(nb::Error::WouldBlock),
+            TargetState::Send => {
+                self.state = TargetState::ReadAck;
+                unsafe { self.sda.pin.make_input(false) };
+                self.p.raw_write_data(0x00);
+                self.p
+                    .raw_write_status(USIOIF | USIPF | USIDC | USICNT_1BIT);
+                Err(nb::Error::WouldBlock)
+            }
+            TargetState::ReadAck => {
+                if data & 0x01 != 0 {
+                    // NACK, the controller does not want any more data.
+                    self.state = TargetState::Idle;
+                    self.start_condition_mode();
+                    Ok(TargetEvent::Stop)
+                } else {
+                    self.state = TargetState::AwaitResponse;
+                    Ok(TargetEvent::ReadRequest)
+                }
+            }
+            TargetState::Idle => {
+                self.start_condition_mode();
+                Err(nb::Error::Other(Error::Unknown))
+            }
+        }
+    }
+
+    fn send_ack(&mut self) {
+        self.p.raw_write_data(0x00);
+        unsafe { self.sda.pin.make_output() };
+        self.p
+            .raw_write_status(USIOIF | USIPF | USIDC | USICNT_1BIT);
+    }
+
+    fn read_data(&mut self) {
+        unsafe { self.sda.pin.make_input(false) };
+        self.p.raw_write_status(USIOIF | USIPF | USIDC);
+    }
+
+    fn start_condition_mode(&mut self) {
+        unsafe { self.sda.pin.make_input(false) };
+        self.hold = false;
+        self.write_control();
+        self.p.raw_write_status(USIOIF | USIPF | USIDC);
+    }
+
+    fn write_control(&mut self) {
+        // While a transaction is ongoing, SCL is held low on counter overflow until the status
+        // register is written.
+        let mut control = USIWM1 | USICS1;
+        if self.hold {
+            control |= USIWM0;
+        }
+        if self.listening {
+            control |= USISIE;
+            if self.hold {
+                control |= USIOIE;
+            }
+        }
+        self.p.raw_write_control(control);
+    }
+}
+
+/// SPI controller using the USI in three-wire mode
+///
+/// The `USCK`, `DO` and `DI` pins of the USI are used for SCLK, MOSI and MISO respectively.
+/// Chip-select is not handled by this driver, any output pin can be used for that purpose (for
+/// example using `embedded_hal_bus::spi::ExclusiveDevice`).
+///
+/// The clock is generated in software.  The `clock` field of the [`Settings`] is used to derive
+/// delays between the clock edges, but the maximum rate is limited by the software overhead.
+///
+/// **Note**: The USI only supports the modes with SCLK idling low ([`spi::MODE_0`] and
+/// [`spi::MODE_1`]).
+///
+/// # Example
+/// ```
+/// let mut spi = attiny_hal::spi::UsiSpi::<CoreClock>::new(
+///     dp.USI,
+///     pins.pb2.into_output(),
+///     pins.pb1.into_output(),
+///     pins.pb0.into_pull_up_input(),
+///     spi::Settings::default(),
+/// );
+/// let cs = pins.pb3.into_output();
+/// let mut device = ExclusiveDevice::new(spi, cs, attiny_hal::delay::Delay::<CoreClock>::new());
+/// ```
+pub struct UsiSpi<H, USI, SCLKPIN, MOSIPIN, MISOPIN, CLOCK> {
+    p: USI,
+    sclk: port::Pin<port::mode::Output, SCLKPIN>,
+    mosi: port::Pin<port::mode::Output, MOSIPIN>,
+    miso: port::Pin<port::mode::Input, MISOPIN>,
+    strobe: u8,
+    half_period: u16,
+    delay: crate::delay::Delay<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, USI, SCLKPIN, MOSIPIN, MISOPIN, CLOCK> UsiSpi<H, USI, SCLKPIN, MOSIPIN, MISOPIN, CLOCK>
+where
+    USI: UsiThreeWirePins<H, SCLKPIN, MOSIPIN, MISOPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: DelayNs,
+{
+    /// Initialize an SPI controller on the USI.
+    ///
+    /// # Panics
+    /// Panics if the settings select a mode with SCLK idling high.
+    pub fn new<IMODE: port::mode::InputMode>(
+        p: USI,
+        sclk: port::Pin<port::mode::Output, SCLKPIN>,
+        mosi: port::Pin<port::mode::Output, MOSIPIN>,
+        miso: port::Pin<port::mode::Input<IMODE>, MISOPIN>,
+        settings: Settings,
+    ) -> Self {
+        let mut spi = Self {
+            p,
+            sclk,
+            mosi,
+            miso: miso.forget_imode(),
+            strobe: 0,
+            half_period: 0,
+            delay: crate::delay::Delay::new(),
+            _h: PhantomData,
+        };
+        spi.reconfigure(settings);
+        spi
+    }
+
+    /// Reconfigure the USI after initializing.
+    ///
+    /// # Panics
+    /// Panics if the settings select a mode with SCLK idling high.
+    pub fn reconfigure(&mut self, settings: Settings) {
+        assert!(
+            settings.mode.polarity == spi::Polarity::IdleLow,
+            "USI only supports SPI modes with SCLK idling low"
+        );
+        assert!(
+            settings.data_order == crate::spi::DataOrder::MostSignificantFirst,
+            "USI only supports MSB-first SPI"
+        );
+
+        // Three-wire mode, shift register clocked by the USCK pin (which is toggled in software
+        // through USITC).  USICS0 selects sampling on the falling edge.
+        self.strobe = USIWM0 | USICS1 | USICLK | USITC;
+        if settings.mode.phase == spi::Phase::CaptureOnSecondTransition {
+            self.strobe |= USICS0;
+        }
+        self.half_period = (settings.clock.into_divider() as u32 * 500_000 / CLOCK::FREQ) as u16;
+
+        self.sclk.set_low();
+        self.p.raw_write_control(self.strobe & !USITC);
+    }
+
+    /// Disable the USI and release ownership of the peripheral and pins.
+    pub fn release(
+        mut self,
+    ) -> (
+        USI,
+        port::Pin<port::mode::Output, SCLKPIN>,
+        port::Pin<port::mode::Output, MOSIPIN>,
+        port::Pin<port::mode::Input, MISOPIN>,
+    ) {
+        self.p.raw_write_control(0);
+        (self.p, self.sclk, self.mosi, self.miso)
+    }
+
+    fn transaction(&mut self, byte: u8) -> u8 {
+        self.p.raw_write_data(byte);
+        self.p.raw_write_status(USIOIF);
+        while self.p.raw_read_status() & USIOIF == 0 {
+            if self.half_period > 0 {
+                self.delay.delay_us(self.half_period.into());
+            }
+            self.p.raw_write_control(self.strobe);
+        }
+        self.p.raw_read_data()
+    }
+}
+
+impl<H, USI, SCLKPIN, MOSIPIN, MISOPIN, CLOCK> spi::ErrorType
+    for UsiSpi<H, USI, SCLKPIN, MOSIPIN, MISOPIN, CLOCK>
+where
+    USI: UsiThreeWirePins<H, SCLKPIN, MOSIPIN, MISOPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: DelayNs,
+{
+    type Error = core::convert::Infallible;
+}
+
+impl<H, USI, SCLKPIN, MOSIPIN, MISOPIN, CLOCK> SpiBus
+    for UsiSpi<H, USI, SCLKPIN, MOSIPIN, MISOPIN, CLOCK>
+where
+    USI: UsiThreeWirePins<H, SCLKPIN, MOSIPIN, MISOPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: DelayNs,
+{
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        // All transactions are blocking so there is never anything left to flush.
+        Ok(())
+    }
+
+    fn read(&mut self, read: &mut [u8]) -> Result<(), Self::Error> {
+        for b in read.iter_mut() {
+            // We send 0x00 on MOSI during "pure" reading
+            *b = self.transaction(0x00);
+        }
+
+        Ok(())
+    }
+
+    fn write(&mut self, write: &[u8]) -> Result<(), Self::Error> {
+        for b in write.iter() {
+            self.transaction(*b);
+        }
+
+        Ok(())
+    }
+
+    fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        let longest = read.len().max(write.len());
+        for i in 0..longest {
+            let r = self.transaction(*write.get(i).unwrap_or(&0x00));
+            if i < read.len() {
+                read[i] = r;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn transfer_in_place(&mut self, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        for b in buffer.iter_mut() {
+            *b = self.transaction(*b)
+        }
+
+        Ok(())
+    }
+}
+
+/// Implement traits for a USI peripheral
+#[macro_export]
+macro_rules! impl_usi {
+    (
+        hal: $HAL:ty,
+        peripheral: $USI:ty,
+        di: $dipin:ty,
+        dout: $dopin:ty,
+        usck: $usckpin:ty,
+    ) => {
+        impl $crate::usi::UsiOps<$HAL> for $USI {
+            fn raw_write_control(&mut self, value: u8) {
+                self.usicr.write(|w| unsafe { w.bits(value) });
+            }
+
+            fn raw_read_status(&self) -> u8 {
+                self.usisr.read().bits()
+            }
+
+            fn raw_write_status(&mut self, value: u8) {
+                self.usisr.write(|w| unsafe { w.bits(value) });
+            }
+
+            fn raw_read_data(&self) -> u8 {
+                self.usidr.read().bits()
+            }
+
+            fn raw_write_data(&mut self, value: u8) {
+                self.usidr.write(|w| unsafe { w.bits(value) });
+            }
+        }
+
+        impl $crate::usi::UsiTwoWirePins<$HAL, $dipin, $usckpin> for $USI {}
+
+        impl $crate::usi::UsiThreeWirePins<$HAL, $usckpin, $dopin, $dipin> for $USI {}
+    };
+}
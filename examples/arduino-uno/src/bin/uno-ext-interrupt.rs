@@ -9,6 +9,7 @@
 #![no_main]
 #![feature(abi_avr_interrupt)]
 
+use arduino_hal::exint::{Exint, Sense};
 use arduino_hal::port::{mode, Pin};
 use core::sync::atomic::{AtomicBool, Ordering};
 use either::*;
@@ -45,11 +46,10 @@ fn main() -> ! {
     let dp = arduino_hal::Peripherals::take().unwrap();
     let pins = arduino_hal::pins!(dp);
 
-    // thanks to tsemczyszyn and Rahix: https://github.com/Rahix/avr-hal/issues/240
-    // Configure INT0 for falling edge. 0x03 would be rising edge.
-    dp.EXINT.eicra.modify(|_, w| w.isc0().bits(0x02));
-    // Enable the INT0 interrupt source.
-    dp.EXINT.eimsk.modify(|_, w| w.int0().set_bit());
+    // Configure INT0 for falling edge and enable the interrupt source.
+    let mut exint = Exint::new(dp.EXINT);
+    let button = exint.configure(pins.d2.into_pull_up_input(), Sense::FallingEdge);
+    exint.enable(&button);
 
     let mut leds: [Pin<mode::Output>; 4] = [
         pins.d3.into_output().downgrade(),
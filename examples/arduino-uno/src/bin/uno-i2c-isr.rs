@@ -0,0 +1,69 @@
+/*!
+ * Read an I2C sensor in the background using the interrupt-driven I2C controller.
+ *
+ * The temperature register of an LM75 sensor is read over and over while the main loop keeps
+ * blinking the LED at a fast, steady rate.  Each time a transaction finished, the result is
+ * printed and the next one is started.
+ *
+ * Connections
+ * -----------
+ *  - `A4`: I2C SDA signal
+ *  - `A5`: I2C SCL signal
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::i2c::IsrI2cState;
+use arduino_hal::prelude::*;
+use panic_halt as _;
+
+const LM75_ADDRESS: u8 = 0x48;
+
+static I2C_STATE: IsrI2cState<arduino_hal::pac::TWI, 2> = IsrI2cState::new();
+
+#[avr_device::interrupt(atmega328p)]
+fn TWI() {
+    I2C_STATE.on_interrupt();
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+    let mut led = pins.d13.into_output();
+
+    let mut i2c = arduino_hal::I2c::new(
+        dp.TWI,
+        pins.a4.into_pull_up_input(),
+        pins.a5.into_pull_up_input(),
+        100_000,
+    )
+    .into_isr_driven(&I2C_STATE);
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    nb::block!(i2c.write_read(LM75_ADDRESS, &[0x00], 2)).unwrap_infallible();
+
+    loop {
+        // Simulate a time-critical control loop
+        led.toggle();
+        arduino_hal::delay_us(500);
+
+        let mut data = [0u8; 2];
+        match i2c.result(&mut data) {
+            Ok(()) => {
+                let temperature = i16::from_be_bytes(data) / 256;
+                ufmt::uwriteln!(&mut serial, "Temperature: {} C\r", temperature)
+                    .unwrap_infallible();
+            }
+            Err(nb::Error::WouldBlock) => continue,
+            Err(nb::Error::Other(e)) => {
+                ufmt::uwriteln!(&mut serial, "Error: {:?}\r", e).unwrap_infallible();
+            }
+        }
+        nb::block!(i2c.write_read(LM75_ADDRESS, &[0x00], 2)).unwrap_infallible();
+    }
+}
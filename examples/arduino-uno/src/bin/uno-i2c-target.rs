@@ -0,0 +1,97 @@
+/*!
+ * Act as an I2C target (slave) with a small register file.
+ *
+ * The Arduino answers on address 0x42.  The first byte of each write selects a register, all
+ * following bytes are stored into consecutive registers.  Reads return the registers starting at
+ * the selected one.  For example, from a Raspberry Pi:
+ *
+ *     i2cset -y 1 0x42 0x00 0x12 0x34 i
+ *     i2cget -y 1 0x42 0x00 w
+ *
+ * Connections
+ * -----------
+ *  - `A4`: I2C SDA signal
+ *  - `A5`: I2C SCL signal
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::i2c::{I2cTarget, TargetEvent};
+use avr_device::interrupt::Mutex;
+use core::cell::RefCell;
+use panic_halt as _;
+
+struct Registers {
+    data: [u8; 16],
+    pointer: usize,
+    pointer_set: bool,
+}
+
+static TARGET: Mutex<RefCell<Option<I2cTarget>>> = Mutex::new(RefCell::new(None));
+static REGISTERS: Mutex<RefCell<Registers>> = Mutex::new(RefCell::new(Registers {
+    data: [0; 16],
+    pointer: 0,
+    pointer_set: false,
+}));
+
+#[avr_device::interrupt(atmega328p)]
+fn TWI() {
+    avr_device::interrupt::free(|cs| {
+        let mut regs = REGISTERS.borrow(cs).borrow_mut();
+        if let Some(target) = TARGET.borrow(cs).borrow_mut().as_mut() {
+            target.handle_interrupt(|event| match event {
+                TargetEvent::WriteRequest { .. } => {
+                    regs.pointer_set = false;
+                    0
+                }
+                TargetEvent::Received(b) if !regs.pointer_set => {
+                    regs.pointer = b as usize % regs.data.len();
+                    regs.pointer_set = true;
+                    0
+                }
+                TargetEvent::Received(b) => {
+                    let p = regs.pointer;
+                    regs.data[p] = b;
+                    regs.pointer = (p + 1) % regs.data.len();
+                    0
+                }
+                TargetEvent::ReadRequest => {
+                    let p = regs.pointer;
+                    regs.pointer = (p + 1) % regs.data.len();
+                    regs.data[p]
+                }
+                TargetEvent::Stop => 0,
+            });
+        }
+    })
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    let mut target = I2cTarget::new(
+        dp.TWI,
+        pins.a4.into_pull_up_input(),
+        pins.a5.into_pull_up_input(),
+        0x42,
+    );
+    target.listen();
+    avr_device::interrupt::free(|cs| TARGET.borrow(cs).replace(Some(target)));
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    let mut led = pins.d13.into_output();
+    loop {
+        // Show the value of register 0 on the LED
+        let on = avr_device::interrupt::free(|cs| REGISTERS.borrow(cs).borrow().data[0] != 0);
+        if on {
+            led.set_high();
+        } else {
+            led.set_low();
+        }
+    }
+}
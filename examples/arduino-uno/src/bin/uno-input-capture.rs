@@ -0,0 +1,51 @@
+/*!
+ * Measure a PWM signal using the input capture unit of Timer1.
+ *
+ * Timer2 generates a PWM signal on d3 which is fed back into the input capture pin of Timer1.
+ * The measured high time, period and duty cycle are printed over the serial console.
+ *
+ * Connections
+ * -----------
+ *   - `D3` -> `D8` (ICP1)
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::input_capture::InputCapture;
+use arduino_hal::prelude::*;
+use arduino_hal::simple_pwm::*;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let timer2 = Timer2Pwm::new(dp.TC2, Prescaler::Prescale64);
+    let mut pwm = pins.d3.into_output().into_pwm(&timer2);
+    pwm.enable();
+
+    // With a prescaler of 8, one timer tick is 0.5 µs and periods up to ~32 ms can be measured.
+    let mut icp = InputCapture::new(dp.TC1, pins.d8.into_floating_input(), Prescaler::Prescale8);
+    icp.set_noise_canceler(true);
+
+    loop {
+        for duty in [32, 64, 128, 192, 224] {
+            pwm.set_duty(duty);
+            arduino_hal::delay_ms(100);
+
+            match icp.duty_cycle_us() {
+                Ok(measured) => ufmt::uwriteln!(
+                    &mut serial,
+                    "high: {} us, period: {} us, duty: {}%\r",
+                    measured.high_us,
+                    measured.period_us,
+                    measured.percent()
+                )
+                .unwrap_infallible(),
+                Err(_) => ufmt::uwriteln!(&mut serial, "No signal on d8!\r").unwrap_infallible(),
+            }
+        }
+    }
+}
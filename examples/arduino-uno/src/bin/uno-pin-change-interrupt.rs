@@ -11,15 +11,25 @@
 
 use panic_halt as _;
 
+use arduino_hal::port::D2;
+use avr_device::interrupt::Mutex;
+use core::cell::RefCell;
 use core::sync::atomic::{AtomicBool, Ordering};
 
 static PIN_CHANGED: AtomicBool = AtomicBool::new(false);
+static PCINT: Mutex<RefCell<Option<arduino_hal::Pcint>>> = Mutex::new(RefCell::new(None));
 
-//This function is called on change of pin 2
+//This function is called on change of any enabled pin on port D
 #[avr_device::interrupt(atmega328p)]
 #[allow(non_snake_case)]
 fn PCINT2() {
-    PIN_CHANGED.store(true, Ordering::SeqCst);
+    avr_device::interrupt::free(|cs| {
+        if let Some(pcint) = PCINT.borrow(cs).borrow_mut().as_mut() {
+            if pcint.changes(2).contains::<D2>() {
+                PIN_CHANGED.store(true, Ordering::SeqCst);
+            }
+        }
+    })
 }
 
 fn rotate(flag: &AtomicBool) -> bool {
@@ -43,16 +53,15 @@ fn main() -> ! {
     let mut step_pin = pins.d5.into_output();
 
     //Rotary encoder attached on these pins
-    let rotary_pins = [
-        pins.d2.into_floating_input().downgrade(), //CLK
-        pins.d3.into_floating_input().downgrade(), //DT
-    ];
-
-    // Enable the PCINT2 pin change interrupt
-    dp.EXINT.pcicr.write(|w| unsafe { w.bits(0b100) });
+    let clk = pins.d2.into_floating_input();
+    let dt = pins.d3.into_floating_input();
 
     // Enable pin change interrupts on PCINT18 which is pin PD2 (= d2)
-    dp.EXINT.pcmsk2.write(|w| w.bits(0b100));
+    let mut pcint = arduino_hal::Pcint::new(dp.EXINT);
+    pcint.enable(&clk);
+    avr_device::interrupt::free(|cs| PCINT.borrow(cs).replace(Some(pcint)));
+
+    let rotary_pins = [clk.downgrade(), dt.downgrade()];
 
     //From this point on an interrupt can happen
     unsafe { avr_device::interrupt::enable() };
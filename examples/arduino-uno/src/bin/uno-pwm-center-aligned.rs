@@ -0,0 +1,35 @@
+/*!
+ * Generate center-aligned 20 kHz PWM for a motor driver on pins d9 and d10.
+ *
+ * Timer1 runs in phase and frequency correct mode, so both pulses are centered within each
+ * period.  d10 is inverted, which gives a complementary pair of signals for the two inputs of a
+ * motor driver.  The speed ramps up and down slowly.
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::simple_pwm::*;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    // At 16 MHz, 20 kHz gives TOP = 400 in the dual-slope modes.
+    let timer1 = Pwm16::with_mode(dp.TC1, 20_000, Pwm16Mode::PhaseFrequencyCorrect).unwrap();
+    let max = timer1.max_duty();
+
+    let mut in1 = pins.d9.into_output().into_pwm(&timer1);
+    let mut in2 = pins.d10.into_output().into_pwm(&timer1);
+    in1.enable();
+    in2.enable_inverted();
+
+    loop {
+        for duty in (0..=max).chain((0..max).rev()) {
+            in1.set_duty(duty);
+            in2.set_duty(duty);
+            arduino_hal::delay_ms(5);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+/*!
+ * Fade a LED on pin d9 using 16-bit PWM at 1 kHz.
+ *
+ * With `ICR1` as `TOP`, the PWM frequency can be chosen freely while the duty cycle keeps a much
+ * finer resolution than the 256 steps of the 8-bit PWM.  This allows smooth fading even at very
+ * low brightness.
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::simple_pwm::*;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    // At 16 MHz, 1 kHz gives TOP = 15999, i.e. 16000 brightness steps.
+    let timer1 = Pwm16::new(dp.TC1, 1_000).unwrap();
+    let max = timer1.max_duty();
+
+    let mut pwm_led = pins.d9.into_output().into_pwm(&timer1);
+    pwm_led.enable();
+
+    loop {
+        // Quadratic ramp for a perceptually even fade
+        for x in (0..=255u32).chain((0..=254).rev()) {
+            pwm_led.set_duty((x * x * max as u32 / (255 * 255)) as u16);
+            arduino_hal::delay_ms(10);
+        }
+    }
+}
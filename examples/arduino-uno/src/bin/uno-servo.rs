@@ -0,0 +1,39 @@
+/*!
+ * Sweep two SG90 compatible servos back and forth using the servo driver.
+ *
+ * Timer TC1 generates the 50 Hz servo signal on both of its output compare pins.  As the limits
+ * are not precisely defined, the pulse width is allowed to go a bit beyond the datasheet's 1 ms to
+ * 2 ms range - you can figure out where exactly the limits are for your model by experimentation.
+ *
+ * Connections
+ * -----------
+ *  - `D9`: First servo's PWM signal
+ *  - `D10`: Second servo's PWM signal
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::servo::Servo;
+use arduino_hal::simple_pwm::Pwm16;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    let timer1 = Pwm16::for_servos(dp.TC1).unwrap();
+
+    let mut servo1 = Servo::new(pins.d9.into_output(), &timer1);
+    let mut servo2 = Servo::new(pins.d10.into_output(), &timer1);
+    servo1.set_limits(500, 2500);
+    servo2.set_limits(500, 2500);
+
+    loop {
+        for angle in (0..=180).chain((0..180).rev()) {
+            servo1.set_angle(angle);
+            servo2.set_angle(180 - angle);
+            arduino_hal::delay_ms(15);
+        }
+    }
+}
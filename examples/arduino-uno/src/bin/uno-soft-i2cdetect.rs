@@ -0,0 +1,46 @@
+/*!
+ * Detect all devices connected on a software (bit-banged) I2C bus.
+ *
+ * This is the same as `uno-i2cdetect`, but the bus is driven from two arbitrary GPIO pins instead
+ * of the TWI peripheral.  The lines are used in open-drain mode, so external pull-up resistors
+ * (e.g. 4.7kΩ to 5V) are required on both of them.
+ *
+ * ATTENTION: Randomly reading from and writing to devices can lead to unexpected results.  Some
+ * devices do not cope well with this.  Use with care!
+ *
+ * Connections
+ * -----------
+ *  - `D2`: I2C SDA signal
+ *  - `D3`: I2C SCL signal
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::prelude::*;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let mut i2c = arduino_hal::i2c::SoftI2c::new(
+        pins.d2.into_opendrain_high(),
+        pins.d3.into_opendrain_high(),
+        50000,
+    );
+    // Don't hang forever if a device stretches the clock indefinitely
+    i2c.set_timeout(Some(10_000));
+
+    ufmt::uwriteln!(&mut serial, "Write direction test:\r").unwrap_infallible();
+    i2c.i2cdetect(&mut serial, arduino_hal::i2c::Direction::Write)
+        .unwrap_infallible();
+    ufmt::uwriteln!(&mut serial, "\r\nRead direction test:\r").unwrap_infallible();
+    i2c.i2cdetect(&mut serial, arduino_hal::i2c::Direction::Read)
+        .unwrap_infallible();
+
+    loop {
+        arduino_hal::delay_ms(1000);
+    }
+}
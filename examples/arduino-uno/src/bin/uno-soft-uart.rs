@@ -0,0 +1,65 @@
+/*!
+ * Forward data from a GPS module on a second, bit-banged serial port.
+ *
+ * The GPS module is connected to a `SoftUart` which receives in the background using the
+ * pin-change interrupt of its RX pin.  All received NMEA sentences are forwarded to the hardware
+ * serial port (and thus the USB connection).  Data sent from the PC is passed on to the GPS
+ * module.
+ *
+ * Connections
+ * -----------
+ *  - `D4`: SoftUart RX (connect to TX of the GPS module)
+ *  - `D5`: SoftUart TX (connect to RX of the GPS module)
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::hal::port::PD4;
+use arduino_hal::prelude::*;
+use arduino_hal::usart::SoftUartBuffers;
+use panic_halt as _;
+
+static GPS_BUFFERS: SoftUartBuffers<PD4, 128> = SoftUartBuffers::new();
+
+#[avr_device::interrupt(atmega328p)]
+fn PCINT2() {
+    GPS_BUFFERS.on_start_bit();
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let mut gps = arduino_hal::usart::SoftUart::new(
+        pins.d4.into_pull_up_input(),
+        pins.d5.into_output(),
+        9600,
+    )
+    .into_buffered(&GPS_BUFFERS);
+
+    // Enable the PCINT2 pin change interrupt
+    dp.EXINT.pcicr.write(|w| unsafe { w.bits(0b100) });
+
+    // Enable pin change interrupts on PCINT20 which is pin PD4 (= d4)
+    dp.EXINT.pcmsk2.write(|w| w.bits(0b10000));
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    ufmt::uwriteln!(&mut serial, "Forwarding GPS data...\r").unwrap_infallible();
+
+    loop {
+        if gps.available() > 0 {
+            match gps.read_byte() {
+                Ok(b) => serial.write_byte(b),
+                Err(e) => ufmt::uwriteln!(&mut serial, "\r\nError: {:?}\r", e).unwrap_infallible(),
+            }
+        }
+        if let Ok(b) = serial.read() {
+            gps.write_byte(b);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+/*!
+ * Stream a buffer over SPI in the background using the interrupt-driven SPI controller.
+ *
+ * A 64 byte block is sent while the main loop keeps counting.  Because MOSI is looped back to
+ * MISO, the received block must be identical to the sent one.  The progress of the transfer is
+ * printed while it is ongoing.
+ *
+ * Connections:
+ *  - `D11` connected directly to `D12` (loop MOSI to MISO)
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::prelude::*;
+use arduino_hal::spi::{self, IsrSpiState};
+use embedded_hal::digital::OutputPin;
+use panic_halt as _;
+
+static SPI_STATE: IsrSpiState<arduino_hal::pac::SPI, 64> = IsrSpiState::new();
+
+#[avr_device::interrupt(atmega328p)]
+fn SPI_STC() {
+    SPI_STATE.on_transfer_complete();
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let (spi, mut cs) = arduino_hal::Spi::new(
+        dp.SPI,
+        pins.d13.into_output(),
+        pins.d11.into_output(),
+        pins.d12.into_pull_up_input(),
+        pins.d10.into_output(),
+        spi::Settings {
+            clock: spi::SerialClockRate::OscfOver128,
+            ..Default::default()
+        },
+    );
+    let mut spi = spi.into_isr_driven(&SPI_STATE);
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    let mut block = [0u8; 64];
+    for (i, b) in block.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+
+    loop {
+        cs.set_low().unwrap_infallible();
+        nb::block!(spi.transfer(&block)).unwrap_infallible();
+
+        let mut counter = 0u32;
+        while !spi.is_done() {
+            counter += 1;
+            if counter % 100 == 0 {
+                ufmt::uwriteln!(&mut serial, "progress: {}\r", spi.progress()).unwrap_infallible();
+            }
+        }
+        cs.set_high().unwrap_infallible();
+
+        let mut received = [0u8; 64];
+        let len = nb::block!(spi.result(&mut received)).unwrap_infallible();
+        let ok = received[..len] == block[..];
+        ufmt::uwriteln!(&mut serial, "done, {} bytes, match: {}\r", len, ok).unwrap_infallible();
+
+        arduino_hal::delay_ms(1000);
+    }
+}
@@ -0,0 +1,62 @@
+/*!
+ * Act as an SPI peripheral (slave) device for another microcontroller.
+ *
+ * The received bytes are collected by the SPI_STC interrupt.  While the controller clocks in its
+ * command, a preloaded status message is shifted out on MISO.  After each transaction, the
+ * received command is echoed on the serial console.
+ *
+ * Connections
+ * -----------
+ *  - `D10`: SS (chip select, driven by the controller)
+ *  - `D11`: MOSI (data from the controller)
+ *  - `D12`: MISO (data to the controller)
+ *  - `D13`: SCLK (clock, driven by the controller)
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::prelude::*;
+use arduino_hal::spi::{self, SpiPeripheralBuffers};
+use panic_halt as _;
+
+static SPI_BUFFERS: SpiPeripheralBuffers<arduino_hal::pac::SPI, 16> = SpiPeripheralBuffers::new();
+
+#[avr_device::interrupt(atmega328p)]
+fn SPI_STC() {
+    SPI_BUFFERS.on_transfer_complete();
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let mut spi = spi::SpiPeripheral::new(
+        dp.SPI,
+        pins.d13.into_floating_input(),
+        pins.d11.into_floating_input(),
+        pins.d12.into_output(),
+        pins.d10.into_pull_up_input(),
+        spi::Settings::default(),
+    )
+    .into_buffered(&SPI_BUFFERS);
+    spi.set_fill(0xff);
+    spi.set_response(b"ready");
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    loop {
+        // Wait for the end of a transaction
+        while !spi.is_selected() {}
+        while spi.is_selected() {}
+
+        let mut command = [0u8; 16];
+        let len = spi.take_received(&mut command);
+        ufmt::uwriteln!(&mut serial, "Received: {:?}\r", &command[..len]).unwrap_infallible();
+
+        spi.set_response(b"ready");
+    }
+}
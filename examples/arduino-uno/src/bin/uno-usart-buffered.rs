@@ -0,0 +1,49 @@
+/*!
+ * Interrupt-driven serial console with receive and transmit buffers.
+ *
+ * Received bytes are collected by the USART_RX interrupt while the main loop is busy, so no
+ * data is lost even though the loop only checks for input every 500 ms.
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::prelude::*;
+use arduino_hal::usart::UsartBuffers;
+use panic_halt as _;
+
+static SERIAL_BUFFERS: UsartBuffers<arduino_hal::pac::USART0, 64, 64> = UsartBuffers::new();
+
+#[avr_device::interrupt(atmega328p)]
+fn USART_RX() {
+    SERIAL_BUFFERS.on_rx_complete();
+}
+
+#[avr_device::interrupt(atmega328p)]
+fn USART_UDRE() {
+    SERIAL_BUFFERS.on_data_register_empty();
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 115200).into_buffered(&SERIAL_BUFFERS);
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    ufmt::uwriteln!(&mut serial, "Hello from Arduino!\r").unwrap_infallible();
+
+    loop {
+        // Simulate a busy main loop
+        arduino_hal::delay_ms(500);
+
+        while serial.available() > 0 {
+            match serial.read_byte() {
+                Ok(b) => ufmt::uwriteln!(&mut serial, "Got {}!\r", b).unwrap_infallible(),
+                Err(e) => ufmt::uwriteln!(&mut serial, "Error: {:?}\r", e).unwrap_infallible(),
+            }
+        }
+    }
+}
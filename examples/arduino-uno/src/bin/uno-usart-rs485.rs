@@ -0,0 +1,56 @@
+/*!
+ * RS-485 echo node using an interrupt-driven USART.
+ *
+ * Connect a MAX485 (or similar) transceiver to the serial pins D0 (RO) and D1 (DI) and tie its
+ * DE and /RE pins together to D2.  D2 is driven high while data is sent and released from the
+ * USART_TX interrupt as soon as the last stop bit has left, so the bus is free for the answer of
+ * the next node.
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::hal::port::PD2;
+use arduino_hal::port::{mode::Output, Pin};
+use arduino_hal::prelude::*;
+use arduino_hal::usart::UsartBuffers;
+use panic_halt as _;
+
+type DriverEnable = Pin<Output, PD2>;
+
+static SERIAL_BUFFERS: UsartBuffers<arduino_hal::pac::USART0, 32, 32, DriverEnable> =
+    UsartBuffers::new();
+
+#[avr_device::interrupt(atmega328p)]
+fn USART_RX() {
+    SERIAL_BUFFERS.on_rx_complete();
+}
+
+#[avr_device::interrupt(atmega328p)]
+fn USART_UDRE() {
+    SERIAL_BUFFERS.on_data_register_empty();
+}
+
+#[avr_device::interrupt(atmega328p)]
+fn USART_TX() {
+    SERIAL_BUFFERS.on_tx_complete();
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600)
+        .into_rs485(pins.d2.into_output())
+        .into_buffered(&SERIAL_BUFFERS);
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    loop {
+        match serial.read_byte() {
+            Ok(b) => ufmt::uwriteln!(&mut serial, "Got {}!\r", b).unwrap_infallible(),
+            Err(e) => ufmt::uwriteln!(&mut serial, "Error: {:?}\r", e).unwrap_infallible(),
+        }
+    }
+}
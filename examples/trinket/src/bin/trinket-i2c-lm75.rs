@@ -0,0 +1,42 @@
+/*!
+ * Read the temperature from an LM75 sensor using the USI-based I2C controller.
+ *
+ * The onboard LED is turned on while the temperature is above 25 °C.
+ *
+ * Connections
+ * -----------
+ *  - `D0`: I2C SDA signal (with external pull-up)
+ *  - `D2`: I2C SCL signal (with external pull-up)
+ */
+#![no_std]
+#![no_main]
+
+use embedded_hal::i2c::I2c;
+use panic_halt as _;
+
+const LM75_ADDRESS: u8 = 0x48;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    let mut i2c = arduino_hal::I2c::with_external_pullup(
+        dp.USI,
+        pins.d0.into_floating_input(),
+        pins.d2.into_floating_input(),
+        50_000,
+    );
+
+    // Digital pin 1 is also connected to an onboard LED marked "L"
+    let mut led = pins.d1.into_output();
+
+    loop {
+        let mut data = [0u8; 2];
+        match i2c.write_read(LM75_ADDRESS, &[0x00], &mut data) {
+            Ok(()) if i16::from_be_bytes(data) / 256 > 25 => led.set_high(),
+            _ => led.set_low(),
+        }
+        arduino_hal::delay_ms(500);
+    }
+}
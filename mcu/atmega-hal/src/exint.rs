@@ -0,0 +1,98 @@
+//! External interrupts (`INTn`)
+//!
+//! # Example
+//!
+//! Complete example source code can be found in the repository:
+//! [`uno-ext-interrupt.rs`](https://github.com/Rahix/avr-hal/blob/main/examples/arduino-uno/src/bin/uno-ext-interrupt.rs)
+//!
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let pins = atmega_hal::pins!(dp);
+//!
+//! let mut exint = Exint::new(dp.EXINT);
+//! let button = exint.configure(pins.pd2.into_pull_up_input(), Sense::FallingEdge);
+//! exint.enable(&button);
+//! ```
+//!
+//! The pin change interrupts are part of the same `EXINT` peripheral.  To use both, split the
+//! peripheral into the two drivers with [`split`]:
+//!
+//! ```
+//! let (mut exint, mut pcint) = atmega_hal::exint::split(dp.EXINT);
+//! ```
+
+#[allow(unused_imports)]
+use crate::port;
+pub use avr_hal_generic::exint::{ExintOps, ExintPin, IntPin, Sense};
+
+pub type Exint = avr_hal_generic::exint::Exint<crate::Atmega, crate::pac::EXINT>;
+
+/// Split `EXINT` into the external interrupt and the pin change interrupt drivers.
+///
+/// Releasing both drivers afterwards yields two `EXINT` instances.  Only one of them should be
+/// used again.
+#[cfg(not(feature = "atmega128a"))]
+pub fn split(exint: crate::pac::EXINT) -> (Exint, crate::pcint::Pcint) {
+    // SAFETY: `Exint` only accesses EICRn, EIMSK and EIFR while `Pcint` only accesses PCICR,
+    // PCIFR and the PCMSKn registers, so the two drivers never touch the same register.
+    let pcint = unsafe { crate::pac::Peripherals::steal() }.EXINT;
+    (Exint::new(exint), crate::pcint::Pcint::new(pcint))
+}
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_exint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    pins: {
+        port::PD2: 0,
+        port::PD3: 1,
+    },
+}
+
+#[cfg(any(feature = "atmega164pa", feature = "atmega1284p"))]
+avr_hal_generic::impl_exint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    pins: {
+        port::PD2: 0,
+        port::PD3: 1,
+        port::PB2: 2,
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_exint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    pins: {
+        port::PD0: 0,
+        port::PD1: 1,
+        port::PD2: 2,
+        port::PD3: 3,
+        port::PE6: 6,
+    },
+    eicrb: eicrb,
+}
+
+#[cfg(any(feature = "atmega128a", feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_exint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    pins: {
+        port::PD0: 0,
+        port::PD1: 1,
+        port::PD2: 2,
+        port::PD3: 3,
+        port::PE4: 4,
+        port::PE5: 5,
+        port::PE6: 6,
+        port::PE7: 7,
+    },
+    eicrb: eicrb,
+}
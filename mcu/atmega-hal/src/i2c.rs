@@ -22,6 +22,7 @@
 #[allow(unused_imports)]
 use crate::port;
 pub use avr_hal_generic::i2c::*;
+pub use avr_hal_generic::soft_i2c::SoftI2c;
 
 #[cfg(any(
     feature = "atmega128a",
@@ -42,11 +43,40 @@ pub type I2c<CLOCK> = avr_hal_generic::i2c::I2c<
     feature = "atmega2560",
     feature = "atmega32u4"
 ))]
+pub type IsrI2c<CLOCK, const N: usize> = avr_hal_generic::i2c::IsrI2c<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PD1>,
+    port::Pin<port::mode::Input, port::PD0>,
+    CLOCK,
+    N,
+>;
+#[cfg(any(
+    feature = "atmega128a",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega32u4"
+))]
+pub type I2cTarget = avr_hal_generic::i2c::I2cTarget<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PD1>,
+    port::Pin<port::mode::Input, port::PD0>,
+>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560", feature = "atmega32u4"))]
+avr_hal_generic::impl_i2c_twi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TWI,
+    sda: port::PD1,
+    scl: port::PD0,
+}
+#[cfg(feature = "atmega128a")]
 avr_hal_generic::impl_i2c_twi! {
     hal: crate::Atmega,
     peripheral: crate::pac::TWI,
     sda: port::PD1,
     scl: port::PD0,
+    address_mask: false,
 }
 
 #[cfg(any(feature = "atmega164pa"))]
@@ -58,6 +88,22 @@ pub type I2c<CLOCK> = avr_hal_generic::i2c::I2c<
     CLOCK,
 >;
 #[cfg(any(feature = "atmega164pa"))]
+pub type IsrI2c<CLOCK, const N: usize> = avr_hal_generic::i2c::IsrI2c<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PC1>,
+    port::Pin<port::mode::Input, port::PC0>,
+    CLOCK,
+    N,
+>;
+#[cfg(any(feature = "atmega164pa"))]
+pub type I2cTarget = avr_hal_generic::i2c::I2cTarget<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PC1>,
+    port::Pin<port::mode::Input, port::PC0>,
+>;
+#[cfg(any(feature = "atmega164pa"))]
 avr_hal_generic::impl_i2c_twi! {
     hal: crate::Atmega,
     peripheral: crate::pac::TWI,
@@ -86,11 +132,46 @@ pub type I2c<CLOCK> = avr_hal_generic::i2c::I2c<
     feature = "atmega8",
     feature = "atmega88p"
 ))]
+pub type IsrI2c<CLOCK, const N: usize> = avr_hal_generic::i2c::IsrI2c<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PC4>,
+    port::Pin<port::mode::Input, port::PC5>,
+    CLOCK,
+    N,
+>;
+#[cfg(any(
+    feature = "atmega328p",
+    feature = "atmega168",
+    feature = "atmega48p",
+    feature = "atmega8",
+    feature = "atmega88p"
+))]
+pub type I2cTarget = avr_hal_generic::i2c::I2cTarget<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PC4>,
+    port::Pin<port::mode::Input, port::PC5>,
+>;
+#[cfg(any(
+    feature = "atmega328p",
+    feature = "atmega168",
+    feature = "atmega48p",
+    feature = "atmega88p"
+))]
+avr_hal_generic::impl_i2c_twi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TWI,
+    sda: port::PC4,
+    scl: port::PC5,
+}
+#[cfg(feature = "atmega8")]
 avr_hal_generic::impl_i2c_twi! {
     hal: crate::Atmega,
     peripheral: crate::pac::TWI,
     sda: port::PC4,
     scl: port::PC5,
+    address_mask: false,
 }
 
 #[cfg(any(feature = "atmega328pb"))]
@@ -102,6 +183,22 @@ pub type I2c0<CLOCK> = avr_hal_generic::i2c::I2c<
     CLOCK,
 >;
 #[cfg(any(feature = "atmega328pb"))]
+pub type IsrI2c0<CLOCK, const N: usize> = avr_hal_generic::i2c::IsrI2c<
+    crate::Atmega,
+    crate::pac::TWI0,
+    port::Pin<port::mode::Input, port::PC4>,
+    port::Pin<port::mode::Input, port::PC5>,
+    CLOCK,
+    N,
+>;
+#[cfg(any(feature = "atmega328pb"))]
+pub type I2cTarget0 = avr_hal_generic::i2c::I2cTarget<
+    crate::Atmega,
+    crate::pac::TWI0,
+    port::Pin<port::mode::Input, port::PC4>,
+    port::Pin<port::mode::Input, port::PC5>,
+>;
+#[cfg(any(feature = "atmega328pb"))]
 avr_hal_generic::impl_i2c_twi! {
     hal: crate::Atmega,
     peripheral: crate::pac::TWI0,
@@ -117,6 +214,22 @@ pub type I2c1<CLOCK> = avr_hal_generic::i2c::I2c<
     CLOCK,
 >;
 #[cfg(any(feature = "atmega328pb"))]
+pub type IsrI2c1<CLOCK, const N: usize> = avr_hal_generic::i2c::IsrI2c<
+    crate::Atmega,
+    crate::pac::TWI1,
+    port::Pin<port::mode::Input, port::PE0>,
+    port::Pin<port::mode::Input, port::PE1>,
+    CLOCK,
+    N,
+>;
+#[cfg(any(feature = "atmega328pb"))]
+pub type I2cTarget1 = avr_hal_generic::i2c::I2cTarget<
+    crate::Atmega,
+    crate::pac::TWI1,
+    port::Pin<port::mode::Input, port::PE0>,
+    port::Pin<port::mode::Input, port::PE1>,
+>;
+#[cfg(any(feature = "atmega328pb"))]
 avr_hal_generic::impl_i2c_twi! {
     hal: crate::Atmega,
     peripheral: crate::pac::TWI1,
@@ -133,9 +246,33 @@ pub type I2c<CLOCK> = avr_hal_generic::i2c::I2c<
     CLOCK,
 >;
 #[cfg(any(feature = "atmega1284p", feature = "atmega32a"))]
+pub type IsrI2c<CLOCK, const N: usize> = avr_hal_generic::i2c::IsrI2c<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PC1>,
+    port::Pin<port::mode::Input, port::PC0>,
+    CLOCK,
+    N,
+>;
+#[cfg(any(feature = "atmega1284p", feature = "atmega32a"))]
+pub type I2cTarget = avr_hal_generic::i2c::I2cTarget<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PC1>,
+    port::Pin<port::mode::Input, port::PC0>,
+>;
+#[cfg(any(feature = "atmega1284p"))]
+avr_hal_generic::impl_i2c_twi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TWI,
+    sda: port::PC1,
+    scl: port::PC0,
+}
+#[cfg(feature = "atmega32a")]
 avr_hal_generic::impl_i2c_twi! {
     hal: crate::Atmega,
     peripheral: crate::pac::TWI,
     sda: port::PC1,
     scl: port::PC0,
+    address_mask: false,
 }
@@ -0,0 +1,208 @@
+//! Input Capture Unit of 16-bit timers
+//!
+//! # Example
+//!
+//! Complete example source code can be found in the repository:
+//! [`uno-input-capture.rs`](https://github.com/Rahix/avr-hal/blob/main/examples/arduino-uno/src/bin/uno-input-capture.rs)
+//!
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let pins = atmega_hal::pins!(dp);
+//!
+//! let mut icp = InputCapture1::<crate::CoreClock>::new(
+//!     dp.TC1,
+//!     pins.pb0.into_floating_input(),
+//!     Prescaler::Prescale8,
+//! );
+//!
+//! let width = icp.pulse_width_us(Edge::Rising).unwrap();
+//! ```
+//!
+//! *Note*: The input capture units of the ATmega8, ATmega32A and ATmega128A are not supported by
+//! this module.
+
+#[allow(unused_imports)]
+use crate::port;
+pub use avr_hal_generic::input_capture::{
+    DutyCycle, Edge, Error, IcpPin, InputCaptureOps, Prescaler,
+};
+
+pub type InputCapture<TC, PIN, CLOCK> =
+    avr_hal_generic::input_capture::InputCapture<crate::Atmega, TC, PIN, CLOCK>;
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+pub type InputCapture1<CLOCK> = InputCapture<crate::pac::TC1, port::PB0, CLOCK>;
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: port::PB0,
+    tccra: tccr1a,
+    tccrb: tccr1b,
+    tcnt: tcnt1,
+    icr: icr1,
+    timsk: timsk1,
+    tifr: tifr1,
+}
+
+#[cfg(feature = "atmega328pb")]
+pub type InputCapture3<CLOCK> = InputCapture<crate::pac::TC3, port::PE2, CLOCK>;
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: port::PE2,
+    tccra: tccr3a,
+    tccrb: tccr3b,
+    tcnt: tcnt3,
+    icr: icr3,
+    timsk: timsk3,
+    tifr: tifr3,
+}
+
+#[cfg(feature = "atmega328pb")]
+pub type InputCapture4<CLOCK> = InputCapture<crate::pac::TC4, port::PE0, CLOCK>;
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    pin: port::PE0,
+    tccra: tccr4a,
+    tccrb: tccr4b,
+    tcnt: tcnt4,
+    icr: icr4,
+    timsk: timsk4,
+    tifr: tifr4,
+}
+
+#[cfg(feature = "atmega32u4")]
+pub type InputCapture1<CLOCK> = InputCapture<crate::pac::TC1, port::PD4, CLOCK>;
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: port::PD4,
+    tccra: tccr1a,
+    tccrb: tccr1b,
+    tcnt: tcnt1,
+    icr: icr1,
+    timsk: timsk1,
+    tifr: tifr1,
+}
+
+#[cfg(feature = "atmega32u4")]
+pub type InputCapture3<CLOCK> = InputCapture<crate::pac::TC3, port::PC7, CLOCK>;
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: port::PC7,
+    tccra: tccr3a,
+    tccrb: tccr3b,
+    tcnt: tcnt3,
+    icr: icr3,
+    timsk: timsk3,
+    tifr: tifr3,
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type InputCapture1<CLOCK> = InputCapture<crate::pac::TC1, port::PD4, CLOCK>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: port::PD4,
+    tccra: tccr1a,
+    tccrb: tccr1b,
+    tcnt: tcnt1,
+    icr: icr1,
+    timsk: timsk1,
+    tifr: tifr1,
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type InputCapture3<CLOCK> = InputCapture<crate::pac::TC3, port::PE7, CLOCK>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: port::PE7,
+    tccra: tccr3a,
+    tccrb: tccr3b,
+    tcnt: tcnt3,
+    icr: icr3,
+    timsk: timsk3,
+    tifr: tifr3,
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type InputCapture4<CLOCK> = InputCapture<crate::pac::TC4, port::PL0, CLOCK>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    pin: port::PL0,
+    tccra: tccr4a,
+    tccrb: tccr4b,
+    tcnt: tcnt4,
+    icr: icr4,
+    timsk: timsk4,
+    tifr: tifr4,
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type InputCapture5<CLOCK> = InputCapture<crate::pac::TC5, port::PL1, CLOCK>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC5,
+    pin: port::PL1,
+    tccra: tccr5a,
+    tccrb: tccr5b,
+    tcnt: tcnt5,
+    icr: icr5,
+    timsk: timsk5,
+    tifr: tifr5,
+}
+
+#[cfg(any(feature = "atmega164pa", feature = "atmega1284p"))]
+pub type InputCapture1<CLOCK> = InputCapture<crate::pac::TC1, port::PD6, CLOCK>;
+#[cfg(any(feature = "atmega164pa", feature = "atmega1284p"))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: port::PD6,
+    tccra: tccr1a,
+    tccrb: tccr1b,
+    tcnt: tcnt1,
+    icr: icr1,
+    timsk: timsk1,
+    tifr: tifr1,
+}
+
+#[cfg(feature = "atmega1284p")]
+pub type InputCapture3<CLOCK> = InputCapture<crate::pac::TC3, port::PB5, CLOCK>;
+#[cfg(feature = "atmega1284p")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: port::PB5,
+    tccra: tccr3a,
+    tccrb: tccr3b,
+    tcnt: tcnt3,
+    icr: icr3,
+    timsk: timsk3,
+    tifr: tifr3,
+}
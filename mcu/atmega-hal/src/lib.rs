@@ -118,11 +118,46 @@ pub mod adc;
 #[cfg(feature = "device-selected")]
 pub use adc::Adc;
 
+// ATmega8 and ATmega32A use a different register layout for the external interrupts
+#[cfg(all(
+    feature = "device-selected",
+    not(any(feature = "atmega8", feature = "atmega32a"))
+))]
+pub mod exint;
+#[cfg(all(
+    feature = "device-selected",
+    not(any(feature = "atmega8", feature = "atmega32a"))
+))]
+pub use exint::Exint;
+
+// ATmega8, ATmega32A and ATmega128A do not have pin change interrupts
+#[cfg(all(
+    feature = "device-selected",
+    not(any(feature = "atmega8", feature = "atmega32a", feature = "atmega128a"))
+))]
+pub mod pcint;
+#[cfg(all(
+    feature = "device-selected",
+    not(any(feature = "atmega8", feature = "atmega32a", feature = "atmega128a"))
+))]
+pub use pcint::Pcint;
+
 #[cfg(feature = "device-selected")]
 pub mod i2c;
 #[cfg(feature = "device-selected")]
 pub use i2c::I2c;
 
+#[cfg(all(
+    feature = "device-selected",
+    not(any(feature = "atmega8", feature = "atmega32a", feature = "atmega128a"))
+))]
+pub mod input_capture;
+#[cfg(all(
+    feature = "device-selected",
+    not(any(feature = "atmega8", feature = "atmega32a", feature = "atmega128a"))
+))]
+pub use input_capture::InputCapture;
+
 #[cfg(feature = "device-selected")]
 pub mod spi;
 #[cfg(feature = "device-selected")]
@@ -133,9 +168,15 @@ pub mod port;
 #[cfg(feature = "device-selected")]
 pub use port::Pins;
 
+#[cfg(feature = "device-selected")]
+pub mod servo;
+
 #[cfg(feature = "device-selected")]
 pub mod simple_pwm;
 
+#[cfg(feature = "device-selected")]
+pub mod timer;
+
 #[cfg(feature = "device-selected")]
 pub mod usart;
 #[cfg(feature = "device-selected")]
@@ -0,0 +1,303 @@
+//! Pin change interrupts (`PCINTn`)
+//!
+//! # Example
+//!
+//! Complete example source code can be found in the repository:
+//! [`uno-pin-change-interrupt.rs`](https://github.com/Rahix/avr-hal/blob/main/examples/arduino-uno/src/bin/uno-pin-change-interrupt.rs)
+//!
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let pins = atmega_hal::pins!(dp);
+//!
+//! let mut pcint = Pcint::new(dp.EXINT);
+//! let button = pins.pd2.into_pull_up_input();
+//! pcint.enable(&button);
+//!
+//! // In the PCINT2 handler:
+//! let changes = pcint.changes(2);
+//! if changes.contains::<port::PD2>() {
+//!     // ...
+//! }
+//! ```
+
+#[allow(unused_imports)]
+use crate::port;
+pub use avr_hal_generic::pcint::{PcintOps, PcintPin};
+
+pub type Pcint = avr_hal_generic::pcint::Pcint<crate::Atmega, crate::pac::EXINT>;
+pub type Changes = avr_hal_generic::pcint::Changes<crate::Atmega, crate::pac::EXINT>;
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p"
+))]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    pcicr: pcicr,
+    pcifr: pcifr,
+    groups: {
+        0: {
+            pcie: 0,
+            pcmsk: pcmsk0,
+            inputs: (*crate::pac::PORTB::ptr()).pinb.read().bits(),
+            pins: {
+                port::PB0: 0,
+                port::PB1: 1,
+                port::PB2: 2,
+                port::PB3: 3,
+                port::PB4: 4,
+                port::PB5: 5,
+                port::PB6: 6,
+                port::PB7: 7,
+            },
+        },
+        1: {
+            pcie: 1,
+            pcmsk: pcmsk1,
+            inputs: (*crate::pac::PORTC::ptr()).pinc.read().bits(),
+            pins: {
+                port::PC0: 0,
+                port::PC1: 1,
+                port::PC2: 2,
+                port::PC3: 3,
+                port::PC4: 4,
+                port::PC5: 5,
+                port::PC6: 6,
+            },
+        },
+        2: {
+            pcie: 2,
+            pcmsk: pcmsk2,
+            inputs: (*crate::pac::PORTD::ptr()).pind.read().bits(),
+            pins: {
+                port::PD0: 0,
+                port::PD1: 1,
+                port::PD2: 2,
+                port::PD3: 3,
+                port::PD4: 4,
+                port::PD5: 5,
+                port::PD6: 6,
+                port::PD7: 7,
+            },
+        },
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    pcicr: pcicr,
+    pcifr: pcifr,
+    groups: {
+        0: {
+            pcie: 0,
+            pcmsk: pcmsk0,
+            inputs: (*crate::pac::PORTB::ptr()).pinb.read().bits(),
+            pins: {
+                port::PB0: 0,
+                port::PB1: 1,
+                port::PB2: 2,
+                port::PB3: 3,
+                port::PB4: 4,
+                port::PB5: 5,
+                port::PB6: 6,
+                port::PB7: 7,
+            },
+        },
+        1: {
+            pcie: 1,
+            pcmsk: pcmsk1,
+            inputs: (*crate::pac::PORTC::ptr()).pinc.read().bits(),
+            pins: {
+                port::PC0: 0,
+                port::PC1: 1,
+                port::PC2: 2,
+                port::PC3: 3,
+                port::PC4: 4,
+                port::PC5: 5,
+                port::PC6: 6,
+            },
+        },
+        2: {
+            pcie: 2,
+            pcmsk: pcmsk2,
+            inputs: (*crate::pac::PORTD::ptr()).pind.read().bits(),
+            pins: {
+                port::PD0: 0,
+                port::PD1: 1,
+                port::PD2: 2,
+                port::PD3: 3,
+                port::PD4: 4,
+                port::PD5: 5,
+                port::PD6: 6,
+                port::PD7: 7,
+            },
+        },
+        3: {
+            pcie: 3,
+            pcmsk: pcmsk3,
+            inputs: (*crate::pac::PORTE::ptr()).pine.read().bits(),
+            pins: {
+                port::PE0: 0,
+                port::PE1: 1,
+                port::PE2: 2,
+                port::PE3: 3,
+            },
+        },
+    },
+}
+
+#[cfg(any(feature = "atmega164pa", feature = "atmega1284p"))]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    pcicr: pcicr,
+    pcifr: pcifr,
+    groups: {
+        0: {
+            pcie: 0,
+            pcmsk: pcmsk0,
+            inputs: (*crate::pac::PORTA::ptr()).pina.read().bits(),
+            pins: {
+                port::PA0: 0,
+                port::PA1: 1,
+                port::PA2: 2,
+                port::PA3: 3,
+                port::PA4: 4,
+                port::PA5: 5,
+                port::PA6: 6,
+                port::PA7: 7,
+            },
+        },
+        1: {
+            pcie: 1,
+            pcmsk: pcmsk1,
+            inputs: (*crate::pac::PORTB::ptr()).pinb.read().bits(),
+            pins: {
+                port::PB0: 0,
+                port::PB1: 1,
+                port::PB2: 2,
+                port::PB3: 3,
+                port::PB4: 4,
+                port::PB5: 5,
+                port::PB6: 6,
+                port::PB7: 7,
+            },
+        },
+        2: {
+            pcie: 2,
+            pcmsk: pcmsk2,
+            inputs: (*crate::pac::PORTC::ptr()).pinc.read().bits(),
+            pins: {
+                port::PC0: 0,
+                port::PC1: 1,
+                port::PC2: 2,
+                port::PC3: 3,
+                port::PC4: 4,
+                port::PC5: 5,
+                port::PC6: 6,
+                port::PC7: 7,
+            },
+        },
+        3: {
+            pcie: 3,
+            pcmsk: pcmsk3,
+            inputs: (*crate::pac::PORTD::ptr()).pind.read().bits(),
+            pins: {
+                port::PD0: 0,
+                port::PD1: 1,
+                port::PD2: 2,
+                port::PD3: 3,
+                port::PD4: 4,
+                port::PD5: 5,
+                port::PD6: 6,
+                port::PD7: 7,
+            },
+        },
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    pcicr: pcicr,
+    pcifr: pcifr,
+    groups: {
+        0: {
+            pcie: 0,
+            pcmsk: pcmsk0,
+            inputs: (*crate::pac::PORTB::ptr()).pinb.read().bits(),
+            pins: {
+                port::PB0: 0,
+                port::PB1: 1,
+                port::PB2: 2,
+                port::PB3: 3,
+                port::PB4: 4,
+                port::PB5: 5,
+                port::PB6: 6,
+                port::PB7: 7,
+            },
+        },
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    pcicr: pcicr,
+    pcifr: pcifr,
+    groups: {
+        0: {
+            pcie: 0,
+            pcmsk: pcmsk0,
+            inputs: (*crate::pac::PORTB::ptr()).pinb.read().bits(),
+            pins: {
+                port::PB0: 0,
+                port::PB1: 1,
+                port::PB2: 2,
+                port::PB3: 3,
+                port::PB4: 4,
+                port::PB5: 5,
+                port::PB6: 6,
+                port::PB7: 7,
+            },
+        },
+        1: {
+            pcie: 1,
+            pcmsk: pcmsk1,
+            inputs: (*crate::pac::PORTE::ptr()).pine.read().bits() & 0x01
+                | (*crate::pac::PORTJ::ptr()).pinj.read().bits() << 1,
+            pins: {
+                port::PE0: 0,
+                port::PJ0: 1,
+                port::PJ1: 2,
+                port::PJ2: 3,
+                port::PJ3: 4,
+                port::PJ4: 5,
+                port::PJ5: 6,
+                port::PJ6: 7,
+            },
+        },
+        2: {
+            pcie: 2,
+            pcmsk: pcmsk2,
+            inputs: (*crate::pac::PORTK::ptr()).pink.read().bits(),
+            pins: {
+                port::PK0: 0,
+                port::PK1: 1,
+                port::PK2: 2,
+                port::PK3: 3,
+                port::PK4: 4,
+                port::PK5: 5,
+                port::PK6: 6,
+                port::PK7: 7,
+            },
+        },
+    },
+}
@@ -0,0 +1,9 @@
+//! Hobby servos on 16-bit timer PWM
+//!
+//! Servos can be attached to the output compare pins of all timers which implement
+//! [`Pwm16Ops`][crate::simple_pwm::Pwm16Ops], see [`avr_hal_generic::servo::Servo`].
+pub use avr_hal_generic::servo::FREQUENCY;
+
+/// Hobby servo on an output compare pin of a 16-bit timer.
+pub type Servo<'a, TC, PIN, CLOCK> =
+    avr_hal_generic::servo::Servo<'a, crate::Atmega, TC, PIN, CLOCK>;
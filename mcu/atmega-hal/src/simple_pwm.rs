@@ -1,4 +1,10 @@
-pub use avr_hal_generic::simple_pwm::{IntoPwmPin, Prescaler, PwmPinOps};
+pub use avr_hal_generic::simple_pwm::{
+    IntoPwmPin, Prescaler, Pwm16Mode, Pwm16Ops, PwmMode, PwmPinOps,
+};
+
+/// PWM on a 16-bit timer with configurable frequency, see
+/// [`avr_hal_generic::simple_pwm::Pwm16`].
+pub type Pwm16<TC, CLOCK> = avr_hal_generic::simple_pwm::Pwm16<crate::Atmega, TC, CLOCK>;
 
 #[allow(unused_imports)]
 use crate::port::*;
@@ -35,6 +41,12 @@ avr_hal_generic::impl_simple_pwm! {
                 Prescaler::Prescale1024 => w.cs0().prescale_1024(),
             });
         },
+        mode: |tim, mode| {
+            tim.tccr0a.modify(|_r, w| match mode {
+                PwmMode::Fast => w.wgm0().pwm_fast(),
+                PwmMode::PhaseCorrect => w.wgm0().pwm_phase(),
+            });
+        },
         pins: {
             PD6: {
                 ocr: ocr0a,
@@ -43,6 +55,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr0a.modify(|_r, w| w.com0a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr0a.modify(|_r, w| w.com0a().match_set());
+                },
             },
 
             PD5: {
@@ -52,6 +67,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr0a.modify(|_r, w| w.com0b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr0a.modify(|_r, w| w.com0b().match_set());
+                },
             },
         },
     }
@@ -93,6 +111,13 @@ avr_hal_generic::impl_simple_pwm! {
                 }
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr1b.modify(|_r, w| w.wgm1().bits(wgm));
+        },
         pins: {
             PB1: {
                 ocr: ocr1a,
@@ -101,6 +126,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_r, w| w.com1a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_r, w| w.com1a().match_set());
+                },
             },
 
             PB2: {
@@ -110,6 +138,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_r, w| w.com1b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_r, w| w.com1b().match_set());
+                },
             },
         },
     }
@@ -147,6 +178,12 @@ avr_hal_generic::impl_simple_pwm! {
                     Prescaler::Prescale1024 => w.cs2().prescale_1024(),
             });
         },
+        mode: |tim, mode| {
+            tim.tccr2a.modify(|_r, w| match mode {
+                PwmMode::Fast => w.wgm2().pwm_fast(),
+                PwmMode::PhaseCorrect => w.wgm2().pwm_phase(),
+            });
+        },
         pins: {
             PB3: {
                 ocr: ocr2a,
@@ -155,6 +192,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr2a.modify(|_r, w| w.com2a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr2a.modify(|_r, w| w.com2a().match_set());
+                },
             },
 
             PD3: {
@@ -164,6 +204,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr2a.modify(|_r, w| w.com2b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr2a.modify(|_r, w| w.com2b().match_set());
+                },
             },
         },
     }
@@ -188,6 +231,13 @@ avr_hal_generic::impl_simple_pwm! {
                 }
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr3b.modify(|_r, w| unsafe { w.wgm3().bits(wgm) });
+        },
         pins: {
             PD0: {
                 ocr: ocr3a,
@@ -196,6 +246,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr3a.modify(|_r, w| w.com3a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr3a.modify(|_r, w| w.com3a().match_set());
+                },
             },
 
             PD2: {
@@ -205,6 +258,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr3a.modify(|_r, w| w.com3b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr3a.modify(|_r, w| w.com3b().match_set());
+                },
             },
         },
     }
@@ -229,6 +285,13 @@ avr_hal_generic::impl_simple_pwm! {
                 }
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr4b.modify(|_r, w| unsafe { w.wgm4().bits(wgm) });
+        },
         pins: {
             PD1: {
                 ocr: ocr4a,
@@ -237,6 +300,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr4a.modify(|_r, w| w.com4a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr4a.modify(|_r, w| w.com4a().match_set());
+                },
             },
 
             PD2: {
@@ -246,6 +312,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr4a.modify(|_r, w| w.com4b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr4a.modify(|_r, w| w.com4b().match_set());
+                },
             },
         },
     }
@@ -277,6 +346,12 @@ avr_hal_generic::impl_simple_pwm! {
                 Prescaler::Prescale1024 => w.cs0().prescale_1024(),
             });
         },
+        mode: |tim, mode| {
+            tim.tccr0a.modify(|_r, w| match mode {
+                PwmMode::Fast => w.wgm0().pwm_fast(),
+                PwmMode::PhaseCorrect => w.wgm0().pwm_phase(),
+            });
+        },
         pins: {
             PB7: {
                 ocr: ocr0a,
@@ -285,6 +360,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr0a.modify(|_r, w| w.com0a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr0a.modify(|_r, w| w.com0a().match_set());
+                },
             },
 
             PG5: {
@@ -294,6 +372,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr0a.modify(|_r, w| w.com0b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr0a.modify(|_r, w| w.com0b().match_set());
+                },
             },
         },
     }
@@ -326,6 +407,13 @@ avr_hal_generic::impl_simple_pwm! {
                 Prescaler::Prescale1024 => w.cs1().prescale_1024(),
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr1b.modify(|_r, w| w.wgm1().bits(wgm));
+        },
         pins: {
             PB5: {
                 ocr: ocr1a,
@@ -334,6 +422,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_r, w| w.com1a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_r, w| w.com1a().match_set());
+                },
             },
 
             PB6: {
@@ -343,6 +434,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_r, w| w.com1b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_r, w| w.com1b().match_set());
+                },
             },
 
             PB7: {
@@ -352,6 +446,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_r, w| w.com1c().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_r, w| w.com1c().match_set());
+                },
             },
         },
     }
@@ -388,6 +485,13 @@ avr_hal_generic::impl_simple_pwm! {
                 }
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b11,
+                PwmMode::PhaseCorrect => 0b01,
+            };
+            tim.tccr2a.modify(|_r, w| w.wgm2().bits(wgm));
+        },
         pins: {
             PB4: {
                 ocr: ocr2a,
@@ -396,6 +500,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr2a.modify(|_r, w| w.com2a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr2a.modify(|_r, w| w.com2a().match_set());
+                },
             },
 
             PH6: {
@@ -405,6 +512,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr2a.modify(|_r, w| w.com2b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr2a.modify(|_r, w| w.com2b().match_set());
+                },
             },
         },
     }
@@ -441,6 +551,13 @@ avr_hal_generic::impl_simple_pwm! {
                 }
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr3b.modify(|_r, w| w.wgm3().bits(wgm));
+        },
         pins: {
             PE3: {
                 ocr: ocr3a,
@@ -449,6 +566,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr3a.modify(|_r, w| w.com3a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr3a.modify(|_r, w| w.com3a().match_set());
+                },
             },
 
             PE4: {
@@ -458,6 +578,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr3a.modify(|_r, w| w.com3b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr3a.modify(|_r, w| w.com3b().match_set());
+                },
             },
 
             PE5: {
@@ -467,6 +590,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr3a.modify(|_r, w| w.com3c().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr3a.modify(|_r, w| w.com3c().match_set());
+                },
             },
 
         },
@@ -504,6 +630,13 @@ avr_hal_generic::impl_simple_pwm! {
                 }
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr4b.modify(|_r, w| w.wgm4().bits(wgm));
+        },
         pins: {
             PH3: {
                 ocr: ocr4a,
@@ -512,6 +645,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr4a.modify(|_r, w| w.com4a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr4a.modify(|_r, w| w.com4a().match_set());
+                },
             },
 
             PH4: {
@@ -521,6 +657,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr4a.modify(|_r, w| w.com4b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr4a.modify(|_r, w| w.com4b().match_set());
+                },
             },
 
             PH5: {
@@ -530,6 +669,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr4a.modify(|_r, w| w.com4c().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr4a.modify(|_r, w| w.com4c().match_set());
+                },
             },
 
         },
@@ -567,6 +709,13 @@ avr_hal_generic::impl_simple_pwm! {
                 }
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr5b.modify(|_r, w| w.wgm5().bits(wgm));
+        },
         pins: {
             PL3: {
                 ocr: ocr5a,
@@ -575,6 +724,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr5a.modify(|_r, w| w.com5a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr5a.modify(|_r, w| w.com5a().match_set());
+                },
             },
 
             PL4: {
@@ -584,6 +736,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr5a.modify(|_r, w| w.com5b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr5a.modify(|_r, w| w.com5b().match_set());
+                },
             },
 
             PL5: {
@@ -593,6 +748,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr5a.modify(|_r, w| w.com5c().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr5a.modify(|_r, w| w.com5c().match_set());
+                },
             },
 
         },
@@ -625,6 +783,12 @@ avr_hal_generic::impl_simple_pwm! {
                 Prescaler::Prescale1024 => w.cs0().prescale_1024(),
             });
         },
+        mode: |tim, mode| {
+            tim.tccr0a.modify(|_r, w| match mode {
+                PwmMode::Fast => w.wgm0().pwm_fast(),
+                PwmMode::PhaseCorrect => w.wgm0().pwm_phase(),
+            });
+        },
         pins: {
             PB7: {
                 ocr: ocr0a,
@@ -633,6 +797,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr0a.modify(|_r, w| w.com0a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr0a.modify(|_r, w| w.com0a().match_set());
+                },
             },
 
             PD0: {
@@ -642,6 +809,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr0a.modify(|_r, w| w.com0b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr0a.modify(|_r, w| w.com0b().match_set());
+                },
             },
         },
     }
@@ -676,6 +846,13 @@ avr_hal_generic::impl_simple_pwm! {
                 Prescaler::Prescale1024 => w.cs1().prescale_1024(),
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr1b.modify(|_r, w| w.wgm1().bits(wgm));
+        },
         pins: {
             PB5: {
                 ocr: ocr1a,
@@ -684,6 +861,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_r, w| w.com1a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_r, w| w.com1a().match_set());
+                },
             },
 
             PB6: {
@@ -693,6 +873,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_r, w| w.com1b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_r, w| w.com1b().match_set());
+                },
             },
 
             PB7: {
@@ -702,6 +885,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_r, w| w.com1c().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_r, w| w.com1c().match_set());
+                },
             },
         },
     }
@@ -734,6 +920,13 @@ avr_hal_generic::impl_simple_pwm! {
                 Prescaler::Prescale1024 => w.cs3().prescale_1024(),
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr3b.modify(|_r, w| w.wgm3().bits(wgm));
+        },
         pins: {
             PC6: {
                 ocr: ocr3a,
@@ -742,72 +935,323 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr3a.modify(|_r, w| w.com3a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr3a.modify(|_r, w| w.com3a().match_set());
+                },
             },
         },
     }
 }
 
-#[cfg(any(feature = "atmega32u4"))]
-avr_hal_generic::impl_simple_pwm! {
-    /// Use `TC4` for PWM (pins `PB6`, `PC7`, `PD7`)
+/// Clock source of the high-speed `TC4` on ATmega32U4
+///
+/// The PLL is run at 96 MHz and its postscaler output is used as the timer clock.  The USB
+/// controller keeps receiving 48 MHz from the PLL.
+#[cfg(feature = "atmega32u4")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timer4Clock {
+    /// The IO clock.
+    Io,
+    /// 64 MHz from the PLL (96 MHz / 1.5).
+    Pll64MHz,
+    /// 48 MHz from the PLL (96 MHz / 2).
+    Pll48MHz,
+}
+
+/// Prescaler of the dead time generator of `TC4`
+///
+/// The dead time generator is clocked by the timer clock before the main prescaler.
+#[cfg(feature = "atmega32u4")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadTimePrescaler {
+    Direct,
+    Prescale2,
+    Prescale4,
+    Prescale8,
+}
+
+/// Output compare channels of `TC4`
+#[cfg(feature = "atmega32u4")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timer4Channel {
+    A,
+    B,
+    D,
+}
+
+/// `OC4x` pins of `TC4` and their complementary `!OC4x` pins.
+#[cfg(feature = "atmega32u4")]
+pub trait Timer4Pin: PwmPinOps<Timer4Pwm> {
+    const CHANNEL: Timer4Channel;
+    type Complement;
+}
+
+/// Use the high-speed `TC4` for 10-bit PWM (pins `PB6`, `PC7`, `PD7`)
+///
+/// `TC4` counts up to the `TOP` value in `OCR4C`, which is 1023 initially, so the duty cycle of
+/// all pins ranges from 0 to `TOP`.  The timer can be clocked from the PLL with up to 64 MHz, see
+/// [`Timer4Pwm::with_pll`].
+///
+/// Each channel can drive its `OC4x` pin together with the complementary `!OC4x` pin (`PC6`,
+/// `PB5` or `PD6`), separated by the dead time set with [`Timer4Pwm::set_dead_time`].  This is
+/// meant for driving half-bridges.
+///
+/// # Example
+/// ```
+/// let mut timer4 = Timer4Pwm::with_pll::<atmega_hal::clock::MHz16>(
+///     dp.TC4,
+///     &dp.PLL,
+///     Timer4Clock::Pll64MHz,
+///     Prescaler::Direct,
+/// );
+/// // About 0.23 µs between switching one output off and the other one on
+/// timer4.set_dead_time(DeadTimePrescaler::Direct, 15, 15);
+///
+/// let mut d13 = pins.d13.into_output().into_pwm(&timer4);
+/// let d5 = pins.d5.into_output();
+///
+/// d13.set_duty(512);
+/// timer4.enable_complementary(&mut d13, d5);
+/// ```
+#[cfg(feature = "atmega32u4")]
+pub struct Timer4Pwm {
+    timer: crate::pac::TC4,
+}
+
+#[cfg(feature = "atmega32u4")]
+impl Timer4Pwm {
+    /// Start `TC4` from the IO clock.
+    pub fn new(timer: crate::pac::TC4, prescaler: Prescaler) -> Self {
+        let mut t = Timer4Pwm { timer };
+        t.setup(prescaler);
+        t
+    }
+
+    /// Start `TC4` from the given clock source, enabling the PLL if needed.
     ///
-    /// # Example
-    /// ```
-    /// let mut timer4 = Timer4Pwm::new(dp.TC4, Prescaler::Prescale64);
+    /// The PLL requires a system clock of 8 MHz or 16 MHz, other clocks are rejected at compile
+    /// time.  It is reconfigured to 96 MHz, which also keeps the 48 MHz clock of the USB
+    /// controller intact.
+    pub fn with_pll<CLOCK: avr_hal_generic::clock::Clock>(
+        timer: crate::pac::TC4,
+        pll: &crate::pac::PLL,
+        clock: Timer4Clock,
+        prescaler: Prescaler,
+    ) -> Self {
+        let pltm = match clock {
+            Timer4Clock::Io => 0b00,
+            Timer4Clock::Pll64MHz => 0b10,
+            Timer4Clock::Pll48MHz => 0b11,
+        };
+        if pltm != 0 {
+            // PINMUX is kept, PLLUSB (bit 6) divides the 96 MHz (PDIV3:0 = 0b1010) for USB.
+            pll.pllfrq
+                .modify(|r, w| unsafe { w.bits(r.bits() & 0x80 | 1 << 6 | 0b1010) });
+            pll.pllcsr
+                .write(|w| unsafe { w.bits(PllInput::<CLOCK>::PINDIV | 1 << 1) });
+            while pll.pllcsr.read().bits() & 1 == 0 {}
+        }
+        pll.pllfrq
+            .modify(|r, w| unsafe { w.bits(r.bits() & !(0b11 << 4) | pltm << 4) });
+        Self::new(timer, prescaler)
+    }
+
+    fn setup(&mut self, prescaler: Prescaler) {
+        let cs = match prescaler {
+            Prescaler::Direct => 0b0001,
+            Prescaler::Prescale8 => 0b0100,
+            Prescaler::Prescale64 => 0b0111,
+            Prescaler::Prescale256 => 0b1001,
+            Prescaler::Prescale1024 => 0b1011,
+        };
+        let tim = &self.timer;
+        tim.tccr4b.write(|w| unsafe { w.bits(0) });
+        // PWM4A and PWM4B, all outputs disconnected
+        tim.tccr4a.write(|w| unsafe { w.bits(0b11) });
+        // PWM4D
+        tim.tccr4c.write(|w| unsafe { w.bits(0b1) });
+        // Fast PWM, no fault protection
+        tim.tccr4d.write(|w| unsafe { w.bits(0) });
+        tim.tccr4e.write(|w| unsafe { w.bits(0) });
+        tim.dt4.write(|w| w.bits(0));
+        // SAFETY: 10-bit registers are written through the shared TC4H register, so the
+        // sequences are wrapped in a critical section.
+        avr_device::interrupt::free(|_| {
+            tim.tc4h.write(|w| w.bits(0x03));
+            tim.ocr4c.write(|w| w.bits(0xff));
+            tim.tc4h.write(|w| w.bits(0));
+            tim.tcnt4.write(|w| w.bits(0));
+        });
+        tim.tccr4b.write(|w| unsafe { w.bits(cs) });
+    }
+
+    /// Select fast PWM or phase and frequency correct PWM.
     ///
-    /// let mut d6 = pins.d6.into_output().into_pwm(&mut timer4);
-    /// let mut d10 = pins.d10.into_output().into_pwm(&mut timer4);
-    /// let mut d13 = pins.d13.into_output().into_pwm(&mut timer4);
+    /// In [`PwmMode::PhaseCorrect`] the counter counts up to `TOP` and back down again, which
+    /// halves the frequency.
+    pub fn set_mode(&mut self, mode: PwmMode) {
+        let wgm = match mode {
+            PwmMode::Fast => 0b00,
+            PwmMode::PhaseCorrect => 0b01,
+        };
+        self.timer
+            .tccr4d
+            .modify(|r, w| unsafe { w.bits(r.bits() & !0b11 | wgm) });
+    }
+
+    /// Set the `TOP` value of the counter (10 bit), which is also the maximum duty cycle.
     ///
-    /// d6.set_duty(128);
-    /// d6.enable();
-    /// ```
-    pub struct Timer4Pwm {
-        timer: crate::pac::TC4,
-        init: |tim, prescaler| {
-            tim.tccr4a.modify(|_r, w| w.pwm4a().set_bit());
-            tim.tccr4a.modify(|_r, w| w.pwm4b().set_bit());
-            tim.tccr4c.modify(|_r, w| w.pwm4d().set_bit());
-
-            tim.tccr4b.modify(|_r, w| match prescaler {
-                Prescaler::Direct => w.cs4().direct(),
-                Prescaler::Prescale8 => w.cs4().prescale_8(),
-                Prescaler::Prescale64 => w.cs4().prescale_64(),
-                Prescaler::Prescale256 => w.cs4().prescale_256(),
-                Prescaler::Prescale1024 => w.cs4().prescale_1024(),
-            });
-        },
-        pins: {
-            PB6: {
-                ocr: ocr4b,
-                into_pwm: |tim| if enable {
-                    tim.tccr4a.modify(|_r, w| w.com4b().match_clear());
-                } else {
-                    tim.tccr4a.modify(|_r, w| w.com4b().disconnected());
-                },
-            },
+    /// The PWM frequency is `F_tc4 / (Prescaler * (TOP + 1))` in fast PWM mode.
+    pub fn set_top(&mut self, top: u16) {
+        let tim = &self.timer;
+        avr_device::interrupt::free(|_| {
+            tim.tc4h.write(|w| w.bits((top >> 8) as u8 & 0x03));
+            tim.ocr4c.write(|w| w.bits(top as u8));
+        });
+    }
 
-            PC7: {
-                ocr: ocr4a,
-                into_pwm: |tim| if enable {
-                    tim.tccr4a.modify(|_r, w| w.com4a().match_clear());
-                } else {
-                    tim.tccr4a.modify(|_r, w| w.com4a().disconnected());
-                },
-            },
+    /// The `TOP` value of the counter, which is also the maximum duty cycle.
+    pub fn max_duty(&self) -> u16 {
+        read_10bit(|| self.timer.ocr4c.read().bits())
+    }
 
-            PD7: {
-                ocr: ocr4d,
-                into_pwm: |tim| if enable {
-                    tim.tccr4c.modify(|_r, w| w.com4d().match_clear());
-                } else {
-                    tim.tccr4c.modify(|_r, w| w.com4d().disconnected());
-                },
-            },
-        },
+    /// Configure the dead time inserted between the complementary outputs of all channels.
+    ///
+    /// `oc` delays the rising edge of `OC4x`, `oc_complement` the rising edge of `!OC4x`, both in
+    /// ticks of the timer clock divided by `prescaler`.  The maximum dead time is 15 ticks.
+    pub fn set_dead_time(&mut self, prescaler: DeadTimePrescaler, oc: u8, oc_complement: u8) {
+        let dtps = match prescaler {
+            DeadTimePrescaler::Direct => 0b00,
+            DeadTimePrescaler::Prescale2 => 0b01,
+            DeadTimePrescaler::Prescale4 => 0b10,
+            DeadTimePrescaler::Prescale8 => 0b11,
+        };
+        let tim = &self.timer;
+        // DT4H (bits 7:4) delays !OC4x, DT4L (bits 3:0) delays OC4x.
+        tim.dt4
+            .write(|w| w.bits(oc_complement.min(15) << 4 | oc.min(15)));
+        tim.tccr4b
+            .modify(|r, w| unsafe { w.bits(r.bits() & !(0b11 << 4) | dtps << 4) });
+    }
+
+    /// Enable a channel with both `OC4x` and the complementary `!OC4x` output.
+    ///
+    /// `OC4x` behaves as after `enable()`, `!OC4x` is its inverse with the dead time inserted
+    /// between the two.  The `!OC4x` pin is consumed as it is driven by the timer from now on.
+    /// Use `disable()` on the `OC4x` pin to turn both outputs off again.
+    pub fn enable_complementary<PIN: Timer4Pin>(
+        &mut self,
+        _pin: &mut avr_hal_generic::port::Pin<
+            avr_hal_generic::port::mode::PwmOutput<Timer4Pwm>,
+            PIN,
+        >,
+        _complement: avr_hal_generic::port::Pin<
+            avr_hal_generic::port::mode::Output,
+            PIN::Complement,
+        >,
+    ) {
+        set_com(PIN::CHANNEL, 0b01);
     }
 }
 
+/// PLL input prescaler for the system clock `CLOCK`.
+#[cfg(feature = "atmega32u4")]
+struct PllInput<CLOCK>(core::marker::PhantomData<CLOCK>);
+
+#[cfg(feature = "atmega32u4")]
+impl<CLOCK: avr_hal_generic::clock::Clock> PllInput<CLOCK> {
+    /// PINDIV (bit 4) divides a 16 MHz system clock down to the 8 MHz PLL input.
+    const PINDIV: u8 = match CLOCK::FREQ {
+        8_000_000 => 0,
+        16_000_000 => 1 << 4,
+        _ => panic!("the PLL requires a system clock of 8 MHz or 16 MHz"),
+    };
+}
+
+/// Read a 10-bit register of `TC4`: The low byte must be read first, which latches the high
+/// byte into `TC4H`.
+#[cfg(feature = "atmega32u4")]
+fn read_10bit(low: impl FnOnce() -> u8) -> u16 {
+    avr_device::interrupt::free(|_| {
+        let low = low() as u16;
+        let high = unsafe { &*crate::pac::TC4::ptr() }.tc4h.read().bits() as u16 & 0x03;
+        high << 8 | low
+    })
+}
+
+#[cfg(feature = "atmega32u4")]
+fn set_com(channel: Timer4Channel, com: u8) {
+    // SAFETY: TCCR4A and TCCR4C are shared by all channels, so the read-modify-write sequence is
+    // wrapped in a critical section.
+    avr_device::interrupt::free(|_| {
+        let tim = unsafe { &*crate::pac::TC4::ptr() };
+        match channel {
+            Timer4Channel::A => tim
+                .tccr4a
+                .modify(|r, w| unsafe { w.bits(r.bits() & !(0b11 << 6) | com << 6) }),
+            Timer4Channel::B => tim
+                .tccr4a
+                .modify(|r, w| unsafe { w.bits(r.bits() & !(0b11 << 4) | com << 4) }),
+            Timer4Channel::D => tim
+                .tccr4c
+                .modify(|r, w| unsafe { w.bits(r.bits() & !(0b11 << 2) | com << 2) }),
+        }
+    });
+}
+
+#[cfg(feature = "atmega32u4")]
+macro_rules! impl_timer4_pin {
+    ($($pin:ty: { channel: $channel:ident, ocr: $ocr:ident, complement: $complement:ty, },)+) => {
+        $(
+            impl Timer4Pin for $pin {
+                const CHANNEL: Timer4Channel = Timer4Channel::$channel;
+                type Complement = $complement;
+            }
+
+            impl PwmPinOps<Timer4Pwm> for $pin {
+                type Duty = u16;
+
+                fn enable(&mut self) {
+                    set_com(Timer4Channel::$channel, 0b10);
+                }
+
+                fn enable_inverted(&mut self) {
+                    set_com(Timer4Channel::$channel, 0b11);
+                }
+
+                fn disable(&mut self) {
+                    set_com(Timer4Channel::$channel, 0b00);
+                }
+
+                fn get_duty(&self) -> Self::Duty {
+                    read_10bit(|| unsafe { &*crate::pac::TC4::ptr() }.$ocr.read().bits())
+                }
+
+                fn get_max_duty(&self) -> Self::Duty {
+                    read_10bit(|| unsafe { &*crate::pac::TC4::ptr() }.ocr4c.read().bits())
+                }
+
+                fn set_duty(&mut self, duty: Self::Duty) {
+                    // SAFETY: The high byte is written to the shared TC4H register first, so the
+                    // sequence is wrapped in a critical section.
+                    avr_device::interrupt::free(|_| {
+                        let tim = unsafe { &*crate::pac::TC4::ptr() };
+                        tim.tc4h.write(|w| w.bits((duty >> 8) as u8 & 0x03));
+                        tim.$ocr.write(|w| w.bits(duty as u8));
+                    });
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "atmega32u4")]
+impl_timer4_pin! {
+    PC7: { channel: A, ocr: ocr4a, complement: PC6, },
+    PB6: { channel: B, ocr: ocr4b, complement: PB5, },
+    PD7: { channel: D, ocr: ocr4d, complement: PD6, },
+}
+
 #[cfg(any(feature = "atmega1284p"))]
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC0` for PWM (pins `PB3`, `PB4`)
@@ -834,6 +1278,12 @@ avr_hal_generic::impl_simple_pwm! {
                 Prescaler::Prescale1024 => w.cs0().prescale_1024(),
             });
         },
+        mode: |tim, mode| {
+            tim.tccr0a.modify(|_r, w| match mode {
+                PwmMode::Fast => w.wgm0().pwm_fast(),
+                PwmMode::PhaseCorrect => w.wgm0().pwm_phase(),
+            });
+        },
         pins: {
             PB3: {
                 ocr: ocr0a,
@@ -842,6 +1292,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr0a.modify(|_r, w| w.com0a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr0a.modify(|_r, w| w.com0a().match_set());
+                },
             },
 
             PB4: {
@@ -851,6 +1304,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr0a.modify(|_r, w| w.com0b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr0a.modify(|_r, w| w.com0b().match_set());
+                },
             },
         },
     }
@@ -886,6 +1342,13 @@ avr_hal_generic::impl_simple_pwm! {
                 }
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr1b.modify(|_r, w| w.wgm1().bits(wgm));
+        },
         pins: {
             PD5: {
                 ocr: ocr1a,
@@ -894,6 +1357,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_r, w| w.com1a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_r, w| w.com1a().match_set());
+                },
             },
 
             PD4: {
@@ -903,6 +1369,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_r, w| w.com1b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_r, w| w.com1b().match_set());
+                },
             },
         },
     }
@@ -934,6 +1403,12 @@ avr_hal_generic::impl_simple_pwm! {
                     Prescaler::Prescale1024 => w.cs2().prescale_1024(),
             });
         },
+        mode: |tim, mode| {
+            tim.tccr2a.modify(|_r, w| match mode {
+                PwmMode::Fast => w.wgm2().pwm_fast(),
+                PwmMode::PhaseCorrect => w.wgm2().pwm_phase(),
+            });
+        },
         pins: {
             PD7: {
                 ocr: ocr2a,
@@ -942,6 +1417,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr2a.modify(|_r, w| w.com2a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr2a.modify(|_r, w| w.com2a().match_set());
+                },
             },
 
             PD6: {
@@ -951,6 +1429,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr2a.modify(|_r, w| w.com2b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr2a.modify(|_r, w| w.com2b().match_set());
+                },
             },
         },
     }
@@ -975,6 +1456,13 @@ avr_hal_generic::impl_simple_pwm! {
                 }
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr3b.modify(|_r, w| w.wgm3().bits(wgm));
+        },
         pins: {
             PB6: {
                 ocr: ocr3a,
@@ -983,6 +1471,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr3a.modify(|_r, w| w.com3a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr3a.modify(|_r, w| w.com3a().match_set());
+                },
             },
 
             PB7: {
@@ -992,6 +1483,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr3a.modify(|_r, w| w.com3b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr3a.modify(|_r, w| w.com3b().match_set());
+                },
             },
         },
     }
@@ -1027,6 +1521,13 @@ avr_hal_generic::impl_simple_pwm! {
                 }
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr1b.modify(|_r, w| w.wgm1().bits(wgm));
+        },
         pins: {
             PB1: {
                 ocr: ocr1a,
@@ -1035,6 +1536,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_r, w| w.com1a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_r, w| w.com1a().match_set());
+                },
             },
 
             PB2: {
@@ -1044,6 +1548,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_r, w| w.com1b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_r, w| w.com1b().match_set());
+                },
             },
         },
     }
@@ -1075,6 +1582,9 @@ avr_hal_generic::impl_simple_pwm! {
                     Prescaler::Prescale1024 => w.cs2().prescale_1024(),
             });
         },
+        mode: |tim, mode| {
+            tim.tccr2.modify(|_r, w| w.wgm21().bit(mode == PwmMode::Fast));
+        },
         pins: {
             PB3: {
                 ocr: ocr2,
@@ -1083,6 +1593,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr2.modify(|_r, w| w.com2().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr2.modify(|_r, w| w.com2().match_set());
+                },
             },
         },
     }
@@ -1092,6 +1605,10 @@ avr_hal_generic::impl_simple_pwm! {
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC0` for PWM (pins `PB3`)
     ///
+    /// **Note**: On this device, `enable()` has always selected the inverting mode
+    /// (`COMnx = 0b11`) and is kept that way for compatibility, so it behaves the same as
+    /// `enable_inverted()`.
+    ///
     /// # Example
     /// ```
     /// let mut timer0 = Timer0Pwm::new(dp.TC0, Prescaler::Prescale64);
@@ -1115,6 +1632,13 @@ avr_hal_generic::impl_simple_pwm! {
                 Prescaler::Prescale1024 => w.cs0().running_clk_1024(),
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b11,
+                PwmMode::PhaseCorrect => 0b01,
+            };
+            tim.tccr0a.modify(|_r, w| w.wgm0().bits(wgm));
+        },
         pins: {
             PB3: {
                 ocr: ocr0a,
@@ -1123,6 +1647,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr0a.modify(|_r, w| w.com0a().bits(0b00));
                 },
+                inverted: |tim| {
+                    tim.tccr0a.modify(|_r, w| w.com0a().bits(0b11));
+                },
             },
         },
     }
@@ -1132,6 +1659,10 @@ avr_hal_generic::impl_simple_pwm! {
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC1` for PWM (pins `PD4`, `PD5`)
     ///
+    /// **Note**: On this device, `enable()` has always selected the inverting mode
+    /// (`COMnx = 0b11`) and is kept that way for compatibility, so it behaves the same as
+    /// `enable_inverted()`.
+    ///
     /// # Example
     /// ```
     /// let mut timer1 = Timer1Pwm::new(dp.TC1, Prescaler::Prescale64);
@@ -1158,6 +1689,14 @@ avr_hal_generic::impl_simple_pwm! {
                 Prescaler::Prescale1024 => w.cs1().running_clk_1024(),
             });
         },
+        mode: |tim, mode| {
+            // WGM13:12 are bits 4:3 of TCCR1B.
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr1b.modify(|r, w| unsafe { w.bits(r.bits() & !(0b11 << 3) | wgm << 3) });
+        },
         pins: {
             PD4: {
                 ocr: ocr1a,
@@ -1166,6 +1705,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_r, w| w.com1a().bits(0b00));
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_r, w| w.com1a().bits(0b11));
+                },
             },
             PD5: {
                 ocr: ocr1b,
@@ -1174,7 +1716,376 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_r, w| w.com1b().bits(0b00));
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_r, w| w.com1b().bits(0b11));
+                },
             },
         },
     }
 }
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+pub type Timer1Pwm16<CLOCK> = Pwm16<crate::pac::TC1, CLOCK>;
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    tccra: tccr1a,
+    tccrb: tccr1b,
+    tcnt: tcnt1,
+    icr: icr1,
+    pins: {
+        PB1: {
+            ocr: ocr1a,
+            com: 6,
+        },
+        PB2: {
+            ocr: ocr1b,
+            com: 4,
+        },
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+pub type Timer3Pwm16<CLOCK> = Pwm16<crate::pac::TC3, CLOCK>;
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    tccra: tccr3a,
+    tccrb: tccr3b,
+    tcnt: tcnt3,
+    icr: icr3,
+    pins: {
+        PD0: {
+            ocr: ocr3a,
+            com: 6,
+        },
+        PD2: {
+            ocr: ocr3b,
+            com: 4,
+        },
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+pub type Timer4Pwm16<CLOCK> = Pwm16<crate::pac::TC4, CLOCK>;
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    tccra: tccr4a,
+    tccrb: tccr4b,
+    tcnt: tcnt4,
+    icr: icr4,
+    pins: {
+        PD1: {
+            ocr: ocr4a,
+            com: 6,
+        },
+        PD2: {
+            ocr: ocr4b,
+            com: 4,
+        },
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Timer1Pwm16<CLOCK> = Pwm16<crate::pac::TC1, CLOCK>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    tccra: tccr1a,
+    tccrb: tccr1b,
+    tcnt: tcnt1,
+    icr: icr1,
+    pins: {
+        PB5: {
+            ocr: ocr1a,
+            com: 6,
+        },
+        PB6: {
+            ocr: ocr1b,
+            com: 4,
+        },
+        PB7: {
+            ocr: ocr1c,
+            com: 2,
+        },
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Timer3Pwm16<CLOCK> = Pwm16<crate::pac::TC3, CLOCK>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    tccra: tccr3a,
+    tccrb: tccr3b,
+    tcnt: tcnt3,
+    icr: icr3,
+    pins: {
+        PE3: {
+            ocr: ocr3a,
+            com: 6,
+        },
+        PE4: {
+            ocr: ocr3b,
+            com: 4,
+        },
+        PE5: {
+            ocr: ocr3c,
+            com: 2,
+        },
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Timer4Pwm16<CLOCK> = Pwm16<crate::pac::TC4, CLOCK>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    tccra: tccr4a,
+    tccrb: tccr4b,
+    tcnt: tcnt4,
+    icr: icr4,
+    pins: {
+        PH3: {
+            ocr: ocr4a,
+            com: 6,
+        },
+        PH4: {
+            ocr: ocr4b,
+            com: 4,
+        },
+        PH5: {
+            ocr: ocr4c,
+            com: 2,
+        },
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Timer5Pwm16<CLOCK> = Pwm16<crate::pac::TC5, CLOCK>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC5,
+    tccra: tccr5a,
+    tccrb: tccr5b,
+    tcnt: tcnt5,
+    icr: icr5,
+    pins: {
+        PL3: {
+            ocr: ocr5a,
+            com: 6,
+        },
+        PL4: {
+            ocr: ocr5b,
+            com: 4,
+        },
+        PL5: {
+            ocr: ocr5c,
+            com: 2,
+        },
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+pub type Timer1Pwm16<CLOCK> = Pwm16<crate::pac::TC1, CLOCK>;
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    tccra: tccr1a,
+    tccrb: tccr1b,
+    tcnt: tcnt1,
+    icr: icr1,
+    pins: {
+        PB5: {
+            ocr: ocr1a,
+            com: 6,
+        },
+        PB6: {
+            ocr: ocr1b,
+            com: 4,
+        },
+        PB7: {
+            ocr: ocr1c,
+            com: 2,
+        },
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+pub type Timer3Pwm16<CLOCK> = Pwm16<crate::pac::TC3, CLOCK>;
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    tccra: tccr3a,
+    tccrb: tccr3b,
+    tcnt: tcnt3,
+    icr: icr3,
+    pins: {
+        PC6: {
+            ocr: ocr3a,
+            com: 6,
+        },
+    },
+}
+
+#[cfg(any(feature = "atmega164pa", feature = "atmega1284p"))]
+pub type Timer1Pwm16<CLOCK> = Pwm16<crate::pac::TC1, CLOCK>;
+#[cfg(any(feature = "atmega164pa", feature = "atmega1284p"))]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    tccra: tccr1a,
+    tccrb: tccr1b,
+    tcnt: tcnt1,
+    icr: icr1,
+    pins: {
+        PD5: {
+            ocr: ocr1a,
+            com: 6,
+        },
+        PD4: {
+            ocr: ocr1b,
+            com: 4,
+        },
+    },
+}
+
+#[cfg(feature = "atmega1284p")]
+pub type Timer3Pwm16<CLOCK> = Pwm16<crate::pac::TC3, CLOCK>;
+#[cfg(feature = "atmega1284p")]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    tccra: tccr3a,
+    tccrb: tccr3b,
+    tcnt: tcnt3,
+    icr: icr3,
+    pins: {
+        PB6: {
+            ocr: ocr3a,
+            com: 6,
+        },
+        PB7: {
+            ocr: ocr3b,
+            com: 4,
+        },
+    },
+}
+
+#[cfg(feature = "atmega8")]
+pub type Timer1Pwm16<CLOCK> = Pwm16<crate::pac::TC1, CLOCK>;
+#[cfg(feature = "atmega8")]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    tccra: tccr1a,
+    tccrb: tccr1b,
+    tcnt: tcnt1,
+    icr: icr1,
+    pins: {
+        PB1: {
+            ocr: ocr1a,
+            com: 6,
+        },
+        PB2: {
+            ocr: ocr1b,
+            com: 4,
+        },
+    },
+}
+
+#[cfg(feature = "atmega32a")]
+pub type Timer1Pwm16<CLOCK> = Pwm16<crate::pac::TC1, CLOCK>;
+#[cfg(feature = "atmega32a")]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    tccra: tccr1a,
+    tccrb: tccr1b,
+    tcnt: tcnt1,
+    icr: icr1,
+    pins: {
+        PD5: {
+            ocr: ocr1a,
+            com: 6,
+        },
+        PD4: {
+            ocr: ocr1b,
+            com: 4,
+        },
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+pub type Timer1Pwm16<CLOCK> = Pwm16<crate::pac::TC1, CLOCK>;
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    tccra: tccr1a,
+    tccrb: tccr1b,
+    tcnt: tcnt1,
+    icr: icr1,
+    pins: {
+        PB5: {
+            ocr: ocr1a,
+            com: 6,
+        },
+        PB6: {
+            ocr: ocr1b,
+            com: 4,
+        },
+        PB7: {
+            ocr: ocr1c,
+            com: 2,
+        },
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+pub type Timer3Pwm16<CLOCK> = Pwm16<crate::pac::TC3, CLOCK>;
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_pwm16! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    tccra: tccr3a,
+    tccrb: tccr3b,
+    tcnt: tcnt3,
+    icr: icr3,
+    pins: {
+        PE3: {
+            ocr: ocr3a,
+            com: 6,
+        },
+        PE4: {
+            ocr: ocr3b,
+            com: 4,
+        },
+        PE5: {
+            ocr: ocr3c,
+            com: 2,
+        },
+    },
+}
@@ -30,6 +30,7 @@
 
 #[allow(unused_imports)]
 use crate::port;
+pub use avr_hal_generic::soft_spi::SoftSpi;
 pub use avr_hal_generic::spi::*;
 
 #[cfg(any(
@@ -52,6 +53,50 @@ pub type Spi = avr_hal_generic::spi::Spi<
     feature = "atmega2560",
     feature = "atmega32u4"
 ))]
+pub type IsrSpi<const N: usize> = avr_hal_generic::spi::IsrSpi<
+    crate::Atmega,
+    crate::pac::SPI,
+    port::PB1,
+    port::PB2,
+    port::PB3,
+    port::PB0,
+    N,
+>;
+#[cfg(any(
+    feature = "atmega128a",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega32u4"
+))]
+pub type SpiPeripheral = avr_hal_generic::spi::SpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI,
+    port::PB1,
+    port::PB2,
+    port::PB3,
+    port::PB0,
+>;
+#[cfg(any(
+    feature = "atmega128a",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega32u4"
+))]
+pub type BufferedSpiPeripheral<const N: usize> = avr_hal_generic::spi::BufferedSpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI,
+    port::PB1,
+    port::PB2,
+    port::PB3,
+    port::PB0,
+    N,
+>;
+#[cfg(any(
+    feature = "atmega128a",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega32u4"
+))]
 avr_hal_generic::impl_spi! {
     hal: crate::Atmega,
     peripheral: crate::pac::SPI,
@@ -83,6 +128,53 @@ pub type Spi = avr_hal_generic::spi::Spi<
     feature = "atmega8",
     feature = "atmega88p"
 ))]
+pub type IsrSpi<const N: usize> = avr_hal_generic::spi::IsrSpi<
+    crate::Atmega,
+    crate::pac::SPI,
+    port::PB5,
+    port::PB3,
+    port::PB4,
+    port::PB2,
+    N,
+>;
+#[cfg(any(
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega48p",
+    feature = "atmega8",
+    feature = "atmega88p"
+))]
+pub type SpiPeripheral = avr_hal_generic::spi::SpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI,
+    port::PB5,
+    port::PB3,
+    port::PB4,
+    port::PB2,
+>;
+#[cfg(any(
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega48p",
+    feature = "atmega8",
+    feature = "atmega88p"
+))]
+pub type BufferedSpiPeripheral<const N: usize> = avr_hal_generic::spi::BufferedSpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI,
+    port::PB5,
+    port::PB3,
+    port::PB4,
+    port::PB2,
+    N,
+>;
+#[cfg(any(
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega48p",
+    feature = "atmega8",
+    feature = "atmega88p"
+))]
 avr_hal_generic::impl_spi! {
     hal: crate::Atmega,
     peripheral: crate::pac::SPI,
@@ -102,6 +194,35 @@ pub type Spi0 = avr_hal_generic::spi::Spi<
     port::PB2,
 >;
 #[cfg(feature = "atmega328pb")]
+pub type IsrSpi0<const N: usize> = avr_hal_generic::spi::IsrSpi<
+    crate::Atmega,
+    crate::pac::SPI0,
+    port::PB5,
+    port::PB3,
+    port::PB4,
+    port::PB2,
+    N,
+>;
+#[cfg(feature = "atmega328pb")]
+pub type SpiPeripheral0 = avr_hal_generic::spi::SpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI0,
+    port::PB5,
+    port::PB3,
+    port::PB4,
+    port::PB2,
+>;
+#[cfg(feature = "atmega328pb")]
+pub type BufferedSpiPeripheral0<const N: usize> = avr_hal_generic::spi::BufferedSpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI0,
+    port::PB5,
+    port::PB3,
+    port::PB4,
+    port::PB2,
+    N,
+>;
+#[cfg(feature = "atmega328pb")]
 avr_hal_generic::impl_spi! {
     hal: crate::Atmega,
     peripheral: crate::pac::SPI0,
@@ -120,6 +241,35 @@ pub type Spi1 = avr_hal_generic::spi::Spi<
     port::PE2,
 >;
 #[cfg(feature = "atmega328pb")]
+pub type IsrSpi1<const N: usize> = avr_hal_generic::spi::IsrSpi<
+    crate::Atmega,
+    crate::pac::SPI1,
+    port::PC1,
+    port::PE3,
+    port::PC0,
+    port::PE2,
+    N,
+>;
+#[cfg(feature = "atmega328pb")]
+pub type SpiPeripheral1 = avr_hal_generic::spi::SpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI1,
+    port::PC1,
+    port::PE3,
+    port::PC0,
+    port::PE2,
+>;
+#[cfg(feature = "atmega328pb")]
+pub type BufferedSpiPeripheral1<const N: usize> = avr_hal_generic::spi::BufferedSpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI1,
+    port::PC1,
+    port::PE3,
+    port::PC0,
+    port::PE2,
+    N,
+>;
+#[cfg(feature = "atmega328pb")]
 avr_hal_generic::impl_spi! {
     hal: crate::Atmega,
     peripheral: crate::pac::SPI1,
@@ -139,6 +289,35 @@ pub type Spi = avr_hal_generic::spi::Spi<
     port::PB4,
 >;
 #[cfg(any(feature = "atmega1284p", feature = "atmega32a"))]
+pub type IsrSpi<const N: usize> = avr_hal_generic::spi::IsrSpi<
+    crate::Atmega,
+    crate::pac::SPI,
+    port::PB7,
+    port::PB5,
+    port::PB6,
+    port::PB4,
+    N,
+>;
+#[cfg(any(feature = "atmega1284p", feature = "atmega32a"))]
+pub type SpiPeripheral = avr_hal_generic::spi::SpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI,
+    port::PB7,
+    port::PB5,
+    port::PB6,
+    port::PB4,
+>;
+#[cfg(any(feature = "atmega1284p", feature = "atmega32a"))]
+pub type BufferedSpiPeripheral<const N: usize> = avr_hal_generic::spi::BufferedSpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI,
+    port::PB7,
+    port::PB5,
+    port::PB6,
+    port::PB4,
+    N,
+>;
+#[cfg(any(feature = "atmega1284p", feature = "atmega32a"))]
 avr_hal_generic::impl_spi! {
     hal: crate::Atmega,
     peripheral: crate::pac::SPI,
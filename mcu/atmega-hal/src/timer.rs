@@ -0,0 +1,777 @@
+//! Timer/Counter
+//!
+//! # Example
+//!
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//!
+//! let mut timer = Timer1::<crate::CoreClock>::new(dp.TC1);
+//!
+//! // Let the TIMER1_COMPA interrupt fire every 250 ms
+//! timer.start_ctc_us(250_000).unwrap();
+//! timer.listen(Event::CompareMatchA);
+//! ```
+//!
+//! *Note*: The old-style timers of the ATmega8, ATmega32A and ATmega128A as well as the
+//! high-speed `TC4` of the ATmega32U4 are not supported by this module.
+
+pub use avr_hal_generic::timer::*;
+
+pub type Timer<TC, CLOCK> = avr_hal_generic::timer::Timer<crate::Atmega, TC, CLOCK>;
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+pub type Timer0<CLOCK> = Timer<crate::pac::TC0, CLOCK>;
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    tcnt: tcnt0,
+    ocr: [ocr0a, ocr0b],
+    timsk: timsk0 {
+        overflow: toie0,
+        compare_a: ocie0a,
+        compare_b: ocie0b,
+    },
+    tifr: tifr0 {
+        overflow: tov0,
+        compare_a: ocf0a,
+        compare_b: ocf0b,
+    },
+    setup: |tim, mode, prescaler| {
+        tim.tccr0a.write(|w| match mode {
+            Mode::Normal => w.wgm0().normal_top(),
+            Mode::Ctc => w.wgm0().ctc(),
+        });
+        tim.tccr0b.write(|w| match prescaler {
+            Prescaler::Direct => w.cs0().direct(),
+            Prescaler::Prescale8 => w.cs0().prescale_8(),
+            Prescaler::Prescale64 => w.cs0().prescale_64(),
+            Prescaler::Prescale256 => w.cs0().prescale_256(),
+            Prescaler::Prescale1024 => w.cs0().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr0b.modify(|_r, w| w.cs0().no_clock());
+    },
+}
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+pub type Timer1<CLOCK> = Timer<crate::pac::TC1, CLOCK>;
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    tcnt: tcnt1,
+    ocr: [ocr1a, ocr1b],
+    timsk: timsk1 {
+        overflow: toie1,
+        compare_a: ocie1a,
+        compare_b: ocie1b,
+    },
+    tifr: tifr1 {
+        overflow: tov1,
+        compare_a: ocf1a,
+        compare_b: ocf1b,
+    },
+    setup: |tim, mode, prescaler| {
+        let wgm = match mode {
+            Mode::Normal => 0b00,
+            Mode::Ctc => 0b01,
+        };
+        tim.tccr1a.write(|w| w.wgm1().bits(0b00));
+        tim.tccr1b.write(|w| {
+            w.wgm1().bits(wgm);
+
+            match prescaler {
+                Prescaler::Direct => w.cs1().direct(),
+                Prescaler::Prescale8 => w.cs1().prescale_8(),
+                Prescaler::Prescale64 => w.cs1().prescale_64(),
+                Prescaler::Prescale256 => w.cs1().prescale_256(),
+                Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+            }
+        });
+    },
+    stop: |tim| {
+        tim.tccr1b.modify(|_r, w| w.cs1().no_clock());
+    },
+}
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+pub type Timer2<CLOCK> = Timer<crate::pac::TC2, CLOCK>;
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    counter: u8,
+    tcnt: tcnt2,
+    ocr: [ocr2a, ocr2b],
+    timsk: timsk2 {
+        overflow: toie2,
+        compare_a: ocie2a,
+        compare_b: ocie2b,
+    },
+    tifr: tifr2 {
+        overflow: tov2,
+        compare_a: ocf2a,
+        compare_b: ocf2b,
+    },
+    setup: |tim, mode, prescaler| {
+        tim.tccr2a.write(|w| match mode {
+            Mode::Normal => w.wgm2().normal_top(),
+            Mode::Ctc => w.wgm2().ctc(),
+        });
+        tim.tccr2b.write(|w| match prescaler {
+            Prescaler::Direct => w.cs2().direct(),
+            Prescaler::Prescale8 => w.cs2().prescale_8(),
+            Prescaler::Prescale64 => w.cs2().prescale_64(),
+            Prescaler::Prescale256 => w.cs2().prescale_256(),
+            Prescaler::Prescale1024 => w.cs2().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr2b.modify(|_r, w| w.cs2().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+pub type Timer3<CLOCK> = Timer<crate::pac::TC3, CLOCK>;
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    counter: u16,
+    tcnt: tcnt3,
+    ocr: [ocr3a, ocr3b],
+    timsk: timsk3 {
+        overflow: toie3,
+        compare_a: ocie3a,
+        compare_b: ocie3b,
+    },
+    tifr: tifr3 {
+        overflow: tov3,
+        compare_a: ocf3a,
+        compare_b: ocf3b,
+    },
+    setup: |tim, mode, prescaler| {
+        let wgm = match mode {
+            Mode::Normal => 0b00,
+            Mode::Ctc => 0b01,
+        };
+        tim.tccr3a.write(|w| w.wgm3().bits(0b00));
+        tim.tccr3b.write(|w| {
+            unsafe { w.wgm3().bits(wgm) };
+
+            match prescaler {
+                Prescaler::Direct => w.cs3().direct(),
+                Prescaler::Prescale8 => w.cs3().prescale_8(),
+                Prescaler::Prescale64 => w.cs3().prescale_64(),
+                Prescaler::Prescale256 => w.cs3().prescale_256(),
+                Prescaler::Prescale1024 => w.cs3().prescale_1024(),
+            }
+        });
+    },
+    stop: |tim| {
+        tim.tccr3b.modify(|_r, w| w.cs3().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+pub type Timer4<CLOCK> = Timer<crate::pac::TC4, CLOCK>;
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    counter: u16,
+    tcnt: tcnt4,
+    ocr: [ocr4a, ocr4b],
+    timsk: timsk4 {
+        overflow: toie4,
+        compare_a: ocie4a,
+        compare_b: ocie4b,
+    },
+    tifr: tifr4 {
+        overflow: tov4,
+        compare_a: ocf4a,
+        compare_b: ocf4b,
+    },
+    setup: |tim, mode, prescaler| {
+        let wgm = match mode {
+            Mode::Normal => 0b00,
+            Mode::Ctc => 0b01,
+        };
+        tim.tccr4a.write(|w| w.wgm4().bits(0b00));
+        tim.tccr4b.write(|w| {
+            unsafe { w.wgm4().bits(wgm) };
+
+            match prescaler {
+                Prescaler::Direct => w.cs4().direct(),
+                Prescaler::Prescale8 => w.cs4().prescale_8(),
+                Prescaler::Prescale64 => w.cs4().prescale_64(),
+                Prescaler::Prescale256 => w.cs4().prescale_256(),
+                Prescaler::Prescale1024 => w.cs4().prescale_1024(),
+            }
+        });
+    },
+    stop: |tim| {
+        tim.tccr4b.modify(|_r, w| w.cs4().no_clock());
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Timer0<CLOCK> = Timer<crate::pac::TC0, CLOCK>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    tcnt: tcnt0,
+    ocr: [ocr0a, ocr0b],
+    timsk: timsk0 {
+        overflow: toie0,
+        compare_a: ocie0a,
+        compare_b: ocie0b,
+    },
+    tifr: tifr0 {
+        overflow: tov0,
+        compare_a: ocf0a,
+        compare_b: ocf0b,
+    },
+    setup: |tim, mode, prescaler| {
+        tim.tccr0a.write(|w| match mode {
+            Mode::Normal => w.wgm0().normal_top(),
+            Mode::Ctc => w.wgm0().ctc(),
+        });
+        tim.tccr0b.write(|w| match prescaler {
+            Prescaler::Direct => w.cs0().direct(),
+            Prescaler::Prescale8 => w.cs0().prescale_8(),
+            Prescaler::Prescale64 => w.cs0().prescale_64(),
+            Prescaler::Prescale256 => w.cs0().prescale_256(),
+            Prescaler::Prescale1024 => w.cs0().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr0b.modify(|_r, w| w.cs0().no_clock());
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Timer1<CLOCK> = Timer<crate::pac::TC1, CLOCK>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    tcnt: tcnt1,
+    ocr: [ocr1a, ocr1b],
+    timsk: timsk1 {
+        overflow: toie1,
+        compare_a: ocie1a,
+        compare_b: ocie1b,
+    },
+    tifr: tifr1 {
+        overflow: tov1,
+        compare_a: ocf1a,
+        compare_b: ocf1b,
+    },
+    setup: |tim, mode, prescaler| {
+        let wgm = match mode {
+            Mode::Normal => 0b00,
+            Mode::Ctc => 0b01,
+        };
+        tim.tccr1a.write(|w| w.wgm1().bits(0b00));
+        tim.tccr1b.write(|w| {
+            w.wgm1().bits(wgm);
+
+            match prescaler {
+                Prescaler::Direct => w.cs1().direct(),
+                Prescaler::Prescale8 => w.cs1().prescale_8(),
+                Prescaler::Prescale64 => w.cs1().prescale_64(),
+                Prescaler::Prescale256 => w.cs1().prescale_256(),
+                Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+            }
+        });
+    },
+    stop: |tim| {
+        tim.tccr1b.modify(|_r, w| w.cs1().no_clock());
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Timer2<CLOCK> = Timer<crate::pac::TC2, CLOCK>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    counter: u8,
+    tcnt: tcnt2,
+    ocr: [ocr2a, ocr2b],
+    timsk: timsk2 {
+        overflow: toie2,
+        compare_a: ocie2a,
+        compare_b: ocie2b,
+    },
+    tifr: tifr2 {
+        overflow: tov2,
+        compare_a: ocf2a,
+        compare_b: ocf2b,
+    },
+    setup: |tim, mode, prescaler| {
+        tim.tccr2a.write(|w| match mode {
+            Mode::Normal => w.wgm2().bits(0b00),
+            Mode::Ctc => w.wgm2().bits(0b10),
+        });
+        tim.tccr2b.write(|w| match prescaler {
+            Prescaler::Direct => w.cs2().direct(),
+            Prescaler::Prescale8 => w.cs2().prescale_8(),
+            Prescaler::Prescale64 => w.cs2().prescale_64(),
+            Prescaler::Prescale256 => w.cs2().prescale_256(),
+            Prescaler::Prescale1024 => w.cs2().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr2b.modify(|_r, w| w.cs2().no_clock());
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Timer3<CLOCK> = Timer<crate::pac::TC3, CLOCK>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    counter: u16,
+    tcnt: tcnt3,
+    ocr: [ocr3a, ocr3b],
+    timsk: timsk3 {
+        overflow: toie3,
+        compare_a: ocie3a,
+        compare_b: ocie3b,
+    },
+    tifr: tifr3 {
+        overflow: tov3,
+        compare_a: ocf3a,
+        compare_b: ocf3b,
+    },
+    setup: |tim, mode, prescaler| {
+        let wgm = match mode {
+            Mode::Normal => 0b00,
+            Mode::Ctc => 0b01,
+        };
+        tim.tccr3a.write(|w| w.wgm3().bits(0b00));
+        tim.tccr3b.write(|w| {
+            w.wgm3().bits(wgm);
+
+            match prescaler {
+                Prescaler::Direct => w.cs3().direct(),
+                Prescaler::Prescale8 => w.cs3().prescale_8(),
+                Prescaler::Prescale64 => w.cs3().prescale_64(),
+                Prescaler::Prescale256 => w.cs3().prescale_256(),
+                Prescaler::Prescale1024 => w.cs3().prescale_1024(),
+            }
+        });
+    },
+    stop: |tim| {
+        tim.tccr3b.modify(|_r, w| w.cs3().no_clock());
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Timer4<CLOCK> = Timer<crate::pac::TC4, CLOCK>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    counter: u16,
+    tcnt: tcnt4,
+    ocr: [ocr4a, ocr4b],
+    timsk: timsk4 {
+        overflow: toie4,
+        compare_a: ocie4a,
+        compare_b: ocie4b,
+    },
+    tifr: tifr4 {
+        overflow: tov4,
+        compare_a: ocf4a,
+        compare_b: ocf4b,
+    },
+    setup: |tim, mode, prescaler| {
+        let wgm = match mode {
+            Mode::Normal => 0b00,
+            Mode::Ctc => 0b01,
+        };
+        tim.tccr4a.write(|w| w.wgm4().bits(0b00));
+        tim.tccr4b.write(|w| {
+            w.wgm4().bits(wgm);
+
+            match prescaler {
+                Prescaler::Direct => w.cs4().direct(),
+                Prescaler::Prescale8 => w.cs4().prescale_8(),
+                Prescaler::Prescale64 => w.cs4().prescale_64(),
+                Prescaler::Prescale256 => w.cs4().prescale_256(),
+                Prescaler::Prescale1024 => w.cs4().prescale_1024(),
+            }
+        });
+    },
+    stop: |tim| {
+        tim.tccr4b.modify(|_r, w| w.cs4().no_clock());
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Timer5<CLOCK> = Timer<crate::pac::TC5, CLOCK>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC5,
+    counter: u16,
+    tcnt: tcnt5,
+    ocr: [ocr5a, ocr5b],
+    timsk: timsk5 {
+        overflow: toie5,
+        compare_a: ocie5a,
+        compare_b: ocie5b,
+    },
+    tifr: tifr5 {
+        overflow: tov5,
+        compare_a: ocf5a,
+        compare_b: ocf5b,
+    },
+    setup: |tim, mode, prescaler| {
+        let wgm = match mode {
+            Mode::Normal => 0b00,
+            Mode::Ctc => 0b01,
+        };
+        tim.tccr5a.write(|w| w.wgm5().bits(0b00));
+        tim.tccr5b.write(|w| {
+            w.wgm5().bits(wgm);
+
+            match prescaler {
+                Prescaler::Direct => w.cs5().direct(),
+                Prescaler::Prescale8 => w.cs5().prescale_8(),
+                Prescaler::Prescale64 => w.cs5().prescale_64(),
+                Prescaler::Prescale256 => w.cs5().prescale_256(),
+                Prescaler::Prescale1024 => w.cs5().prescale_1024(),
+            }
+        });
+    },
+    stop: |tim| {
+        tim.tccr5b.modify(|_r, w| w.cs5().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+pub type Timer0<CLOCK> = Timer<crate::pac::TC0, CLOCK>;
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    tcnt: tcnt0,
+    ocr: [ocr0a, ocr0b],
+    timsk: timsk0 {
+        overflow: toie0,
+        compare_a: ocie0a,
+        compare_b: ocie0b,
+    },
+    tifr: tifr0 {
+        overflow: tov0,
+        compare_a: ocf0a,
+        compare_b: ocf0b,
+    },
+    setup: |tim, mode, prescaler| {
+        tim.tccr0a.write(|w| match mode {
+            Mode::Normal => w.wgm0().normal_top(),
+            Mode::Ctc => w.wgm0().ctc(),
+        });
+        tim.tccr0b.write(|w| match prescaler {
+            Prescaler::Direct => w.cs0().direct(),
+            Prescaler::Prescale8 => w.cs0().prescale_8(),
+            Prescaler::Prescale64 => w.cs0().prescale_64(),
+            Prescaler::Prescale256 => w.cs0().prescale_256(),
+            Prescaler::Prescale1024 => w.cs0().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr0b.modify(|_r, w| w.cs0().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+pub type Timer1<CLOCK> = Timer<crate::pac::TC1, CLOCK>;
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    tcnt: tcnt1,
+    ocr: [ocr1a, ocr1b],
+    timsk: timsk1 {
+        overflow: toie1,
+        compare_a: ocie1a,
+        compare_b: ocie1b,
+    },
+    tifr: tifr1 {
+        overflow: tov1,
+        compare_a: ocf1a,
+        compare_b: ocf1b,
+    },
+    setup: |tim, mode, prescaler| {
+        let wgm = match mode {
+            Mode::Normal => 0b00,
+            Mode::Ctc => 0b01,
+        };
+        tim.tccr1a.write(|w| w.wgm1().bits(0b00));
+        tim.tccr1b.write(|w| {
+            w.wgm1().bits(wgm);
+
+            match prescaler {
+                Prescaler::Direct => w.cs1().direct(),
+                Prescaler::Prescale8 => w.cs1().prescale_8(),
+                Prescaler::Prescale64 => w.cs1().prescale_64(),
+                Prescaler::Prescale256 => w.cs1().prescale_256(),
+                Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+            }
+        });
+    },
+    stop: |tim| {
+        tim.tccr1b.modify(|_r, w| w.cs1().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+pub type Timer3<CLOCK> = Timer<crate::pac::TC3, CLOCK>;
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    counter: u16,
+    tcnt: tcnt3,
+    ocr: [ocr3a, ocr3b],
+    timsk: timsk3 {
+        overflow: toie3,
+        compare_a: ocie3a,
+        compare_b: ocie3b,
+    },
+    tifr: tifr3 {
+        overflow: tov3,
+        compare_a: ocf3a,
+        compare_b: ocf3b,
+    },
+    setup: |tim, mode, prescaler| {
+        let wgm = match mode {
+            Mode::Normal => 0b00,
+            Mode::Ctc => 0b01,
+        };
+        tim.tccr3a.write(|w| w.wgm3().bits(0b00));
+        tim.tccr3b.write(|w| {
+            w.wgm3().bits(wgm);
+
+            match prescaler {
+                Prescaler::Direct => w.cs3().direct(),
+                Prescaler::Prescale8 => w.cs3().prescale_8(),
+                Prescaler::Prescale64 => w.cs3().prescale_64(),
+                Prescaler::Prescale256 => w.cs3().prescale_256(),
+                Prescaler::Prescale1024 => w.cs3().prescale_1024(),
+            }
+        });
+    },
+    stop: |tim| {
+        tim.tccr3b.modify(|_r, w| w.cs3().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega1284p")]
+pub type Timer0<CLOCK> = Timer<crate::pac::TC0, CLOCK>;
+#[cfg(feature = "atmega1284p")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    tcnt: tcnt0,
+    ocr: [ocr0a, ocr0b],
+    timsk: timsk0 {
+        overflow: toie0,
+        compare_a: ocie0a,
+        compare_b: ocie0b,
+    },
+    tifr: tifr0 {
+        overflow: tov0,
+        compare_a: ocf0a,
+        compare_b: ocf0b,
+    },
+    setup: |tim, mode, prescaler| {
+        tim.tccr0a.write(|w| match mode {
+            Mode::Normal => w.wgm0().normal_top(),
+            Mode::Ctc => w.wgm0().ctc(),
+        });
+        tim.tccr0b.write(|w| match prescaler {
+            Prescaler::Direct => w.cs0().direct(),
+            Prescaler::Prescale8 => w.cs0().prescale_8(),
+            Prescaler::Prescale64 => w.cs0().prescale_64(),
+            Prescaler::Prescale256 => w.cs0().prescale_256(),
+            Prescaler::Prescale1024 => w.cs0().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr0b.modify(|_r, w| w.cs0().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega1284p")]
+pub type Timer1<CLOCK> = Timer<crate::pac::TC1, CLOCK>;
+#[cfg(feature = "atmega1284p")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    tcnt: tcnt1,
+    ocr: [ocr1a, ocr1b],
+    timsk: timsk1 {
+        overflow: toie1,
+        compare_a: ocie1a,
+        compare_b: ocie1b,
+    },
+    tifr: tifr1 {
+        overflow: tov1,
+        compare_a: ocf1a,
+        compare_b: ocf1b,
+    },
+    setup: |tim, mode, prescaler| {
+        let wgm = match mode {
+            Mode::Normal => 0b00,
+            Mode::Ctc => 0b01,
+        };
+        tim.tccr1a.write(|w| w.wgm1().bits(0b00));
+        tim.tccr1b.write(|w| {
+            w.wgm1().bits(wgm);
+
+            match prescaler {
+                Prescaler::Direct => w.cs1().direct(),
+                Prescaler::Prescale8 => w.cs1().prescale_8(),
+                Prescaler::Prescale64 => w.cs1().prescale_64(),
+                Prescaler::Prescale256 => w.cs1().prescale_256(),
+                Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+            }
+        });
+    },
+    stop: |tim| {
+        tim.tccr1b.modify(|_r, w| w.cs1().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega1284p")]
+pub type Timer2<CLOCK> = Timer<crate::pac::TC2, CLOCK>;
+#[cfg(feature = "atmega1284p")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    counter: u8,
+    tcnt: tcnt2,
+    ocr: [ocr2a, ocr2b],
+    timsk: timsk2 {
+        overflow: toie2,
+        compare_a: ocie2a,
+        compare_b: ocie2b,
+    },
+    tifr: tifr2 {
+        overflow: tov2,
+        compare_a: ocf2a,
+        compare_b: ocf2b,
+    },
+    setup: |tim, mode, prescaler| {
+        tim.tccr2a.write(|w| match mode {
+            Mode::Normal => w.wgm2().normal_top(),
+            Mode::Ctc => w.wgm2().ctc(),
+        });
+        tim.tccr2b.write(|w| match prescaler {
+            Prescaler::Direct => w.cs2().direct(),
+            Prescaler::Prescale8 => w.cs2().prescale_8(),
+            Prescaler::Prescale64 => w.cs2().prescale_64(),
+            Prescaler::Prescale256 => w.cs2().prescale_256(),
+            Prescaler::Prescale1024 => w.cs2().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr2b.modify(|_r, w| w.cs2().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega1284p")]
+pub type Timer3<CLOCK> = Timer<crate::pac::TC3, CLOCK>;
+#[cfg(feature = "atmega1284p")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    counter: u16,
+    tcnt: tcnt3,
+    ocr: [ocr3a, ocr3b],
+    timsk: timsk3 {
+        overflow: toie3,
+        compare_a: ocie3a,
+        compare_b: ocie3b,
+    },
+    tifr: tifr3 {
+        overflow: tov3,
+        compare_a: ocf3a,
+        compare_b: ocf3b,
+    },
+    setup: |tim, mode, prescaler| {
+        let wgm = match mode {
+            Mode::Normal => 0b00,
+            Mode::Ctc => 0b01,
+        };
+        tim.tccr3a.write(|w| w.wgm3().bits(0b00));
+        tim.tccr3b.write(|w| {
+            w.wgm3().bits(wgm);
+
+            match prescaler {
+                Prescaler::Direct => w.cs3().direct(),
+                Prescaler::Prescale8 => w.cs3().prescale_8(),
+                Prescaler::Prescale64 => w.cs3().prescale_64(),
+                Prescaler::Prescale256 => w.cs3().prescale_256(),
+                Prescaler::Prescale1024 => w.cs3().prescale_1024(),
+            }
+        });
+    },
+    stop: |tim| {
+        tim.tccr3b.modify(|_r, w| w.cs3().no_clock());
+    },
+}
@@ -31,7 +31,9 @@
 
 #[allow(unused_imports)]
 use crate::port;
+pub use avr_hal_generic::soft_uart::{BufferedSoftUart, SoftUart, SoftUartBuffers};
 pub use avr_hal_generic::usart::*;
+pub use avr_hal_generic::usart_spi::UsartSpiOps;
 
 pub type Usart<USART, RX, TX, CLOCK> =
     avr_hal_generic::usart::Usart<crate::Atmega, USART, RX, TX, CLOCK>;
@@ -39,6 +41,26 @@ pub type UsartWriter<USART, RX, TX, CLOCK> =
     avr_hal_generic::usart::UsartWriter<crate::Atmega, USART, RX, TX, CLOCK>;
 pub type UsartReader<USART, RX, TX, CLOCK> =
     avr_hal_generic::usart::UsartReader<crate::Atmega, USART, RX, TX, CLOCK>;
+pub type BufferedUsart<
+    USART,
+    RX,
+    TX,
+    CLOCK,
+    const RX_SIZE: usize,
+    const TX_SIZE: usize,
+    DE = NoDriverEnable,
+> = avr_hal_generic::usart::BufferedUsart<
+    crate::Atmega,
+    USART,
+    RX,
+    TX,
+    CLOCK,
+    RX_SIZE,
+    TX_SIZE,
+    DE,
+>;
+pub type Rs485Usart<USART, RX, TX, DE, CLOCK> =
+    avr_hal_generic::usart::Rs485Usart<crate::Atmega, USART, RX, TX, DE, CLOCK>;
 
 #[cfg(any(
     feature = "atmega88p",
@@ -186,7 +208,9 @@ impl
         crate::port::Pin<crate::port::mode::Output, port::PD1>,
     > for crate::pac::USART
 {
-    fn raw_init<CLOCK>(&mut self, baudrate: crate::usart::Baudrate<CLOCK>) {
+    fn raw_init<CLOCK>(&mut self, settings: crate::usart::UsartSettings<CLOCK>) {
+        let baudrate = settings.baudrate;
+        let frame = settings.frame_format;
         // msb of ubrrh has to be 0 to set ubrrh register. (see atmega8 datasheet)
         let ubrrh: u8 = ((baudrate.ubrr >> 8) & 0x0F) as u8;
         let ubrrl: u8 = (baudrate.ubrr & 0xFF) as u8;
@@ -199,18 +223,30 @@ impl
         self.ucsrb.write(|w| w
             .txen().set_bit()
             .rxen().set_bit()
+            .ucsz2().bit(frame.data_bits == DataBits::Nine)
         );
 
-        // Set frame format to 8n1 for now.  At some point, this should be made
-        // configurable, similar to what is done in other HALs.
-        #[rustfmt::skip]
-        self.ucsrc().write(|w| w
-            .ursel().set_bit() // sets the ucsrc instead of ubrrh (ubrrh and ucsrc share same location on ATmega8, see atmega8 datasheet)
-            .umsel().usart_async()
-            .ucsz().chr8()
-            .usbs().stop1()
-            .upm().disabled()
-        );
+        self.ucsrc().write(|w| {
+            // sets the ucsrc instead of ubrrh (ubrrh and ucsrc share same location on ATmega8,
+            // see atmega8 datasheet)
+            let w = w.ursel().set_bit().umsel().usart_async();
+            let w = match frame.data_bits {
+                DataBits::Five => w.ucsz().chr5(),
+                DataBits::Six => w.ucsz().chr6(),
+                DataBits::Seven => w.ucsz().chr7(),
+                // 9-bit frames are selected by the additional UCSZn2 bit.
+                DataBits::Eight | DataBits::Nine => w.ucsz().chr8(),
+            };
+            let w = match frame.stop_bits {
+                StopBits::One => w.usbs().stop1(),
+                StopBits::Two => w.usbs().stop2(),
+            };
+            match frame.parity {
+                Parity::None => w.upm().disabled(),
+                Parity::Even => w.upm().parity_even(),
+                Parity::Odd => w.upm().parity_odd(),
+            }
+        });
     }
 
     fn raw_deinit(&mut self) {
@@ -238,12 +274,72 @@ impl
         Ok(())
     }
 
-    fn raw_read(&mut self) -> avr_hal_generic::nb::Result<u8, core::convert::Infallible> {
-        if self.ucsra.read().rxc().bit_is_clear() {
+    fn raw_read(&mut self) -> avr_hal_generic::nb::Result<u8, crate::usart::Error> {
+        let status = self.ucsra.read();
+        if status.rxc().bit_is_clear() {
+            return Err(avr_hal_generic::nb::Error::WouldBlock);
+        }
+
+        // The error flags belong to the byte in the receive buffer, so they must be read before
+        // it.  Reading the data register clears them again.
+        let byte = self.udr.read().bits();
+        match crate::usart::Error::from_flags(
+            status.fe().bit_is_set(),
+            status.dor().bit_is_set(),
+            // The parity error flag is bit 2 on all devices, but the ATmega8 PAC does not name it.
+            status.bits() & (1 << 2) != 0,
+        ) {
+            Some(e) => Err(avr_hal_generic::nb::Error::Other(e)),
+            None => Ok(byte),
+        }
+    }
+
+    fn raw_read_ready(&mut self) -> bool {
+        self.ucsra.read().rxc().bit_is_set()
+    }
+
+    fn raw_tx_complete(&mut self) -> bool {
+        self.ucsra.read().txc().bit_is_set()
+    }
+
+    fn raw_clear_tx_complete(&mut self) {
+        // TXC is cleared by writing a one.  Only U2X and MPCM are preserved as the other flags
+        // must be written as zero.
+        self.ucsra
+            .modify(|r, w| unsafe { w.bits(r.bits() & 0x03 | 0x40) });
+    }
+
+    fn raw_write_9bit(
+        &mut self,
+        word: u16,
+    ) -> avr_hal_generic::nb::Result<(), core::convert::Infallible> {
+        // Call flush to make sure the data-register is empty
+        self.raw_flush()?;
+
+        // The 9th bit must be written before the low byte.
+        self.ucsrb.modify(|_, w| w.txb8().bit(word & 0x100 != 0));
+        self.udr.write(|w| w.bits(word as u8));
+        Ok(())
+    }
+
+    fn raw_read_9bit(&mut self) -> avr_hal_generic::nb::Result<u16, crate::usart::Error> {
+        let status = self.ucsra.read();
+        if status.rxc().bit_is_clear() {
             return Err(avr_hal_generic::nb::Error::WouldBlock);
         }
 
-        Ok(self.udr.read().bits())
+        // The error flags and the 9th bit must be read before the low byte.
+        let high = self.ucsrb.read().rxb8().bit() as u16;
+        let word = high << 8 | self.udr.read().bits() as u16;
+        match crate::usart::Error::from_flags(
+            status.fe().bit_is_set(),
+            status.dor().bit_is_set(),
+            // The parity error flag is bit 2 on all devices, but the ATmega8 PAC does not name it.
+            status.bits() & (1 << 2) != 0,
+        ) {
+            Some(e) => Err(avr_hal_generic::nb::Error::Other(e)),
+            None => Ok(word),
+        }
     }
 
     fn raw_interrupt(&mut self, event: crate::usart::Event, state: bool) {
@@ -267,7 +363,9 @@ impl
         crate::port::Pin<crate::port::mode::Output, port::PD3>,
     > for crate::pac::USART1
 {
-    fn raw_init<CLOCK>(&mut self, baudrate: crate::usart::Baudrate<CLOCK>) {
+    fn raw_init<CLOCK>(&mut self, settings: crate::usart::UsartSettings<CLOCK>) {
+        let baudrate = settings.baudrate;
+        let frame = settings.frame_format;
         let ubrr1h: u8 = (baudrate.ubrr >> 8) as u8;
         let ubrr1l: u8 = baudrate.ubrr as u8;
         self.ubrr1h.write(|w| w.bits(ubrr1h));
@@ -279,17 +377,28 @@ impl
         self.ucsr1b.write(|w| w
             .txen1().set_bit()
             .rxen1().set_bit()
+            .ucsz12().bit(frame.data_bits == DataBits::Nine)
         );
 
-        // Set frame format to 8n1 for now.  At some point, this should be made
-        // configurable, similar to what is done in other HALs.
-        #[rustfmt::skip]
-        self.ucsr1c.write(|w| w
-            .umsel1().usart_async()
-            .ucsz1().chr8()
-            .usbs1().stop1()
-            .upm1().disabled()
-        );
+        self.ucsr1c.write(|w| {
+            let w = w.umsel1().usart_async();
+            let w = match frame.data_bits {
+                DataBits::Five => w.ucsz1().chr5(),
+                DataBits::Six => w.ucsz1().chr6(),
+                DataBits::Seven => w.ucsz1().chr7(),
+                // 9-bit frames are selected by the additional UCSZn2 bit.
+                DataBits::Eight | DataBits::Nine => w.ucsz1().chr8(),
+            };
+            let w = match frame.stop_bits {
+                StopBits::One => w.usbs1().stop1(),
+                StopBits::Two => w.usbs1().stop2(),
+            };
+            match frame.parity {
+                Parity::None => w.upm1().disabled(),
+                Parity::Even => w.upm1().parity_even(),
+                Parity::Odd => w.upm1().parity_odd(),
+            }
+        });
     }
 
     fn raw_deinit(&mut self) {
@@ -317,12 +426,70 @@ impl
         Ok(())
     }
 
-    fn raw_read(&mut self) -> avr_hal_generic::nb::Result<u8, core::convert::Infallible> {
-        if self.ucsr1a.read().rxc1().bit_is_clear() {
+    fn raw_read(&mut self) -> avr_hal_generic::nb::Result<u8, crate::usart::Error> {
+        let status = self.ucsr1a.read();
+        if status.rxc1().bit_is_clear() {
+            return Err(avr_hal_generic::nb::Error::WouldBlock);
+        }
+
+        // The error flags belong to the byte in the receive buffer, so they must be read before
+        // it.  Reading the data register clears them again.
+        let byte = self.udr1.read().bits();
+        match crate::usart::Error::from_flags(
+            status.fe1().bit_is_set(),
+            status.dor1().bit_is_set(),
+            status.upe1().bit_is_set(),
+        ) {
+            Some(e) => Err(avr_hal_generic::nb::Error::Other(e)),
+            None => Ok(byte),
+        }
+    }
+
+    fn raw_read_ready(&mut self) -> bool {
+        self.ucsr1a.read().rxc1().bit_is_set()
+    }
+
+    fn raw_tx_complete(&mut self) -> bool {
+        self.ucsr1a.read().txc1().bit_is_set()
+    }
+
+    fn raw_clear_tx_complete(&mut self) {
+        // TXC is cleared by writing a one.  Only U2X and MPCM are preserved as the other flags
+        // must be written as zero.
+        self.ucsr1a
+            .modify(|r, w| unsafe { w.bits(r.bits() & 0x03 | 0x40) });
+    }
+
+    fn raw_write_9bit(
+        &mut self,
+        word: u16,
+    ) -> avr_hal_generic::nb::Result<(), core::convert::Infallible> {
+        // Call flush to make sure the data-register is empty
+        self.raw_flush()?;
+
+        // The 9th bit must be written before the low byte.
+        self.ucsr1b.modify(|_, w| w.txb81().bit(word & 0x100 != 0));
+        self.udr1.write(|w| w.bits(word as u8));
+        Ok(())
+    }
+
+    fn raw_read_9bit(&mut self) -> avr_hal_generic::nb::Result<u16, crate::usart::Error> {
+        let status = self.ucsr1a.read();
+        if status.rxc1().bit_is_clear() {
             return Err(avr_hal_generic::nb::Error::WouldBlock);
         }
 
-        Ok(self.udr1.read().bits())
+        // The error flags and the 9th bit must be read before the low byte.
+        let high = self.ucsr1b.read().rxb81().bit() as u16;
+        let word = high << 8 | self.udr1.read().bits() as u16;
+        match crate::usart::Error::from_flags(
+            status.fe1().bit_is_set(),
+            status.dor1().bit_is_set(),
+            status.upe1().bit_is_set(),
+        ) {
+            Some(e) => Err(avr_hal_generic::nb::Error::Other(e)),
+            None => Ok(word),
+        }
     }
 
     fn raw_interrupt(&mut self, event: crate::usart::Event, state: bool) {
@@ -347,7 +514,9 @@ impl
         crate::port::Pin<crate::port::mode::Output, port::PE1>,
     > for crate::pac::USART0
 {
-    fn raw_init<CLOCK>(&mut self, baudrate: crate::usart::Baudrate<CLOCK>) {
+    fn raw_init<CLOCK>(&mut self, settings: crate::usart::UsartSettings<CLOCK>) {
+        let baudrate = settings.baudrate;
+        let frame = settings.frame_format;
         let ubrr0h: u8 = (baudrate.ubrr >> 8) as u8;
         let ubrr0l: u8 = baudrate.ubrr as u8;
         self.ubrr0h.write(|w| w.bits(ubrr0h));
@@ -355,17 +524,32 @@ impl
         self.ucsr0a.write(|w| w.u2x0().bit(baudrate.u2x));
 
         // Enable receiver and transmitter but leave interrupts disabled.
-        self.ucsr0b.write(|w| w.txen0().set_bit().rxen0().set_bit());
-
-        // Set frame format to 8n1 for now.  At some point, this should be made
-        // configurable, similar to what is done in other HALs.
         #[rustfmt::skip]
-        self.ucsr0c.write(|w| w
-            .umsel0().usart_async()
-            .ucsz0().chr8()
-            .usbs0().stop1()
-            .upm0().disabled()
+        self.ucsr0b.write(|w| w
+            .txen0().set_bit()
+            .rxen0().set_bit()
+            .ucsz02().bit(frame.data_bits == DataBits::Nine)
         );
+
+        self.ucsr0c.write(|w| {
+            let w = w.umsel0().usart_async();
+            let w = match frame.data_bits {
+                DataBits::Five => w.ucsz0().chr5(),
+                DataBits::Six => w.ucsz0().chr6(),
+                DataBits::Seven => w.ucsz0().chr7(),
+                // 9-bit frames are selected by the additional UCSZn2 bit.
+                DataBits::Eight | DataBits::Nine => w.ucsz0().chr8(),
+            };
+            let w = match frame.stop_bits {
+                StopBits::One => w.usbs0().stop1(),
+                StopBits::Two => w.usbs0().stop2(),
+            };
+            match frame.parity {
+                Parity::None => w.upm0().disabled(),
+                Parity::Even => w.upm0().parity_even(),
+                Parity::Odd => w.upm0().parity_odd(),
+            }
+        });
     }
 
     fn raw_deinit(&mut self) {
@@ -393,12 +577,70 @@ impl
         Ok(())
     }
 
-    fn raw_read(&mut self) -> avr_hal_generic::nb::Result<u8, core::convert::Infallible> {
-        if self.ucsr0a.read().rxc0().bit_is_clear() {
+    fn raw_read(&mut self) -> avr_hal_generic::nb::Result<u8, crate::usart::Error> {
+        let status = self.ucsr0a.read();
+        if status.rxc0().bit_is_clear() {
             return Err(avr_hal_generic::nb::Error::WouldBlock);
         }
 
-        Ok(self.udr0.read().bits())
+        // The error flags belong to the byte in the receive buffer, so they must be read before
+        // it.  Reading the data register clears them again.
+        let byte = self.udr0.read().bits();
+        match crate::usart::Error::from_flags(
+            status.fe0().bit_is_set(),
+            status.dor0().bit_is_set(),
+            status.upe0().bit_is_set(),
+        ) {
+            Some(e) => Err(avr_hal_generic::nb::Error::Other(e)),
+            None => Ok(byte),
+        }
+    }
+
+    fn raw_read_ready(&mut self) -> bool {
+        self.ucsr0a.read().rxc0().bit_is_set()
+    }
+
+    fn raw_tx_complete(&mut self) -> bool {
+        self.ucsr0a.read().txc0().bit_is_set()
+    }
+
+    fn raw_clear_tx_complete(&mut self) {
+        // TXC is cleared by writing a one.  Only U2X and MPCM are preserved as the other flags
+        // must be written as zero.
+        self.ucsr0a
+            .modify(|r, w| unsafe { w.bits(r.bits() & 0x03 | 0x40) });
+    }
+
+    fn raw_write_9bit(
+        &mut self,
+        word: u16,
+    ) -> avr_hal_generic::nb::Result<(), core::convert::Infallible> {
+        // Call flush to make sure the data-register is empty
+        self.raw_flush()?;
+
+        // The 9th bit must be written before the low byte.
+        self.ucsr0b.modify(|_, w| w.txb80().bit(word & 0x100 != 0));
+        self.udr0.write(|w| w.bits(word as u8));
+        Ok(())
+    }
+
+    fn raw_read_9bit(&mut self) -> avr_hal_generic::nb::Result<u16, crate::usart::Error> {
+        let status = self.ucsr0a.read();
+        if status.rxc0().bit_is_clear() {
+            return Err(avr_hal_generic::nb::Error::WouldBlock);
+        }
+
+        // The error flags and the 9th bit must be read before the low byte.
+        let high = self.ucsr0b.read().rxb80().bit() as u16;
+        let word = high << 8 | self.udr0.read().bits() as u16;
+        match crate::usart::Error::from_flags(
+            status.fe0().bit_is_set(),
+            status.dor0().bit_is_set(),
+            status.upe0().bit_is_set(),
+        ) {
+            Some(e) => Err(avr_hal_generic::nb::Error::Other(e)),
+            None => Ok(word),
+        }
     }
 
     fn raw_interrupt(&mut self, event: crate::usart::Event, state: bool) {
@@ -411,3 +653,100 @@ impl
         }
     }
 }
+
+pub type UsartSpi<USART, SCLKPIN, MOSIPIN, MISOPIN> =
+    avr_hal_generic::usart_spi::UsartSpi<crate::Atmega, USART, SCLKPIN, MOSIPIN, MISOPIN>;
+
+#[cfg(any(
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+pub type Usart0Spi = UsartSpi<crate::pac::USART0, port::PD4, port::PD1, port::PD0>;
+#[cfg(any(
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART0,
+    register_suffix: 0,
+    sclk: port::PD4,
+    mosi: port::PD1,
+    miso: port::PD0,
+}
+
+#[cfg(any(feature = "atmega1284p", feature = "atmega164pa"))]
+pub type Usart0Spi = UsartSpi<crate::pac::USART0, port::PB0, port::PD1, port::PD0>;
+#[cfg(any(feature = "atmega1284p", feature = "atmega164pa"))]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART0,
+    register_suffix: 0,
+    sclk: port::PB0,
+    mosi: port::PD1,
+    miso: port::PD0,
+}
+
+#[cfg(any(feature = "atmega1284p", feature = "atmega164pa"))]
+pub type Usart1Spi = UsartSpi<crate::pac::USART1, port::PD4, port::PD3, port::PD2>;
+#[cfg(any(feature = "atmega1284p", feature = "atmega164pa"))]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART1,
+    register_suffix: 1,
+    sclk: port::PD4,
+    mosi: port::PD3,
+    miso: port::PD2,
+}
+
+#[cfg(any(feature = "atmega32u4", feature = "atmega1280", feature = "atmega2560"))]
+pub type Usart1Spi = UsartSpi<crate::pac::USART1, port::PD5, port::PD3, port::PD2>;
+#[cfg(any(feature = "atmega32u4", feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART1,
+    register_suffix: 1,
+    sclk: port::PD5,
+    mosi: port::PD3,
+    miso: port::PD2,
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Usart0Spi = UsartSpi<crate::pac::USART0, port::PE2, port::PE1, port::PE0>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART0,
+    register_suffix: 0,
+    sclk: port::PE2,
+    mosi: port::PE1,
+    miso: port::PE0,
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Usart2Spi = UsartSpi<crate::pac::USART2, port::PH2, port::PH1, port::PH0>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART2,
+    register_suffix: 2,
+    sclk: port::PH2,
+    mosi: port::PH1,
+    miso: port::PH0,
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Usart3Spi = UsartSpi<crate::pac::USART3, port::PJ2, port::PJ1, port::PJ0>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART3,
+    register_suffix: 3,
+    sclk: port::PJ2,
+    mosi: port::PJ1,
+    miso: port::PJ0,
+}
@@ -0,0 +1,49 @@
+//! I2C
+//!
+//! This crate does not provide a driver for the TWI peripheral (found on the ATtiny88).  On
+//! devices with a USI (ATtiny84, ATtiny85, ATtiny2313), it is used for the [`I2c`] controller and
+//! the [`I2cTarget`].  Otherwise, an I2C bus can be driven from any two pins using the bit-banged
+//! [`SoftI2c`].
+//!
+//! # Example
+//!
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//! let pins = attiny_hal::pins!(dp);
+//!
+//! type CoreClock = attiny_hal::clock::MHz8;
+//!
+//! let mut i2c = attiny_hal::i2c::SoftI2c::<CoreClock, _, _>::new(
+//!     pins.pb0.into_opendrain_high(),
+//!     pins.pb2.into_opendrain_high(),
+//!     50_000,
+//! );
+//!
+//! i2c.i2cdetect(&mut serial, attiny_hal::i2c::Direction::Read).unwrap();
+//! ```
+
+#[allow(unused_imports)]
+use crate::port;
+pub use avr_hal_generic::i2c::{Direction, Error, TargetEvent};
+pub use avr_hal_generic::soft_i2c::SoftI2c;
+
+#[cfg(feature = "attiny84")]
+pub type I2c<CLOCK> =
+    avr_hal_generic::usi::UsiI2c<crate::Attiny, crate::pac::USI, port::PA6, port::PA4, CLOCK>;
+#[cfg(feature = "attiny84")]
+pub type I2cTarget =
+    avr_hal_generic::usi::UsiI2cTarget<crate::Attiny, crate::pac::USI, port::PA6, port::PA4>;
+
+#[cfg(feature = "attiny85")]
+pub type I2c<CLOCK> =
+    avr_hal_generic::usi::UsiI2c<crate::Attiny, crate::pac::USI, port::PB0, port::PB2, CLOCK>;
+#[cfg(feature = "attiny85")]
+pub type I2cTarget =
+    avr_hal_generic::usi::UsiI2cTarget<crate::Attiny, crate::pac::USI, port::PB0, port::PB2>;
+
+#[cfg(feature = "attiny2313")]
+pub type I2c<CLOCK> =
+    avr_hal_generic::usi::UsiI2c<crate::Attiny, crate::pac::USI, port::PB5, port::PB7, CLOCK>;
+#[cfg(feature = "attiny2313")]
+pub type I2cTarget =
+    avr_hal_generic::usi::UsiI2cTarget<crate::Attiny, crate::pac::USI, port::PB5, port::PB7>;
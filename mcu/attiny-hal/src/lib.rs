@@ -70,6 +70,14 @@ pub mod adc;
 #[cfg(all(feature = "device-selected", not(feature = "attiny2313")))]
 pub use adc::Adc;
 
+#[cfg(feature = "device-selected")]
+pub mod i2c;
+
+#[cfg(feature = "device-selected")]
+pub mod pcint;
+#[cfg(feature = "device-selected")]
+pub use pcint::Pcint;
+
 #[cfg(feature = "device-selected")]
 pub mod port;
 #[cfg(feature = "device-selected")]
@@ -78,6 +86,9 @@ pub use port::Pins;
 #[cfg(feature = "device-selected")]
 pub mod simple_pwm;
 
+#[cfg(feature = "device-selected")]
+pub mod timer;
+
 #[cfg(feature = "device-selected")]
 pub mod wdt;
 #[cfg(feature = "device-selected")]
@@ -93,6 +104,12 @@ pub mod spi;
 #[cfg(feature = "device-selected")]
 pub use spi::Spi;
 
+#[cfg(feature = "device-selected")]
+pub mod usart;
+
+#[cfg(any(feature = "attiny84", feature = "attiny85", feature = "attiny2313"))]
+pub mod usi;
+
 pub struct Attiny;
 
 #[cfg(feature = "attiny84")]
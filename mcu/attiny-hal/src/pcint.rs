@@ -0,0 +1,258 @@
+//! Pin change interrupts (`PCINTn`)
+//!
+//! # Example
+//!
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//! let pins = attiny_hal::pins!(dp);
+//!
+//! let mut pcint = Pcint::new(dp.EXINT);
+//! let button = pins.pb3.into_pull_up_input();
+//! pcint.enable(&button);
+//!
+//! // In the PCINT0 handler:
+//! let changes = pcint.changes(0);
+//! if changes.contains::<port::PB3>() {
+//!     // ...
+//! }
+//! ```
+
+#[allow(unused_imports)]
+use crate::port;
+pub use avr_hal_generic::pcint::{PcintOps, PcintPin};
+
+pub type Pcint = avr_hal_generic::pcint::Pcint<crate::Attiny, crate::pac::EXINT>;
+pub type Changes = avr_hal_generic::pcint::Changes<crate::Attiny, crate::pac::EXINT>;
+
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    pcicr: gimsk,
+    pcifr: gifr,
+    groups: {
+        0: {
+            pcie: 4,
+            pcmsk: pcmsk0,
+            inputs: (*crate::pac::PORTA::ptr()).pina.read().bits(),
+            pins: {
+                port::PA0: 0,
+                port::PA1: 1,
+                port::PA2: 2,
+                port::PA3: 3,
+                port::PA4: 4,
+                port::PA5: 5,
+                port::PA6: 6,
+                port::PA7: 7,
+            },
+        },
+        1: {
+            pcie: 5,
+            pcmsk: pcmsk1,
+            inputs: (*crate::pac::PORTB::ptr()).pinb.read().bits(),
+            pins: {
+                port::PB0: 0,
+                port::PB1: 1,
+                port::PB2: 2,
+                port::PB3: 3,
+            },
+        },
+    },
+}
+
+#[cfg(feature = "attiny85")]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    pcicr: gimsk,
+    pcifr: gifr,
+    groups: {
+        0: {
+            pcie: 5,
+            pcmsk: pcmsk,
+            inputs: (*crate::pac::PORTB::ptr()).pinb.read().bits(),
+            pins: {
+                port::PB0: 0,
+                port::PB1: 1,
+                port::PB2: 2,
+                port::PB3: 3,
+                port::PB4: 4,
+                port::PB5: 5,
+            },
+        },
+    },
+}
+
+#[cfg(feature = "attiny88")]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    pcicr: pcicr,
+    pcifr: pcifr,
+    groups: {
+        0: {
+            pcie: 0,
+            pcmsk: pcmsk0,
+            inputs: (*crate::pac::PORTB::ptr()).pinb.read().bits(),
+            pins: {
+                port::PB0: 0,
+                port::PB1: 1,
+                port::PB2: 2,
+                port::PB3: 3,
+                port::PB4: 4,
+                port::PB5: 5,
+                port::PB6: 6,
+                port::PB7: 7,
+            },
+        },
+        1: {
+            pcie: 1,
+            pcmsk: pcmsk1,
+            inputs: (*crate::pac::PORTC::ptr()).pinc.read().bits(),
+            pins: {
+                port::PC0: 0,
+                port::PC1: 1,
+                port::PC2: 2,
+                port::PC3: 3,
+                port::PC4: 4,
+                port::PC5: 5,
+                port::PC6: 6,
+                port::PC7: 7,
+            },
+        },
+        2: {
+            pcie: 2,
+            pcmsk: pcmsk2,
+            inputs: (*crate::pac::PORTD::ptr()).pind.read().bits(),
+            pins: {
+                port::PD0: 0,
+                port::PD1: 1,
+                port::PD2: 2,
+                port::PD3: 3,
+                port::PD4: 4,
+                port::PD5: 5,
+                port::PD6: 6,
+                port::PD7: 7,
+            },
+        },
+        3: {
+            pcie: 3,
+            pcmsk: pcmsk3,
+            inputs: (*crate::pac::PORTA::ptr()).pina.read().bits(),
+            pins: {
+                port::PA0: 0,
+                port::PA1: 1,
+                port::PA2: 2,
+                port::PA3: 3,
+            },
+        },
+    },
+}
+
+#[cfg(feature = "attiny167")]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    pcicr: pcicr,
+    pcifr: pcifr,
+    groups: {
+        0: {
+            pcie: 0,
+            pcmsk: pcmsk0,
+            inputs: (*crate::pac::PORTA::ptr()).pina.read().bits(),
+            pins: {
+                port::PA0: 0,
+                port::PA1: 1,
+                port::PA2: 2,
+                port::PA3: 3,
+                port::PA4: 4,
+                port::PA5: 5,
+                port::PA6: 6,
+                port::PA7: 7,
+            },
+        },
+        1: {
+            pcie: 1,
+            pcmsk: pcmsk1,
+            inputs: (*crate::pac::PORTB::ptr()).pinb.read().bits(),
+            pins: {
+                port::PB0: 0,
+                port::PB1: 1,
+                port::PB2: 2,
+                port::PB3: 3,
+                port::PB4: 4,
+                port::PB5: 5,
+                port::PB6: 6,
+                port::PB7: 7,
+            },
+        },
+    },
+}
+
+// On the ATtiny2313, the mask register `PCMSK` is part of the `CPU` peripheral and the flag is in
+// `EIFR`, so `impl_pcint!` cannot be used here.
+#[cfg(feature = "attiny2313")]
+impl PcintOps<crate::Attiny> for crate::pac::EXINT {
+    fn raw_interrupt(&mut self, group: u8, state: bool) {
+        if group == 0 {
+            self.gimsk.modify(|_, w| w.pcie().bit(state));
+        }
+    }
+
+    fn raw_read_mask(&self, group: u8) -> u8 {
+        match group {
+            0 => unsafe { &*crate::pac::CPU::ptr() }.pcmsk.read().bits(),
+            _ => 0,
+        }
+    }
+
+    fn raw_write_mask(&mut self, group: u8, mask: u8) {
+        if group == 0 {
+            unsafe { &*crate::pac::CPU::ptr() }
+                .pcmsk
+                .write(|w| w.bits(mask));
+        }
+    }
+
+    fn raw_is_pending(&self, group: u8) -> bool {
+        group == 0 && self.eifr.read().pcif().bit_is_set()
+    }
+
+    fn raw_clear_pending(&mut self, group: u8) {
+        // Flags are cleared by writing a one, all others must be written as zero.
+        if group == 0 {
+            self.eifr.write(|w| w.pcif().set_bit());
+        }
+    }
+
+    fn raw_read_inputs(&self, group: u8) -> u8 {
+        match group {
+            0 => unsafe { &*crate::pac::PORTB::ptr() }.pinb.read().bits(),
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(feature = "attiny2313")]
+macro_rules! impl_pcint_pins {
+    ($($pin:ident: $bit:literal,)+) => {
+        $(
+            impl PcintPin<crate::Attiny, crate::pac::EXINT> for port::$pin {
+                const GROUP: u8 = 0;
+                const BIT: u8 = $bit;
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "attiny2313")]
+impl_pcint_pins! {
+    PB0: 0,
+    PB1: 1,
+    PB2: 2,
+    PB3: 3,
+    PB4: 4,
+    PB5: 5,
+    PB6: 6,
+    PB7: 7,
+}
@@ -1,4 +1,4 @@
-pub use avr_hal_generic::simple_pwm::{IntoPwmPin, Prescaler, PwmPinOps};
+pub use avr_hal_generic::simple_pwm::{IntoPwmPin, Prescaler, PwmMode, PwmPinOps};
 
 #[cfg(any(feature = "attiny85", feature = "attiny84", feature = "attiny88"))]
 use crate::port::*;
@@ -18,6 +18,12 @@ avr_hal_generic::impl_simple_pwm! {
                 Prescaler::Prescale1024 => w.cs0().prescale_1024(),
             });
         },
+        mode: |tim, mode| {
+            tim.tccr0a.modify(|_r, w| match mode {
+                PwmMode::Fast => w.wgm0().pwm_fast(),
+                PwmMode::PhaseCorrect => w.wgm0().pwm_phase(),
+            });
+        },
         pins: {
             PB2: {
                 ocr: ocr0a,
@@ -26,6 +32,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr0a.modify(|_r, w| w.com0a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr0a.modify(|_r, w| w.com0a().match_set());
+                },
             },
 
             PA7: {
@@ -35,6 +44,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr0a.modify(|_r, w| w.com0b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr0a.modify(|_r, w| w.com0b().match_set());
+                },
             },
         },
     }
@@ -57,6 +69,13 @@ avr_hal_generic::impl_simple_pwm! {
                 Prescaler::Prescale1024 => w.cs1().prescale_1024(),
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr1b.modify(|_, w| w.wgm1().bits(wgm));
+        },
         pins: {
             PA6: {
                 ocr: ocr1a,
@@ -65,6 +84,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_, w| w.com1a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_, w| w.com1a().bits(0b11));
+                },
             },
 
             PA5: {
@@ -74,6 +96,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_, w| w.com1b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_, w| w.com1b().bits(0b11));
+                },
             },
         },
     }
@@ -105,6 +130,12 @@ avr_hal_generic::impl_simple_pwm! {
                 Prescaler::Prescale1024 => w.cs0().prescale_1024(),
             });
         },
+        mode: |tim, mode| {
+            tim.tccr0a.modify(|_r, w| match mode {
+                PwmMode::Fast => w.wgm0().pwm_fast(),
+                PwmMode::PhaseCorrect => w.wgm0().pwm_phase(),
+            });
+        },
         pins: {
             PB0: {
                 ocr: ocr0a,
@@ -113,6 +144,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr0a.modify(|_r, w| w.com0a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr0a.modify(|_r, w| w.com0a().match_set());
+                },
             },
 
             PB1: {
@@ -122,6 +156,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr0a.modify(|_r, w| w.com0b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr0a.modify(|_r, w| w.com0b().match_set());
+                },
             },
         },
     }
@@ -131,6 +168,8 @@ avr_hal_generic::impl_simple_pwm! {
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC1` for PWM (pins `PB4`)
     ///
+    /// `TC1` only supports a single PWM mode, so there is no `with_mode()` for this timer.
+    ///
     /// # Example
     /// ```
     /// let mut timer1 = Timer1Pwm::new(dp.TC1, Prescaler::Prescale64);
@@ -161,6 +200,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.gtccr.modify(|_, w| w.com1b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.gtccr.modify(|_, w| w.com1b().bits(0b11));
+                },
             },
         },
     }
@@ -194,6 +236,13 @@ avr_hal_generic::impl_simple_pwm! {
                 Prescaler::Prescale1024 => w.cs1().prescale_1024(),
             });
         },
+        mode: |tim, mode| {
+            let wgm = match mode {
+                PwmMode::Fast => 0b01,
+                PwmMode::PhaseCorrect => 0b00,
+            };
+            tim.tccr1b.modify(|_, w| w.wgm1().bits(wgm));
+        },
         pins: {
             PB1: {
                 ocr: ocr1a,
@@ -202,6 +251,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_, w| w.com1a().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_, w| w.com1a().bits(0b11));
+                },
             },
 
             PB2: {
@@ -211,6 +263,9 @@ avr_hal_generic::impl_simple_pwm! {
                 } else {
                     tim.tccr1a.modify(|_, w| w.com1b().disconnected());
                 },
+                inverted: |tim| {
+                    tim.tccr1a.modify(|_, w| w.com1b().bits(0b11));
+                },
             },
         },
     }
@@ -31,8 +31,37 @@
 
 #[allow(unused_imports)]
 use crate::port;
+pub use avr_hal_generic::soft_spi::SoftSpi;
 pub use avr_hal_generic::spi::*;
 
+#[cfg(feature = "attiny84")]
+pub type UsiSpi<CLOCK> = avr_hal_generic::usi::UsiSpi<
+    crate::Attiny,
+    crate::pac::USI,
+    port::PA4,
+    port::PA5,
+    port::PA6,
+    CLOCK,
+>;
+#[cfg(feature = "attiny85")]
+pub type UsiSpi<CLOCK> = avr_hal_generic::usi::UsiSpi<
+    crate::Attiny,
+    crate::pac::USI,
+    port::PB2,
+    port::PB1,
+    port::PB0,
+    CLOCK,
+>;
+#[cfg(feature = "attiny2313")]
+pub type UsiSpi<CLOCK> = avr_hal_generic::usi::UsiSpi<
+    crate::Attiny,
+    crate::pac::USI,
+    port::PB7,
+    port::PB6,
+    port::PB5,
+    CLOCK,
+>;
+
 #[cfg(feature = "attiny88")]
 pub type Spi = avr_hal_generic::spi::Spi<
     crate::Attiny,
@@ -43,6 +72,35 @@ pub type Spi = avr_hal_generic::spi::Spi<
     port::PB2,
 >;
 #[cfg(feature = "attiny88")]
+pub type IsrSpi<const N: usize> = avr_hal_generic::spi::IsrSpi<
+    crate::Attiny,
+    crate::pac::SPI,
+    port::PB5,
+    port::PB3,
+    port::PB4,
+    port::PB2,
+    N,
+>;
+#[cfg(feature = "attiny88")]
+pub type SpiPeripheral = avr_hal_generic::spi::SpiPeripheral<
+    crate::Attiny,
+    crate::pac::SPI,
+    port::PB5,
+    port::PB3,
+    port::PB4,
+    port::PB2,
+>;
+#[cfg(feature = "attiny88")]
+pub type BufferedSpiPeripheral<const N: usize> = avr_hal_generic::spi::BufferedSpiPeripheral<
+    crate::Attiny,
+    crate::pac::SPI,
+    port::PB5,
+    port::PB3,
+    port::PB4,
+    port::PB2,
+    N,
+>;
+#[cfg(feature = "attiny88")]
 avr_hal_generic::impl_spi! {
     hal: crate::Attiny,
     peripheral: crate::pac::SPI,
@@ -62,6 +120,35 @@ pub type Spi = avr_hal_generic::spi::Spi<
     port::PA6,
 >;
 #[cfg(feature = "attiny167")]
+pub type IsrSpi<const N: usize> = avr_hal_generic::spi::IsrSpi<
+    crate::Attiny,
+    crate::pac::SPI,
+    port::PA5,
+    port::PA4,
+    port::PA2,
+    port::PA6,
+    N,
+>;
+#[cfg(feature = "attiny167")]
+pub type SpiPeripheral = avr_hal_generic::spi::SpiPeripheral<
+    crate::Attiny,
+    crate::pac::SPI,
+    port::PA5,
+    port::PA4,
+    port::PA2,
+    port::PA6,
+>;
+#[cfg(feature = "attiny167")]
+pub type BufferedSpiPeripheral<const N: usize> = avr_hal_generic::spi::BufferedSpiPeripheral<
+    crate::Attiny,
+    crate::pac::SPI,
+    port::PA5,
+    port::PA4,
+    port::PA2,
+    port::PA6,
+    N,
+>;
+#[cfg(feature = "attiny167")]
 avr_hal_generic::impl_spi! {
     hal: crate::Attiny,
     peripheral: crate::pac::SPI,
@@ -0,0 +1,178 @@
+//! Timer/Counter
+//!
+//! # Example
+//!
+//! For full source code, please refer to the Arduino Uno timer example:
+//! [`uno-timer.rs`](https://github.com/Rahix/avr-hal/blob/main/examples/arduino-uno/src/bin/uno-timer.rs)
+//!
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//!
+//! let mut timer = Timer0::<crate::CoreClock>::new(dp.TC0);
+//!
+//! // Let the TIMER0_COMPA interrupt fire every millisecond
+//! timer.start_ctc_us(1_000).unwrap();
+//! timer.listen(Event::CompareMatchA);
+//! ```
+
+pub use avr_hal_generic::timer::*;
+
+pub type Timer<TC, CLOCK> = avr_hal_generic::timer::Timer<crate::Attiny, TC, CLOCK>;
+
+#[cfg(feature = "attiny84")]
+pub type Timer0<CLOCK> = Timer<crate::pac::TC0, CLOCK>;
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    tcnt: tcnt0,
+    ocr: [ocr0a, ocr0b],
+    timsk: timsk0 {
+        overflow: toie0,
+        compare_a: ocie0a,
+        compare_b: ocie0b,
+    },
+    tifr: tifr0 {
+        overflow: tov0,
+        compare_a: ocf0a,
+        compare_b: ocf0b,
+    },
+    setup: |tim, mode, prescaler| {
+        tim.tccr0a.write(|w| match mode {
+            Mode::Normal => w.wgm0().normal_top(),
+            Mode::Ctc => w.wgm0().ctc(),
+        });
+        tim.tccr0b.write(|w| match prescaler {
+            Prescaler::Direct => w.cs0().direct(),
+            Prescaler::Prescale8 => w.cs0().prescale_8(),
+            Prescaler::Prescale64 => w.cs0().prescale_64(),
+            Prescaler::Prescale256 => w.cs0().prescale_256(),
+            Prescaler::Prescale1024 => w.cs0().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr0b.modify(|_r, w| w.cs0().no_clock());
+    },
+}
+
+#[cfg(feature = "attiny84")]
+pub type Timer1<CLOCK> = Timer<crate::pac::TC1, CLOCK>;
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    tcnt: tcnt1,
+    ocr: [ocr1a, ocr1b],
+    timsk: timsk1 {
+        overflow: toie1,
+        compare_a: ocie1a,
+        compare_b: ocie1b,
+    },
+    tifr: tifr1 {
+        overflow: tov1,
+        compare_a: ocf1a,
+        compare_b: ocf1b,
+    },
+    setup: |tim, mode, prescaler| {
+        let wgm = match mode {
+            Mode::Normal => 0b00,
+            Mode::Ctc => 0b01,
+        };
+        tim.tccr1a.write(|w| w.wgm1().bits(0b00));
+        tim.tccr1b.write(|w| {
+            w.wgm1().bits(wgm);
+
+            match prescaler {
+                Prescaler::Direct => w.cs1().direct(),
+                Prescaler::Prescale8 => w.cs1().prescale_8(),
+                Prescaler::Prescale64 => w.cs1().prescale_64(),
+                Prescaler::Prescale256 => w.cs1().prescale_256(),
+                Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+            }
+        });
+    },
+    stop: |tim| {
+        tim.tccr1b.modify(|_r, w| w.cs1().no_clock());
+    },
+}
+
+#[cfg(feature = "attiny85")]
+pub type Timer0<CLOCK> = Timer<crate::pac::TC0, CLOCK>;
+#[cfg(feature = "attiny85")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    tcnt: tcnt0,
+    ocr: [ocr0a, ocr0b],
+    timsk: timsk {
+        overflow: toie0,
+        compare_a: ocie0a,
+        compare_b: ocie0b,
+    },
+    tifr: tifr {
+        overflow: tov0,
+        compare_a: ocf0a,
+        compare_b: ocf0b,
+    },
+    setup: |tim, mode, prescaler| {
+        tim.tccr0a.write(|w| match mode {
+            Mode::Normal => w.wgm0().normal_top(),
+            Mode::Ctc => w.wgm0().ctc(),
+        });
+        tim.tccr0b.write(|w| match prescaler {
+            Prescaler::Direct => w.cs0().direct(),
+            Prescaler::Prescale8 => w.cs0().prescale_8(),
+            Prescaler::Prescale64 => w.cs0().prescale_64(),
+            Prescaler::Prescale256 => w.cs0().prescale_256(),
+            Prescaler::Prescale1024 => w.cs0().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr0b.modify(|_r, w| w.cs0().no_clock());
+    },
+}
+
+#[cfg(feature = "attiny88")]
+pub type Timer1<CLOCK> = Timer<crate::pac::TC1, CLOCK>;
+#[cfg(feature = "attiny88")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    tcnt: tcnt1,
+    ocr: [ocr1a, ocr1b],
+    timsk: timsk1 {
+        overflow: toie1,
+        compare_a: ocie1a,
+        compare_b: ocie1b,
+    },
+    tifr: tifr1 {
+        overflow: tov1,
+        compare_a: ocf1a,
+        compare_b: ocf1b,
+    },
+    setup: |tim, mode, prescaler| {
+        let wgm = match mode {
+            Mode::Normal => 0b00,
+            Mode::Ctc => 0b01,
+        };
+        tim.tccr1a.write(|w| w.wgm1().bits(0b00));
+        tim.tccr1b.write(|w| {
+            w.wgm1().bits(wgm);
+
+            match prescaler {
+                Prescaler::Direct => w.cs1().direct(),
+                Prescaler::Prescale8 => w.cs1().prescale_8(),
+                Prescaler::Prescale64 => w.cs1().prescale_64(),
+                Prescaler::Prescale256 => w.cs1().prescale_256(),
+                Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+            }
+        });
+    },
+    stop: |tim| {
+        tim.tccr1b.modify(|_r, w| w.cs1().no_clock());
+    },
+}
@@ -0,0 +1,398 @@
+//! USART
+//!
+//! The ATtiny2313 has a traditional USART (`Usart0`).  The ATtiny167 has a LIN/UART
+//! (`LinUart`), which can additionally send LIN headers using [`Usart::send_lin_header`].  On
+//! all other devices, the bit-banged [`SoftUart`] can be used instead.
+//!
+//! *Note: [ufmt](https://crates.io/crates/ufmt/) is used instead of `core::fmt` because
+//! `core::fmt` code quickly grows too large for AVR platforms.*
+//!
+//! # Example
+//!
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//! let pins = attiny_hal::pins!(dp);
+//!
+//! type CoreClock = attiny_hal::clock::MHz8;
+//!
+//! let mut serial = attiny_hal::usart::Usart0::<CoreClock>::new(
+//!     dp.USART,
+//!     pins.pd0,
+//!     pins.pd1.into_output(),
+//!     Baudrate::<CoreClock>::new(9600),
+//! );
+//!
+//! ufmt::uwriteln!(&mut serial, "Hello from ATtiny!").unwrap();
+//! ```
+
+#[allow(unused_imports)]
+use crate::port;
+pub use avr_hal_generic::soft_uart::{BufferedSoftUart, SoftUart, SoftUartBuffers};
+pub use avr_hal_generic::usart::*;
+
+pub type Usart<USART, RX, TX, CLOCK> =
+    avr_hal_generic::usart::Usart<crate::Attiny, USART, RX, TX, CLOCK>;
+pub type UsartWriter<USART, RX, TX, CLOCK> =
+    avr_hal_generic::usart::UsartWriter<crate::Attiny, USART, RX, TX, CLOCK>;
+pub type UsartReader<USART, RX, TX, CLOCK> =
+    avr_hal_generic::usart::UsartReader<crate::Attiny, USART, RX, TX, CLOCK>;
+
+#[cfg(feature = "attiny2313")]
+pub type Usart0<CLOCK> = Usart<
+    crate::pac::USART,
+    port::Pin<port::mode::Input, port::PD0>,
+    port::Pin<port::mode::Output, port::PD1>,
+    CLOCK,
+>;
+
+// The baudrate registers of the ATtiny2313 are not adjacent (UBRRH and UBRRL are exposed as
+// separate registers) so `impl_usart_traditional!` cannot be used here.
+#[cfg(feature = "attiny2313")]
+impl
+    crate::usart::UsartOps<
+        crate::Attiny,
+        crate::port::Pin<crate::port::mode::Input, port::PD0>,
+        crate::port::Pin<crate::port::mode::Output, port::PD1>,
+    > for crate::pac::USART
+{
+    fn raw_init<CLOCK>(&mut self, settings: crate::usart::UsartSettings<CLOCK>) {
+        let baudrate = settings.baudrate;
+        let frame = settings.frame_format;
+        self.ubrrh.write(|w| w.bits((baudrate.ubrr >> 8) as u8));
+        self.ubrrl.write(|w| w.bits(baudrate.ubrr as u8));
+        self.ucsra.write(|w| w.u2x().bit(baudrate.u2x));
+
+        // Enable receiver and transmitter but leave interrupts disabled.
+        #[rustfmt::skip]
+        self.ucsrb.write(|w| w
+            .txen().set_bit()
+            .rxen().set_bit()
+            .ucsz2().bit(frame.data_bits == DataBits::Nine)
+        );
+
+        // UCSRC: Asynchronous mode (UMSEL = 0), UPM1:0, USBS and UCSZ1:0
+        let ucsz = match frame.data_bits {
+            DataBits::Five => 0b00,
+            DataBits::Six => 0b01,
+            DataBits::Seven => 0b10,
+            // 9-bit frames are selected by the additional UCSZ2 bit.
+            DataBits::Eight | DataBits::Nine => 0b11,
+        };
+        let usbs = match frame.stop_bits {
+            StopBits::One => 0b0,
+            StopBits::Two => 0b1,
+        };
+        let upm = match frame.parity {
+            Parity::None => 0b00,
+            Parity::Even => 0b10,
+            Parity::Odd => 0b11,
+        };
+        self.ucsrc
+            .write(|w| unsafe { w.bits(upm << 4 | usbs << 3 | ucsz << 1) });
+    }
+
+    fn raw_deinit(&mut self) {
+        // Wait for any ongoing transfer to finish.
+        avr_hal_generic::nb::block!(self.raw_flush()).ok();
+        self.ucsrb.reset();
+    }
+
+    fn raw_flush(&mut self) -> avr_hal_generic::nb::Result<(), core::convert::Infallible> {
+        if self.ucsra.read().udre().bit_is_clear() {
+            Err(avr_hal_generic::nb::Error::WouldBlock)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn raw_write(
+        &mut self,
+        byte: u8,
+    ) -> avr_hal_generic::nb::Result<(), core::convert::Infallible> {
+        // Call flush to make sure the data-register is empty
+        self.raw_flush()?;
+
+        self.udr.write(|w| w.bits(byte));
+        Ok(())
+    }
+
+    fn raw_read(&mut self) -> avr_hal_generic::nb::Result<u8, crate::usart::Error> {
+        let status = self.ucsra.read();
+        if status.rxc().bit_is_clear() {
+            return Err(avr_hal_generic::nb::Error::WouldBlock);
+        }
+
+        // The error flags belong to the byte in the receive buffer, so they must be read before
+        // it.  Reading the data register clears them again.
+        let byte = self.udr.read().bits();
+        match crate::usart::Error::from_flags(
+            status.fe().bit_is_set(),
+            status.dor().bit_is_set(),
+            status.upe().bit_is_set(),
+        ) {
+            Some(e) => Err(avr_hal_generic::nb::Error::Other(e)),
+            None => Ok(byte),
+        }
+    }
+
+    fn raw_read_ready(&mut self) -> bool {
+        self.ucsra.read().rxc().bit_is_set()
+    }
+
+    fn raw_write_9bit(
+        &mut self,
+        word: u16,
+    ) -> avr_hal_generic::nb::Result<(), core::convert::Infallible> {
+        // Call flush to make sure the data-register is empty
+        self.raw_flush()?;
+
+        // The 9th bit must be written before the low byte.
+        self.ucsrb.modify(|_, w| w.txb8().bit(word & 0x100 != 0));
+        self.udr.write(|w| w.bits(word as u8));
+        Ok(())
+    }
+
+    fn raw_read_9bit(&mut self) -> avr_hal_generic::nb::Result<u16, crate::usart::Error> {
+        let status = self.ucsra.read();
+        if status.rxc().bit_is_clear() {
+            return Err(avr_hal_generic::nb::Error::WouldBlock);
+        }
+
+        // The error flags and the 9th bit must be read before the low byte.
+        let high = self.ucsrb.read().rxb8().bit() as u16;
+        let word = high << 8 | self.udr.read().bits() as u16;
+        match crate::usart::Error::from_flags(
+            status.fe().bit_is_set(),
+            status.dor().bit_is_set(),
+            status.upe().bit_is_set(),
+        ) {
+            Some(e) => Err(avr_hal_generic::nb::Error::Other(e)),
+            None => Ok(word),
+        }
+    }
+
+    fn raw_tx_complete(&mut self) -> bool {
+        self.ucsra.read().txc().bit_is_set()
+    }
+
+    fn raw_clear_tx_complete(&mut self) {
+        // TXC is cleared by writing a one.  Only U2X and MPCM are preserved as the other flags
+        // must be written as zero.
+        self.ucsra
+            .modify(|r, w| unsafe { w.bits(r.bits() & 0x03 | 0x40) });
+    }
+
+    fn raw_interrupt(&mut self, event: crate::usart::Event, state: bool) {
+        match event {
+            crate::usart::Event::RxComplete => self.ucsrb.modify(|_, w| w.rxcie().bit(state)),
+            crate::usart::Event::TxComplete => self.ucsrb.modify(|_, w| w.txcie().bit(state)),
+            crate::usart::Event::DataRegisterEmpty => {
+                self.ucsrb.modify(|_, w| w.udrie().bit(state))
+            }
+        }
+    }
+}
+
+/// LIN/UART of the ATtiny167 in UART mode
+///
+/// The LIN/UART only supports 8 data bits and one stop bit, 9-bit words are truncated to their
+/// low byte.  It has no data register empty interrupt: [`Event::DataRegisterEmpty`] is mapped to
+/// the same interrupt (`LIN_TC`) as [`Event::TxComplete`], which only fires after a byte was
+/// sent.
+#[cfg(feature = "attiny167")]
+pub type LinUart<CLOCK> = Usart<
+    crate::pac::LINUART,
+    port::Pin<port::mode::Input, port::PA0>,
+    port::Pin<port::mode::Output, port::PA1>,
+    CLOCK,
+>;
+
+#[cfg(feature = "attiny167")]
+mod lin {
+    // LINCR bits
+    pub const LSWRES: u8 = 1 << 7;
+    pub const LENA: u8 = 1 << 3;
+    pub const LCMD_TX_HEADER: u8 = 0b001;
+    pub const LCMD_UART_RX_TX: u8 = 0b111;
+
+    // LINSIR bits
+    pub const LBUSY: u8 = 1 << 4;
+    pub const LERR: u8 = 1 << 3;
+    pub const LIDOK: u8 = 1 << 2;
+    pub const LTXOK: u8 = 1 << 1;
+    pub const LRXOK: u8 = 1 << 0;
+
+    // LINENIR bits
+    pub const LENTXOK: u8 = 1 << 1;
+    pub const LENRXOK: u8 = 1 << 0;
+
+    // LINERR bits
+    pub const LOVERR: u8 = 1 << 5;
+    pub const LFERR: u8 = 1 << 4;
+    pub const LPERR: u8 = 1 << 2;
+
+    // LINBTR bits
+    pub const LDISR: u8 = 1 << 7;
+}
+
+#[cfg(feature = "attiny167")]
+fn lin_read(p: &mut crate::pac::LINUART) -> avr_hal_generic::nb::Result<u8, crate::usart::Error> {
+    let status = p.linsir.read().bits();
+    if status & lin::LRXOK == 0 {
+        return Err(avr_hal_generic::nb::Error::WouldBlock);
+    }
+
+    // The error flags belong to the byte in the receive buffer, so they must be read before it.
+    // Clearing LERR also clears all flags in LINERR.
+    let errors = p.linerr.read().bits();
+    let byte = p.lindat.read().bits();
+    if status & lin::LERR != 0 {
+        p.linsir.write(|w| unsafe { w.bits(lin::LERR) });
+    }
+    match crate::usart::Error::from_flags(
+        errors & lin::LFERR != 0,
+        errors & lin::LOVERR != 0,
+        errors & lin::LPERR != 0,
+    ) {
+        Some(e) => Err(avr_hal_generic::nb::Error::Other(e)),
+        None => Ok(byte),
+    }
+}
+
+#[cfg(feature = "attiny167")]
+impl
+    crate::usart::UsartOps<
+        crate::Attiny,
+        crate::port::Pin<crate::port::mode::Input, port::PA0>,
+        crate::port::Pin<crate::port::mode::Output, port::PA1>,
+    > for crate::pac::LINUART
+{
+    fn raw_init<CLOCK>(&mut self, settings: crate::usart::UsartSettings<CLOCK>) {
+        let baudrate = settings.baudrate;
+        let frame = settings.frame_format;
+        assert!(
+            frame.data_bits == DataBits::Eight && frame.stop_bits == StopBits::One,
+            "LIN/UART only supports 8 data bits and one stop bit"
+        );
+
+        // The bit timing can only be changed while the controller is disabled.  16 (or 8 for
+        // double speed) samples per bit give the same baudrate as the UBRR of a traditional USART.
+        self.lincr.write(|w| unsafe { w.bits(lin::LSWRES) });
+        let samples = if baudrate.u2x { 8 } else { 16 };
+        self.linbtr
+            .write(|w| unsafe { w.bits(lin::LDISR | samples) });
+        self.linbrrl.write(|w| w.bits(baudrate.ubrr as u8));
+        self.linbrrh
+            .write(|w| unsafe { w.bits((baudrate.ubrr >> 8) as u8) });
+
+        // LCONF selects the parity in UART mode.
+        let lconf = match frame.parity {
+            Parity::None => 0b00,
+            Parity::Even => 0b01,
+            Parity::Odd => 0b10,
+        };
+        self.lincr
+            .write(|w| unsafe { w.bits(lin::LENA | lconf << 4 | lin::LCMD_UART_RX_TX) });
+    }
+
+    fn raw_deinit(&mut self) {
+        // Wait for any ongoing transfer to finish.
+        avr_hal_generic::nb::block!(self.raw_flush()).ok();
+        self.lincr.reset();
+    }
+
+    fn raw_flush(&mut self) -> avr_hal_generic::nb::Result<(), core::convert::Infallible> {
+        // LBUSY is also set while a byte is received, so it only tells that no transmission can
+        // be in progress when it is clear.  Otherwise, LTXOK (cleared by `raw_write()`) tells
+        // whether the last byte was sent completely.
+        let status = self.linsir.read().bits();
+        if status & lin::LBUSY != 0 && status & lin::LTXOK == 0 {
+            Err(avr_hal_generic::nb::Error::WouldBlock)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn raw_write(
+        &mut self,
+        byte: u8,
+    ) -> avr_hal_generic::nb::Result<(), core::convert::Infallible> {
+        // There is no separate transmit buffer, wait until the previous byte was sent.
+        self.raw_flush()?;
+
+        self.linsir.write(|w| unsafe { w.bits(lin::LTXOK) });
+        self.lindat.write(|w| w.bits(byte));
+        Ok(())
+    }
+
+    fn raw_read(&mut self) -> avr_hal_generic::nb::Result<u8, crate::usart::Error> {
+        lin_read(self)
+    }
+
+    fn raw_read_ready(&mut self) -> bool {
+        self.linsir.read().bits() & lin::LRXOK != 0
+    }
+
+    fn raw_write_9bit(
+        &mut self,
+        word: u16,
+    ) -> avr_hal_generic::nb::Result<(), core::convert::Infallible> {
+        // 9-bit frames are not supported by the LIN/UART.
+        self.raw_write(word as u8)
+    }
+
+    fn raw_read_9bit(&mut self) -> avr_hal_generic::nb::Result<u16, crate::usart::Error> {
+        // 9-bit frames are not supported by the LIN/UART.
+        lin_read(self).map(u16::from)
+    }
+
+    fn raw_tx_complete(&mut self) -> bool {
+        self.linsir.read().bits() & lin::LTXOK != 0
+    }
+
+    fn raw_clear_tx_complete(&mut self) {
+        // LTXOK is cleared by writing a one, the other flags are left untouched by writing zero.
+        self.linsir.write(|w| unsafe { w.bits(lin::LTXOK) });
+    }
+
+    fn raw_interrupt(&mut self, event: crate::usart::Event, state: bool) {
+        let mask = match event {
+            crate::usart::Event::RxComplete => lin::LENRXOK,
+            crate::usart::Event::TxComplete | crate::usart::Event::DataRegisterEmpty => {
+                lin::LENTXOK
+            }
+        };
+        self.linenir.modify(|r, w| unsafe {
+            w.bits(if state {
+                r.bits() | mask
+            } else {
+                r.bits() & !mask
+            })
+        });
+    }
+}
+
+#[cfg(feature = "attiny167")]
+impl
+    crate::usart::UsartLinOps<
+        crate::Attiny,
+        crate::port::Pin<crate::port::mode::Input, port::PA0>,
+        crate::port::Pin<crate::port::mode::Output, port::PA1>,
+    > for crate::pac::LINUART
+{
+    fn raw_send_lin_header(&mut self, identifier: u8) {
+        avr_hal_generic::nb::block!(self.raw_flush()).ok();
+
+        // Switch to LIN mode (LIN 2.1, LCONF = 0) and request a header transmission.  The header
+        // is sent as soon as the identifier is written.
+        // Keep LCONF, which selects the parity of the UART mode.
+        let uart_control = self.lincr.read().bits();
+        self.lincr
+            .write(|w| unsafe { w.bits(lin::LENA | lin::LCMD_TX_HEADER) });
+        self.linidr.write(|w| unsafe { w.bits(identifier & 0x3f) });
+        while self.linsir.read().bits() & lin::LIDOK == 0 {}
+        self.linsir.write(|w| unsafe { w.bits(lin::LIDOK) });
+
+        self.lincr.write(|w| unsafe { w.bits(uart_control) });
+    }
+}
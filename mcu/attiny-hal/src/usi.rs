@@ -0,0 +1,36 @@
+//! Universal Serial Interface (USI)
+//!
+//! The USI is used by the [`i2c::I2c`][crate::i2c::I2c],
+//! [`i2c::I2cTarget`][crate::i2c::I2cTarget] and [`spi::UsiSpi`][crate::spi::UsiSpi] drivers.
+//! Check their documentation for details.
+
+#[allow(unused_imports)]
+use crate::port;
+pub use avr_hal_generic::usi::*;
+
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_usi! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::USI,
+    di: port::PA6,
+    dout: port::PA5,
+    usck: port::PA4,
+}
+
+#[cfg(feature = "attiny85")]
+avr_hal_generic::impl_usi! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::USI,
+    di: port::PB0,
+    dout: port::PB1,
+    usck: port::PB2,
+}
+
+#[cfg(feature = "attiny2313")]
+avr_hal_generic::impl_usi! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::USI,
+    di: port::PB5,
+    dout: port::PB6,
+    usck: port::PB7,
+}